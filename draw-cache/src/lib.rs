@@ -72,6 +72,8 @@ struct LossyGlyphInfo {
     /// `u16` is enough as subpixel position `[-0.5, 0.5]` converted to `[0, 1]`
     ///  divided by the min `position_tolerance` (`0.001`) is small.
     offset_over_tolerance: (u16, u16),
+    /// Drawn rotated 90° clockwise about the glyph position
+    sideways: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -497,7 +499,8 @@ pub struct DrawCache {
     space_start_for_end: FxHashMap<u32, u32>,
     /// Mapping of row gaps top -> bottom
     space_end_for_start: FxHashMap<u32, u32>,
-    queue: Vec<(usize, Glyph)>,
+    /// Queued font ids, glyphs & whether they are sideways
+    queue: Vec<(usize, Glyph, bool)>,
     all_glyphs: FxHashMap<LossyGlyphInfo, TextureRowGlyphIndex>,
     pad_glyphs: bool,
     align_4x4: bool,
@@ -531,7 +534,17 @@ impl DrawCache {
     /// is used to disambiguate glyphs from different fonts. The user should
     /// ensure that `font_id` is unique to the font the glyph is from.
     pub fn queue_glyph(&mut self, font_id: usize, glyph: Glyph) {
-        self.queue.push((font_id, glyph));
+        self.queue.push((font_id, glyph, false));
+    }
+
+    /// Queue a glyph drawn rotated 90° clockwise about its position, e.g. sideways Latin in
+    /// vertical text, for caching by the next call to `cache_queued`. As
+    /// [`queue_glyph`](#method.queue_glyph) the `font_id` should be unique to the font the
+    /// glyph is from.
+    ///
+    /// The glyph is cached separately to the same glyph queued upright.
+    pub fn queue_glyph_sideways(&mut self, font_id: usize, glyph: Glyph) {
+        self.queue.push((font_id, glyph, true));
     }
 
     /// Clears the cache. Does not affect the glyph queue.
@@ -562,7 +575,7 @@ impl DrawCache {
     }
 
     /// Returns glyph info with accuracy according to the set tolerances.
-    fn lossy_info_for(&self, font_id: usize, glyph: &Glyph) -> LossyGlyphInfo {
+    fn lossy_info_for(&self, font_id: usize, glyph: &Glyph, sideways: bool) -> LossyGlyphInfo {
         let scale = glyph.scale;
         let offset = normalised_offset_from_position(glyph.position);
 
@@ -578,6 +591,7 @@ impl DrawCache {
                 ((offset.x + 0.5) / self.position_tolerance + 0.5) as u16,
                 ((offset.y + 0.5) / self.position_tolerance + 0.5) as u16,
             ),
+            sideways,
         }
     }
 
@@ -618,8 +632,8 @@ impl DrawCache {
 
                 // divide glyphs into texture rows where a matching glyph texture
                 // already exists & glyphs where new textures must be cached
-                for (font_id, ref glyph) in &self.queue {
-                    let glyph_info = self.lossy_info_for(*font_id, glyph);
+                for (font_id, ref glyph, sideways) in &self.queue {
+                    let glyph_info = self.lossy_info_for(*font_id, glyph, *sideways);
                     if let Some((row, ..)) = self.all_glyphs.get(&glyph_info) {
                         in_use_rows.insert(*row);
                     } else {
@@ -638,17 +652,31 @@ impl DrawCache {
             let mut uncached_outlined: Vec<_> = uncached_glyphs
                 .into_iter()
                 .filter_map(|(info, glyph)| {
-                    Some((info, fonts[info.font_id].outline_glyph(glyph.clone())?))
+                    let glyph = match info.sideways {
+                        // outlined upright with the subpixel offsets of the rotated position
+                        true => Glyph {
+                            position: point(glyph.position.y, -glyph.position.x),
+                            ..glyph.clone()
+                        },
+                        false => glyph.clone(),
+                    };
+                    Some((info, fonts[info.font_id].outline_glyph(glyph)?))
                 })
                 .collect();
 
+            // texture width & height of the, possibly rotated, glyph
+            let tex_size = |info: &LossyGlyphInfo, outlined: &OutlinedGlyph| {
+                let bounds = outlined.px_bounds();
+                match info.sideways {
+                    true => (bounds.height() as u32, bounds.width() as u32),
+                    false => (bounds.width() as u32, bounds.height() as u32),
+                }
+            };
+
             // tallest first gives better packing
             // can use 'sort_unstable' as order of equal elements is unimportant
-            uncached_outlined.sort_unstable_by(|(_, ga), (_, gb)| {
-                gb.px_bounds()
-                    .height()
-                    .partial_cmp(&ga.px_bounds().height())
-                    .unwrap_or(core::cmp::Ordering::Equal)
+            uncached_outlined.sort_unstable_by_key(|(info, outlined)| {
+                core::cmp::Reverse(tex_size(info, outlined).1)
             });
 
             self.all_glyphs.reserve(uncached_outlined.len());
@@ -658,10 +686,11 @@ impl DrawCache {
                 let bounds = outlined.px_bounds();
 
                 let (unaligned_width, unaligned_height) = {
+                    let (width, height) = tex_size(&glyph_info, &outlined);
                     if self.pad_glyphs {
-                        (bounds.width() as u32 + 2, bounds.height() as u32 + 2)
+                        (width + 2, height + 2)
                     } else {
-                        (bounds.width() as u32, bounds.height() as u32)
+                        (width, height)
                     }
                 };
                 let (aligned_width, aligned_height) = if self.align_4x4 {
@@ -768,26 +797,30 @@ impl DrawCache {
                 };
 
                 let g = outlined.glyph();
+                let min = bounds.min - g.position;
+                let max = bounds.max - g.position;
+                let bounds_minus_position_over_scale = match glyph_info.sideways {
+                    // (x, y) relative to the position rotates clockwise to (-y, x)
+                    true => Rect {
+                        min: point(-max.y / g.scale.y, min.x / g.scale.x),
+                        max: point(-min.y / g.scale.y, max.x / g.scale.x),
+                    },
+                    false => Rect {
+                        min: point(min.x / g.scale.x, min.y / g.scale.y),
+                        max: point(max.x / g.scale.x, max.y / g.scale.y),
+                    },
+                };
 
                 // add the glyph to the row
                 row.glyphs.push(GlyphTexInfo {
                     glyph_info,
                     tex_coords: unaligned_tex_coords,
-                    bounds_minus_position_over_scale: Rect {
-                        min: point(
-                            (bounds.min.x - g.position.x) / g.scale.x,
-                            (bounds.min.y - g.position.y) / g.scale.y,
-                        ),
-                        max: point(
-                            (bounds.max.x - g.position.x) / g.scale.x,
-                            (bounds.max.y - g.position.y) / g.scale.y,
-                        ),
-                    },
+                    bounds_minus_position_over_scale,
                 });
                 row.width += aligned_width;
                 in_use_rows.insert(row_top);
 
-                draw_and_upload.push((aligned_tex_coords, outlined));
+                draw_and_upload.push((aligned_tex_coords, outlined, glyph_info.sideways));
 
                 self.all_glyphs
                     .insert(glyph_info, (row_top, row.glyphs.len() as u32 - 1));
@@ -850,7 +883,7 @@ impl DrawCache {
     #[cfg(not(target_arch = "wasm32"))]
     fn draw_and_upload<U>(
         &self,
-        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph)>,
+        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph, bool)>,
         uploader: &mut U,
     ) where
         U: FnMut(Rectangle<u32>, &[u8]),
@@ -866,7 +899,7 @@ impl DrawCache {
         let work_magnitude = {
             let tallest_h = draw_and_upload
                 .first()
-                .map(|(r, _)| r.height() as usize)
+                .map(|(r, ..)| r.height() as usize)
                 .unwrap_or(0);
             glyph_count
                 .saturating_mul(tallest_h)
@@ -909,8 +942,8 @@ impl DrawCache {
                     });
 
                     match task {
-                        Some((tex_coords, glyph)) => {
-                            let pixels = draw_glyph(tex_coords, &glyph, pad_glyphs);
+                        Some((tex_coords, glyph, sideways)) => {
+                            let pixels = draw_glyph(tex_coords, &glyph, sideways, pad_glyphs);
                             to_main.send((tex_coords, pixels)).unwrap();
                         }
                         None => break,
@@ -933,8 +966,8 @@ impl DrawCache {
                 });
 
                 match task {
-                    Some((tex_coords, glyph)) => {
-                        let pixels = draw_glyph(tex_coords, &glyph, pad_glyphs);
+                    Some((tex_coords, glyph, sideways)) => {
+                        let pixels = draw_glyph(tex_coords, &glyph, sideways, pad_glyphs);
                         uploader(tex_coords, pixels.as_slice());
                     }
                     None if workers_finished => break,
@@ -958,7 +991,7 @@ impl DrawCache {
     #[inline]
     fn draw_and_upload<U>(
         &self,
-        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph)>,
+        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph, bool)>,
         uploader: &mut U,
     ) where
         U: FnMut(Rectangle<u32>, &[u8]),
@@ -970,13 +1003,13 @@ impl DrawCache {
     #[inline]
     fn draw_and_upload_1_thread<U>(
         &self,
-        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph)>,
+        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph, bool)>,
         uploader: &mut U,
     ) where
         U: FnMut(Rectangle<u32>, &[u8]),
     {
-        for (tex_coords, outlined) in draw_and_upload {
-            let pixels = draw_glyph(tex_coords, &outlined, self.pad_glyphs);
+        for (tex_coords, outlined, sideways) in draw_and_upload {
+            let pixels = draw_glyph(tex_coords, &outlined, sideways, self.pad_glyphs);
             uploader(tex_coords, pixels.as_slice());
         }
     }
@@ -996,7 +1029,28 @@ impl DrawCache {
     /// Ensure that `font_id` matches the `font_id` that was passed to
     /// `queue_glyph` with this `glyph`.
     pub fn rect_for(&self, font_id: usize, glyph: &Glyph) -> Option<TextureCoords> {
-        let (row, index) = self.all_glyphs.get(&self.lossy_info_for(font_id, glyph))?;
+        self.rect_for_orientation(font_id, glyph, false)
+    }
+
+    /// Retrieves the texture coordinates & pixel-space coordinates of the quad for a glyph
+    /// queued with [`queue_glyph_sideways`](#method.queue_glyph_sideways), as
+    /// [`rect_for`](#method.rect_for).
+    ///
+    /// The texture holds the glyph rotated 90° clockwise about its position, so the quad is
+    /// drawn as any other.
+    pub fn rect_for_sideways(&self, font_id: usize, glyph: &Glyph) -> Option<TextureCoords> {
+        self.rect_for_orientation(font_id, glyph, true)
+    }
+
+    fn rect_for_orientation(
+        &self,
+        font_id: usize,
+        glyph: &Glyph,
+        sideways: bool,
+    ) -> Option<TextureCoords> {
+        let (row, index) = self
+            .all_glyphs
+            .get(&self.lossy_info_for(font_id, glyph, sideways))?;
 
         let (tex_width, tex_height) = (self.width as f32, self.height as f32);
 
@@ -1019,14 +1073,19 @@ impl DrawCache {
             ),
         };
 
+        // sideways glyph widths are scaled by the vertical scale & heights by the horizontal
+        let (scale_x, scale_y) = match sideways {
+            true => (glyph.scale.y, glyph.scale.x),
+            false => (glyph.scale.x, glyph.scale.y),
+        };
         let equivalent_bounds = Rect {
             min: point(
-                bounds_minus_position_over_scale.min.x * glyph.scale.x,
-                bounds_minus_position_over_scale.min.y * glyph.scale.y,
+                bounds_minus_position_over_scale.min.x * scale_x,
+                bounds_minus_position_over_scale.min.y * scale_y,
             ) + glyph.position,
             max: point(
-                bounds_minus_position_over_scale.max.x * glyph.scale.x,
-                bounds_minus_position_over_scale.max.y * glyph.scale.y,
+                bounds_minus_position_over_scale.max.x * scale_x,
+                bounds_minus_position_over_scale.max.y * scale_y,
             ) + glyph.position,
         };
 
@@ -1035,16 +1094,24 @@ impl DrawCache {
 }
 
 #[inline]
-fn draw_glyph(tex_coords: Rectangle<u32>, glyph: &OutlinedGlyph, pad_glyphs: bool) -> ByteArray2d {
+fn draw_glyph(
+    tex_coords: Rectangle<u32>,
+    glyph: &OutlinedGlyph,
+    sideways: bool,
+    pad_glyphs: bool,
+) -> ByteArray2d {
     let mut pixels = ByteArray2d::zeros(tex_coords.height() as usize, tex_coords.width() as usize);
-    if pad_glyphs {
+    // `+ 1` accounts for top/left glyph padding
+    let pad = pad_glyphs as usize;
+    if sideways {
+        // rotated clockwise, the top of the glyph is the right of the texture
+        let height = glyph.px_bounds().height() as usize;
         glyph.draw(|x, y, v| {
-            // `+ 1` accounts for top/left glyph padding
-            pixels[(y as usize + 1, x as usize + 1)] = (v * 255.0) as u8;
+            pixels[(x as usize + pad, height - 1 - y as usize + pad)] = (v * 255.0) as u8;
         });
     } else {
         glyph.draw(|x, y, v| {
-            pixels[(y as usize, x as usize)] = (v * 255.0) as u8;
+            pixels[(y as usize + pad, x as usize + pad)] = (v * 255.0) as u8;
         });
     }
     pixels
@@ -1124,6 +1191,35 @@ mod test {
         cache.rect_for(0, &large_right).unwrap();
     }
 
+    #[test]
+    fn sideways_rect() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let glyph = font
+            .glyph_id('l')
+            .with_scale_and_position(16.0, point(20.0, 10.0));
+
+        let mut cache = DrawCache::builder()
+            .dimensions(32, 32)
+            .pad_glyphs(false)
+            .build();
+
+        cache.queue_glyph(0, glyph.clone());
+        cache.queue_glyph_sideways(0, glyph.clone());
+        cache.cache_queued(&[&font], |_, _| {}).unwrap();
+
+        let (upright_tex, upright) = cache.rect_for(0, &glyph).unwrap();
+        let (sideways_tex, sideways) = cache.rect_for_sideways(0, &glyph).unwrap();
+        assert_ne!(upright_tex, sideways_tex);
+
+        // drawn rotated 90° clockwise about the glyph position
+        let rotated = VerticalOrientation::Sideways.rotate_rect(upright, glyph.position);
+        assert_relative_eq!(sideways.min.x, rotated.min.x);
+        assert_relative_eq!(sideways.min.y, rotated.min.y);
+        assert_relative_eq!(sideways.max.x, rotated.max.x);
+        assert_relative_eq!(sideways.max.y, rotated.max.y);
+        assert!(sideways.width() > sideways.height(), "{sideways:?}");
+    }
+
     #[test]
     fn lossy_info() {
         let font = FontRef::try_from_slice(FONT).unwrap();
@@ -1143,15 +1239,15 @@ mod test {
         let miss_2 = gid.with_scale_and_position(12.0, point(0.0, 0.0));
         let miss_3 = gid.with_scale_and_position(9.91, point(0.3, 0.0));
 
-        let small_info = cache.lossy_info_for(0, &small);
+        let small_info = cache.lossy_info_for(0, &small, false);
 
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_1));
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_2));
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_3));
+        assert_eq!(small_info, cache.lossy_info_for(0, &match_1, false));
+        assert_eq!(small_info, cache.lossy_info_for(0, &match_2, false));
+        assert_eq!(small_info, cache.lossy_info_for(0, &match_3, false));
 
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_1));
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_2));
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_3));
+        assert_ne!(small_info, cache.lossy_info_for(0, &miss_1, false));
+        assert_ne!(small_info, cache.lossy_info_for(0, &miss_2, false));
+        assert_ne!(small_info, cache.lossy_info_for(0, &miss_3, false));
     }
 
     #[test]
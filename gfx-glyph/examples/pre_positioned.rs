@@ -107,6 +107,7 @@ impl App {
                 text: include_str!("lipsum.txt"),
                 scale: PxScale::from(30.0),
                 font_id: FontId(0),
+                ..gfx_glyph::SectionText::default()
             }],
         );
 
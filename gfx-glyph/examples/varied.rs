@@ -182,6 +182,7 @@ impl App {
                                 color: [0.9, 0.3, 0.3, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                         Text {
                             text: "dolorem",
@@ -191,6 +192,7 @@ impl App {
                                 color: [0.3, 0.9, 0.3, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                         Text {
                             text: " Iriure vocibus est te, natum delicata dignissim pri ea.",
@@ -200,6 +202,7 @@ impl App {
                                 color: [0.3, 0.3, 0.9, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                     ],
                     layout: Layout::default().v_align(VerticalAlign::Center),
@@ -217,6 +220,7 @@ impl App {
                                 color: [0.3, 0.3, 0.9, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                         Text {
                             text: " eruditi habemus qualisque eam an. No atqui apeirian phaedrum pri ex, hinc omnes sapientem. ",
@@ -226,6 +230,7 @@ impl App {
                                 color: [0.9, 0.3, 0.3, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                         Text {
                             text: "Eu facilisi maluisset eos.",
@@ -235,6 +240,7 @@ impl App {
                                 color: [0.3, 0.9, 0.3, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                         Text {
                             text: " ius nullam impetus. ",
@@ -244,6 +250,7 @@ impl App {
                                 color: [0.9, 0.9, 0.3, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                         Text {
                             text: "Ut quo elitr viderer constituam, pro omnesque forensibus at. Timeam scaevola mediocrem ut pri, te pro congue delicatissimi. Mei wisi nostro imperdiet ea, ridens salutatus per no, ut viris partem disputationi sit. Exerci eripuit referrentur vix at, sale mediocrem repudiare per te, modus admodum an eam. No vocent indoctum vis, ne quodsi patrioque vix. Vocent labores omittam et usu.",
@@ -253,6 +260,7 @@ impl App {
                                 color: [0.8, 0.3, 0.5, 1.0],
                                 z: 0.0,
                             },
+                            ..Text::default()
                         },
                     ],
                     layout: Layout::default().h_align(HorizontalAlign::Right).v_align(VerticalAlign::Center),
@@ -46,9 +46,10 @@ mod draw_builder;
 
 pub use crate::{builder::*, draw_builder::*};
 pub use glyph_brush::{
-    ab_glyph, legacy, BuiltInLineBreaker, Extra, FontId, GlyphCruncher, GlyphPositioner,
-    HorizontalAlign, Layout, LineBreak, LineBreaker, OwnedSection, OwnedText, Section,
-    SectionGeometry, SectionGlyph, SectionGlyphIter, SectionText, Text, VerticalAlign,
+    ab_glyph, legacy, BuiltInLineBreaker, Caret, CaretAffinity, Extra, FontId, GlyphCruncher,
+    GlyphPositioner, HorizontalAlign, IntrinsicWidths, Layout, LineBreak, LineBreaker,
+    MeasuredWords, OwnedSection, OwnedText, Section, SectionGeometry, SectionGlyph,
+    SectionGlyphIter, SectionLine, SectionText, Text, VerticalAlign,
 };
 
 use crate::pipe::{glyph_pipe, GlyphVertex, IntoDimensions, RawAndFormat};
@@ -196,6 +197,34 @@ where
             .glyphs_custom_layout(section, custom_layout)
     }
 
+    #[inline]
+    fn section_lines_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> &'b [SectionLine]
+    where
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        self.glyph_brush
+            .section_lines_custom_layout(section, custom_layout)
+    }
+
+    #[inline]
+    fn measured_words_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<&'b MeasuredWords>
+    where
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        self.glyph_brush
+            .measured_words_custom_layout(section, custom_layout)
+    }
+
     #[inline]
     fn fonts(&self) -> &[F] {
         self.glyph_brush.fonts()
@@ -0,0 +1,623 @@
+use super::*;
+use std::{hash::Hash, ops::Range};
+
+/// Text position between characters, e.g. of a text cursor, see
+/// [`GlyphCruncher::caret_at_point`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Caret {
+    /// Index of the [`Section::text`] the caret is in.
+    pub section_index: usize,
+    /// Byte index of the character following the caret within the section text.
+    pub byte_index: usize,
+    /// Which of the characters either side of the caret it is positioned by.
+    pub affinity: CaretAffinity,
+}
+
+impl Caret {
+    /// Returns a caret before the character at `byte_index` with leading affinity.
+    #[inline]
+    pub fn new(section_index: usize, byte_index: usize) -> Self {
+        Self {
+            section_index,
+            byte_index,
+            affinity: CaretAffinity::Leading,
+        }
+    }
+
+    #[inline]
+    fn position(&self) -> (usize, usize) {
+        (self.section_index, self.byte_index)
+    }
+}
+
+/// Disambiguates carets at a position shared by two lines, i.e. where text wraps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CaretAffinity {
+    /// Caret is positioned by the following character, e.g. at the start of a wrapped line.
+    #[default]
+    Leading,
+    /// Caret is positioned by the preceding character, e.g. at the end of a wrapped line.
+    Trailing,
+}
+
+/// Caret geometry of laid out section glyphs & lines.
+///
+/// In lines with right-to-left text carets are positioned by the direction of the visual
+/// run of the adjacent character.
+pub(crate) struct SectionCarets<'a, F, X> {
+    pub fonts: &'a [F],
+    pub section: &'a Section<'a, X>,
+    pub glyphs: &'a [SectionGlyph],
+    pub lines: &'a [SectionLine],
+}
+
+impl<F: Font, X> SectionCarets<'_, F, X> {
+    /// Returns the caret nearest to `point`.
+    pub fn caret_at(&self, point: Point) -> Option<Caret> {
+        let line = self
+            .lines
+            .iter()
+            .find(|line| point.y < line.baseline - line.descent)
+            .or_else(|| self.lines.last())?;
+        // lines flowing around exclusions share a row, pick the last starting before `point`
+        let top = line.baseline - line.ascent;
+        let line = self
+            .lines
+            .iter()
+            .filter(|l| (l.baseline - l.ascent - top).abs() < 1e-3)
+            .rfind(|l| l.left <= point.x)
+            .unwrap_or(line);
+
+        let clusters = LineClusters::new(self, line);
+        // the logical position at the nearest visual edge of the hit cluster
+        let hit = clusters
+            .visual
+            .iter()
+            .position(|c| point.x < (c.left + c.right) / 2.0);
+        let pos = match hit {
+            Some(idx) if clusters.visual[idx].rtl => clusters.after(idx),
+            Some(idx) => clusters.visual[idx].pos,
+            // after the last cluster
+            None => match clusters.visual.len().checked_sub(1) {
+                Some(idx) if clusters.visual[idx].rtl => clusters.visual[idx].pos,
+                Some(idx) => clusters.after(idx),
+                None => line.start,
+            },
+        };
+
+        let caret = Caret::new(pos.0, pos.1);
+        Some(match line.hard_break || pos < line.end {
+            true => caret,
+            // end of a wrapped line
+            false => Caret {
+                affinity: CaretAffinity::Trailing,
+                ..caret
+            },
+        })
+    }
+
+    /// Returns the zero width rectangle of `caret`, spanning its line's ascent & descent.
+    pub fn caret_rect(&self, caret: Caret) -> Option<Rect> {
+        let pos = caret.position();
+        let line_idx = self
+            .lines
+            .iter()
+            .position(|line| pos < line.end)
+            .map(|idx| match idx {
+                // end of a wrapped line
+                idx if idx > 0
+                    && caret.affinity == CaretAffinity::Trailing
+                    && pos == self.lines[idx].start
+                    && !self.lines[idx - 1].hard_break =>
+                {
+                    idx - 1
+                }
+                idx => idx,
+            })
+            .or_else(|| self.lines.len().checked_sub(1))?;
+
+        let line = &self.lines[line_idx];
+        let x = self.caret_x(line, pos);
+        Some(Rect {
+            min: point(x, line.baseline - line.ascent),
+            max: point(x, line.baseline - line.descent),
+        })
+    }
+
+    /// Returns a rectangle for each visually contiguous run of the characters in `range`
+    /// on each line, so mixed direction lines may have multiple rectangles.
+    ///
+    /// Selected lines without selected glyphs, like empty lines, have zero width rectangles.
+    pub fn selection_rects(&self, range: Range<(usize, usize)>) -> Vec<Rect> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+        let mut rects = Vec::new();
+        for line in self
+            .lines
+            .iter()
+            .filter(|line| range.start < line.end && line.start < range.end)
+        {
+            let rect = |left, right| Rect {
+                min: point(left, line.baseline - line.ascent),
+                max: point(right, line.baseline - line.descent),
+            };
+            let line_rects = rects.len();
+            let mut run: Option<(f32, f32)> = None;
+            for c in LineClusters::new(self, line).visual {
+                if range.contains(&c.pos) {
+                    let (left, _) = run.get_or_insert((c.left, c.right));
+                    run = Some((*left, c.right));
+                } else if let Some((left, right)) = run.take() {
+                    rects.push(rect(left, right));
+                }
+            }
+            if let Some((left, right)) = run {
+                rects.push(rect(left, right));
+            }
+            if rects.len() == line_rects {
+                let x = self.caret_x(line, range.start.max(line.start));
+                rects.push(rect(x, x));
+            }
+        }
+        rects
+    }
+
+    /// Returns the screen x position of the caret at `pos` within `line`.
+    ///
+    /// This is the leading edge of the following character, or the trailing edge of the
+    /// logically last character if there is none.
+    fn caret_x(&self, line: &SectionLine, pos: (usize, usize)) -> f32 {
+        let clusters = LineClusters::new(self, line);
+        let following = clusters
+            .logical
+            .iter()
+            .map(|&idx| &clusters.visual[idx])
+            .find(|c| c.pos >= pos);
+        match following {
+            Some(c) if c.rtl => c.right,
+            Some(c) => c.left,
+            None => match clusters.logical.last().map(|&idx| &clusters.visual[idx]) {
+                Some(c) if c.rtl => c.left,
+                Some(c) => c.right,
+                None => line.left,
+            },
+        }
+    }
+
+    /// Returns the `(section_index, byte_index)` following the line's text, before any
+    /// hard line break.
+    fn text_end(&self, line: &SectionLine) -> (usize, usize) {
+        if !line.hard_break {
+            return line.end;
+        }
+        let (mut section_index, mut byte_index) = line.end;
+        while byte_index == 0 && section_index > line.start.0 {
+            section_index -= 1;
+            byte_index = self.section.text[section_index].text.len();
+        }
+        let Some(text) = self
+            .section
+            .text
+            .get(section_index)
+            .map(|t| &t.text[..byte_index])
+        else {
+            return line.end;
+        };
+        let break_len = match text.chars().next_back() {
+            Some('\n') if text.ends_with("\r\n") => 2,
+            Some(c @ ('\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}')) => {
+                c.len_utf8()
+            }
+            _ => 0,
+        };
+        (section_index, byte_index - break_len)
+    }
+
+    /// Returns the line's glyphs that are from the section text.
+    fn line_glyphs<'b>(&'b self, line: &SectionLine) -> impl Iterator<Item = &'b SectionGlyph> {
+        self.glyphs[line.glyphs.clone()]
+            .iter()
+            .filter(|sg| !sg.is_synthetic())
+    }
+
+    /// Returns the screen left & right of the glyph's advance.
+    fn h_bounds(&self, sg: &SectionGlyph) -> (f32, f32) {
+        let left = sg.glyph.position.x;
+        if let Some(placeholder) = self.section.text[sg.section_index]
+            .placeholder
+            .filter(|_| sg.is_placeholder())
+        {
+            return (left, left + placeholder.width);
+        }
+        let scale_font = self.fonts[sg.font_id].as_scaled(sg.glyph.scale);
+        (left, left + scale_font.h_advance(sg.glyph.id))
+    }
+}
+
+/// A line's glyphs from the same character, e.g. a base glyph & its shaped marks.
+struct Cluster {
+    /// `(section_index, byte_index)` of the character.
+    pos: (usize, usize),
+    left: f32,
+    right: f32,
+    /// Indicates the cluster is in a right-to-left visual run.
+    rtl: bool,
+}
+
+/// Clusters of a line's section text glyphs.
+struct LineClusters {
+    /// Clusters in visual, left-to-right, order.
+    visual: Vec<Cluster>,
+    /// Indices of `visual` clusters in logical order.
+    logical: Vec<usize>,
+    /// `(section_index, byte_index)` following the logically last cluster.
+    end: (usize, usize),
+}
+
+impl LineClusters {
+    fn new<F: Font, X>(carets: &SectionCarets<'_, F, X>, line: &SectionLine) -> Self {
+        let mut visual: Vec<Cluster> = Vec::new();
+        for sg in carets.line_glyphs(line) {
+            let pos = (sg.section_index, sg.byte_index);
+            let (left, right) = carets.h_bounds(sg);
+            match visual.last_mut() {
+                Some(c) if c.pos == pos => {
+                    c.left = c.left.min(left);
+                    c.right = c.right.max(right);
+                }
+                _ => visual.push(Cluster {
+                    pos,
+                    left,
+                    right,
+                    rtl: line.rtl,
+                }),
+            }
+        }
+
+        let mut logical: Vec<_> = (0..visual.len()).collect();
+        logical.sort_by_key(|&idx| visual[idx].pos);
+        let mut rank = vec![0; visual.len()];
+        for (r, &idx) in logical.iter().enumerate() {
+            rank[idx] = r;
+        }
+
+        // visual runs are logically consecutive clusters, ascending in left-to-right runs &
+        // descending in right-to-left runs. Single cluster runs take the line direction.
+        let step_rtl = |idx: usize| match (rank[idx], rank[idx + 1]) {
+            (a, b) if b == a + 1 => Some(false),
+            (a, b) if a == b + 1 => Some(true),
+            _ => None,
+        };
+        let mut start = 0;
+        while start < visual.len() {
+            let mut end = start + 1;
+            if let Some(rtl) = (end < visual.len()).then(|| step_rtl(start)).flatten() {
+                while end < visual.len() && step_rtl(end - 1) == Some(rtl) {
+                    end += 1;
+                }
+                for c in &mut visual[start..end] {
+                    c.rtl = rtl;
+                }
+            }
+            start = end;
+        }
+
+        Self {
+            visual,
+            logical,
+            end: carets.text_end(line),
+        }
+    }
+
+    /// Returns the logical position following the visual cluster at `idx`, the position of
+    /// the logically next cluster or the end of the line's text.
+    fn after(&self, idx: usize) -> (usize, usize) {
+        let r = self.logical.iter().position(|&i| i == idx).unwrap();
+        self.logical
+            .get(r + 1)
+            .map_or(self.end, |&next| self.visual[next].pos)
+    }
+}
+
+/// Returns the cached glyphs & lines of `section` laid out with `layout`.
+pub(crate) fn cached_layout<'a, F, X, G, L>(
+    cruncher: &mut G,
+    section: &Section<'a, X>,
+    layout: &L,
+) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+where
+    F: Font,
+    X: Clone + 'a,
+    G: GlyphCruncher<F, X> + ?Sized,
+    L: GlyphPositioner + Hash,
+{
+    let lines = cruncher
+        .section_lines_custom_layout(section, layout)
+        .to_vec();
+    let glyphs = cruncher
+        .glyphs_custom_layout(section, layout)
+        .cloned()
+        .collect();
+    (glyphs, lines)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+    use std::sync::LazyLock;
+
+    static MONO_FONT: LazyLock<FontArc> = LazyLock::new(|| {
+        FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf") as &[u8]).unwrap()
+    });
+
+    /// Returns a section wrapping into lines "hello ", "world\n", "\n" & "xy", with the
+    /// glyph advance & line height.
+    fn wrapped_section() -> (Section<'static>, f32, f32) {
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let advance = sfont.h_advance(MONO_FONT.glyph_id('w'));
+        let line_h = sfont.height() + sfont.line_gap();
+        let section = Section::default()
+            .add_text(Text::new("hello world\n\nxy").with_scale(16.0))
+            .with_bounds((advance * 8.0, f32::INFINITY));
+        (section, advance, line_h)
+    }
+
+    #[test]
+    fn caret_at_point() {
+        let (section, w, line_h) = wrapped_section();
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let mut caret_at = |x: f32, line: f32| {
+            glyphs
+                .caret_at_point(&section, point(x, line_h * line + 1.0))
+                .unwrap()
+        };
+
+        assert_eq!(caret_at(w * 2.4, 0.0), Caret::new(0, 2));
+        assert_eq!(caret_at(w * 2.6, 0.0), Caret::new(0, 3));
+        assert_eq!(caret_at(-5.0, -1.0), Caret::new(0, 0));
+        assert_eq!(
+            caret_at(w * 20.0, 0.0),
+            Caret {
+                affinity: CaretAffinity::Trailing,
+                ..Caret::new(0, 6)
+            }
+        );
+        assert_eq!(caret_at(0.0, 1.0), Caret::new(0, 6));
+        assert_eq!(caret_at(w * 20.0, 1.0), Caret::new(0, 11));
+        assert_eq!(caret_at(w * 20.0, 2.0), Caret::new(0, 12));
+        assert_eq!(caret_at(w * 20.0, 9.0), Caret::new(0, 15));
+    }
+
+    #[test]
+    fn caret_at_point_beside_exclusion() {
+        static QUOTE: &[Exclusion] = &[Exclusion::Rect(Rect {
+            min: Point { x: 50.0, y: 0.0 },
+            max: Point { x: 100.0, y: 10.0 },
+        })];
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let w = sfont.h_advance(MONO_FONT.glyph_id('w'));
+        let section = Section::default()
+            .add_text(Text::new("aaa bbb ccc ddd").with_scale(16.0))
+            .with_bounds((200.0, f32::INFINITY))
+            .with_layout(Layout::default().exclusions(QUOTE));
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let lines = glyphs.section_lines(&section).to_vec();
+        assert_eq!(lines.len(), 2, "{lines:?}");
+        assert_relative_eq!(lines[0].baseline, lines[1].baseline);
+
+        // "aaa " is left of the exclusion, the rest after it
+        let caret = glyphs.caret_at_point(&section, point(100.0 + w * 0.4, 1.0));
+        assert_eq!(caret, Some(Caret::new(0, 4)));
+        let caret = glyphs.caret_at_point(&section, point(w * 1.6, 1.0));
+        assert_eq!(caret, Some(Caret::new(0, 2)));
+    }
+
+    #[test]
+    fn caret_rect() {
+        let (section, w, line_h) = wrapped_section();
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let mut caret_rect = |caret| glyphs.caret_rect(&section, caret).unwrap();
+
+        let rect = caret_rect(Caret::new(0, 6));
+        assert_relative_eq!(rect.min.x, 0.0);
+        assert_relative_eq!(rect.max.x, 0.0);
+        assert_relative_eq!(rect.min.y, line_h, epsilon = 1e-3);
+        assert_relative_eq!(
+            rect.height(),
+            MONO_FONT.as_scaled(16.0).height(),
+            epsilon = 1e-3
+        );
+
+        let rect = caret_rect(Caret {
+            affinity: CaretAffinity::Trailing,
+            ..Caret::new(0, 6)
+        });
+        assert_relative_eq!(rect.min.x, w * 6.0, epsilon = 1e-3);
+        assert_relative_eq!(rect.min.y, 0.0, epsilon = 1e-3);
+
+        let rect = caret_rect(Caret::new(0, 12));
+        assert_relative_eq!(rect.min.x, 0.0);
+        assert_relative_eq!(rect.min.y, line_h * 2.0, epsilon = 1e-3);
+
+        let rect = caret_rect(Caret::new(0, 15));
+        assert_relative_eq!(rect.min.x, w * 2.0, epsilon = 1e-3);
+        assert_relative_eq!(rect.min.y, line_h * 3.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn selection_rects() {
+        let (section, w, line_h) = wrapped_section();
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let rects = glyphs.selection_rects(&section, (0, 3)..(0, 13));
+        assert_eq!(rects.len(), 3, "{rects:?}");
+
+        assert_relative_eq!(rects[0].min.x, w * 3.0, epsilon = 1e-3);
+        assert_relative_eq!(rects[0].max.x, w * 6.0, epsilon = 1e-3);
+        assert_relative_eq!(rects[0].min.y, 0.0, epsilon = 1e-3);
+
+        assert_relative_eq!(rects[1].min.x, 0.0);
+        assert_relative_eq!(rects[1].max.x, w * 5.0, epsilon = 1e-3);
+        assert_relative_eq!(rects[1].min.y, line_h, epsilon = 1e-3);
+
+        // empty line
+        assert_relative_eq!(rects[2].width(), 0.0);
+        assert_relative_eq!(rects[2].min.y, line_h * 2.0, epsilon = 1e-3);
+
+        assert!(glyphs.selection_rects(&section, (0, 4)..(0, 4)).is_empty());
+    }
+
+    #[test]
+    fn vertical_no_carets() {
+        let section = Section::default()
+            .add_text(Text::new("提高\n代碼").with_scale(16.0))
+            .with_layout(Layout::default_vertical());
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        // vertical layouts have no lines
+        assert!(glyphs.section_lines(&section).is_empty());
+        assert_eq!(glyphs.caret_at_point(&section, point(-5.0, 5.0)), None);
+        assert_eq!(glyphs.caret_rect(&section, Caret::new(0, 3)), None);
+        assert!(glyphs.selection_rects(&section, (0, 0)..(0, 6)).is_empty());
+    }
+
+    #[test]
+    fn rtl_carets() {
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let w = sfont.h_advance(MONO_FONT.glyph_id('w'));
+        // visually "גבא", each 2 bytes
+        let section = Section::default().add_text(Text::new("אבג").with_scale(16.0));
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let mut caret_x = |byte_index| {
+            glyphs
+                .caret_rect(&section, Caret::new(0, byte_index))
+                .unwrap()
+                .min
+                .x
+        };
+        assert_relative_eq!(caret_x(0), w * 3.0, epsilon = 1e-3);
+        assert_relative_eq!(caret_x(2), w * 2.0, epsilon = 1e-3);
+        assert_relative_eq!(caret_x(6), 0.0, epsilon = 1e-3);
+
+        let mut caret_at = |x: f32| glyphs.caret_at_point(&section, point(x, 1.0)).unwrap();
+        assert_eq!(caret_at(w * 0.4), Caret::new(0, 6));
+        assert_eq!(caret_at(w * 2.4), Caret::new(0, 2));
+        assert_eq!(caret_at(w * 2.6), Caret::new(0, 0));
+
+        let rects = glyphs.selection_rects(&section, (0, 0)..(0, 4));
+        assert_eq!(rects.len(), 1, "{rects:?}");
+        assert_relative_eq!(rects[0].min.x, w, epsilon = 1e-3);
+        assert_relative_eq!(rects[0].max.x, w * 3.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn mixed_direction_carets() {
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let w = sfont.h_advance(MONO_FONT.glyph_id('w'));
+        // visually "ab בא cd", the hebrew letters are 2 bytes
+        let section = Section::default().add_text(Text::new("ab אב cd").with_scale(16.0));
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let mut caret_x = |byte_index| {
+            glyphs
+                .caret_rect(&section, Caret::new(0, byte_index))
+                .unwrap()
+                .min
+                .x
+        };
+        assert_relative_eq!(caret_x(1), w, epsilon = 1e-3);
+        // leading edges of the right-to-left run are on the right
+        assert_relative_eq!(caret_x(3), w * 5.0, epsilon = 1e-3);
+        assert_relative_eq!(caret_x(5), w * 4.0, epsilon = 1e-3);
+        assert_relative_eq!(caret_x(7), w * 5.0, epsilon = 1e-3);
+        assert_relative_eq!(caret_x(10), w * 8.0, epsilon = 1e-3);
+
+        let mut caret_at = |x: f32| glyphs.caret_at_point(&section, point(x, 1.0)).unwrap();
+        assert_eq!(caret_at(w * 1.4), Caret::new(0, 1));
+        assert_eq!(caret_at(w * 3.4), Caret::new(0, 7));
+        assert_eq!(caret_at(w * 4.4), Caret::new(0, 5));
+        assert_eq!(caret_at(w * 20.0), Caret::new(0, 10));
+
+        // "b א" is visually split by "ב"
+        let rects = glyphs.selection_rects(&section, (0, 1)..(0, 5));
+        assert_eq!(rects.len(), 2, "{rects:?}");
+        assert_relative_eq!(rects[0].min.x, w, epsilon = 1e-3);
+        assert_relative_eq!(rects[0].max.x, w * 3.0, epsilon = 1e-3);
+        assert_relative_eq!(rects[1].min.x, w * 4.0, epsilon = 1e-3);
+        assert_relative_eq!(rects[1].max.x, w * 5.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn caret_at_point_line_end() {
+        let section = Section::default().add_text(Text::new("ab\r\ncd").with_scale(16.0));
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        // before the hard break
+        let caret = glyphs.caret_at_point(&section, point(100.0, 1.0));
+        assert_eq!(caret, Some(Caret::new(0, 2)));
+        let caret = glyphs.caret_at_point(&section, point(100.0, 100.0));
+        assert_eq!(caret, Some(Caret::new(0, 6)));
+    }
+
+    #[test]
+    fn carets_skip_ellipsis() {
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let w = sfont.h_advance(MONO_FONT.glyph_id('w'));
+        let section = Section::default()
+            .add_text(Text::new("abcdefghij").with_scale(16.0))
+            .with_bounds((w * 5.0, f32::INFINITY))
+            .with_layout(Layout::default_single_line().ellipsis(Ellipsis::Middle("…")));
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let positioned: Vec<_> = glyphs.glyphs(&section).cloned().collect();
+        let ellipsis = positioned
+            .iter()
+            .find(|sg| sg.is_synthetic())
+            .expect("no ellipsis");
+        let after = positioned.iter().rfind(|sg| !sg.is_synthetic()).unwrap();
+
+        // carets after the ellipsis are positioned by their own glyph
+        let rect = glyphs
+            .caret_rect(&section, Caret::new(0, after.byte_index))
+            .unwrap();
+        assert_relative_eq!(rect.min.x, after.glyph.position.x, epsilon = 1e-3);
+        assert!(ellipsis.glyph.position.x < rect.min.x);
+
+        // the end of the text is after the last glyph
+        let rect = glyphs.caret_rect(&section, Caret::new(0, 10)).unwrap();
+        assert_relative_eq!(rect.min.x, after.glyph.position.x + w, epsilon = 1e-3);
+    }
+
+    #[cfg(feature = "shaping")]
+    #[test]
+    fn caret_at_point_after_ligature() {
+        let font =
+            FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf") as &[u8]).unwrap();
+        let section = Section::default().add_text(Text::new("fi").with_scale(16.0));
+        let layout = Layout::default().shaped();
+        let glyphs = GlyphCalculatorBuilder::using_font(font).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let count = glyphs.glyphs_custom_layout(&section, &layout).count();
+        assert_eq!(count, 1, "expected an 'fi' ligature");
+
+        // after the whole cluster
+        let caret = glyphs.caret_at_point_custom_layout(&section, &layout, point(100.0, 1.0));
+        assert_eq!(caret, Some(Caret::new(0, 2)));
+    }
+}
@@ -51,6 +51,7 @@ type SectionHash = u64;
 /// This behaviour can be adjusted with [`GlyphBrushBuilder::draw_cache_position_tolerance`].
 pub struct GlyphBrush<V, X = Extra, F = FontArc, H = DefaultSectionHasher> {
     fonts: Vec<F>,
+    font_fallbacks: FontFallbacks,
     texture_cache: DrawCache,
     last_draw: LastDrawInfo,
 
@@ -68,6 +69,11 @@ pub struct GlyphBrush<V, X = Extra, F = FontArc, H = DefaultSectionHasher> {
     // Set of section hashes to keep in the glyph cache this frame even if they haven't been drawn
     keep_in_cache: FxHashSet<SectionHash>,
 
+    // cache of section text-layout hash -> measured words, independent of the geometry
+    measured_words_cache: FxHashMap<SectionHash, Option<MeasuredWords>>,
+    // Set of measured words hashes to keep in the cache this frame
+    keep_in_words_cache: FxHashSet<SectionHash>,
+
     // config
     cache_glyph_positioning: bool,
     cache_redraws: bool,
@@ -127,7 +133,10 @@ where
             .positioned
             .glyphs()
             .fold(None, |b: Option<Rect>, sg| {
-                let bounds = self.fonts[sg.font_id.0].glyph_bounds(&sg.glyph);
+                let bounds = sg.orientation.rotate_rect(
+                    self.fonts[sg.font_id.0].glyph_bounds(&sg.glyph),
+                    sg.glyph.position,
+                );
                 b.map(|b| {
                     let min_x = b.min.x.min(bounds.min.x);
                     let max_x = b.max.x.max(bounds.max.x);
@@ -151,6 +160,49 @@ where
             })
     }
 
+    fn section_lines_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> &'b [SectionLine]
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let section_hash = self.cache_glyphs(&section, custom_layout);
+        self.keep_in_cache.insert(section_hash);
+        &self.calculate_glyph_cache[&section_hash].positioned.lines
+    }
+
+    fn measured_words_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<&'b MeasuredWords>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let words_hash = {
+            let mut hasher = self.section_hasher.build_hasher();
+            custom_layout.hash(&mut hasher);
+            section.to_hashable_parts().hash_text_no_extra(&mut hasher);
+            hasher.finish()
+        };
+        self.keep_in_words_cache.insert(words_hash);
+
+        self.measured_words_cache
+            .entry(words_hash)
+            .or_insert_with(|| {
+                custom_layout.measure_words(&self.fonts, &self.font_fallbacks, &section.text)
+            })
+            .as_ref()
+    }
+
     #[inline]
     fn fonts(&self) -> &[F] {
         &self.fonts
@@ -217,7 +269,7 @@ where
         S: Into<Cow<'a, Section<'a, X>>>,
     {
         let section = section.into();
-        let layout = section.layout;
+        let layout = section.layout.clone();
         self.queue_custom_layout(section, &layout)
     }
 
@@ -229,6 +281,7 @@ where
             bounds,
             glyphs,
             extra,
+            lines: Vec::new(),
         }));
     }
 
@@ -247,63 +300,99 @@ where
             if !self.calculate_glyph_cache.contains_key(&section_hash.full) {
                 let geometry = SectionGeometry::from(section);
 
-                let recalculated_glyphs = self
+                let recalculated = self
                     .last_frame_seq_id_sections
                     .get(frame_seq_id)
                     .cloned()
                     .and_then(|hash| {
-                        let change = hash.layout_diff(section_hash);
+                        let cached = self.calculate_glyph_cache.get(&hash.full)?;
+                        let change = hash.layout_diff(
+                            section_hash,
+                            &self.section_hasher,
+                            &cached.texts,
+                            &cached.scales,
+                            section,
+                        );
                         if let Some(GlyphChange::Unknown) = change {
                             return None;
                         }
 
-                        if self.keep_in_cache.contains(&hash.full) {
-                            let cached = self.calculate_glyph_cache.get(&hash.full)?;
-                            match change {
-                                None => Some(cached.positioned.glyphs.clone()),
-                                Some(change) => Some(layout.recalculate_glyphs(
-                                    cached.positioned.glyphs.iter().cloned(),
-                                    change,
-                                    &self.fonts,
-                                    &geometry,
-                                    &section.text,
-                                )),
-                            }
+                        let (glyphs, lines, words) = if self.keep_in_cache.contains(&hash.full) {
+                            let GlyphedSection { glyphs, lines, .. } = &cached.positioned;
+                            (glyphs.clone(), lines.clone(), cached.words.clone())
                         } else {
                             let old = self.calculate_glyph_cache.remove(&hash.full)?;
-                            match change {
-                                None => Some(old.positioned.glyphs),
-                                Some(change) => Some(layout.recalculate_glyphs(
-                                    old.positioned.glyphs,
-                                    change,
+                            (old.positioned.glyphs, old.positioned.lines, old.words)
+                        };
+
+                        let words = match change {
+                            // measure words on the first bounds change to speed up the next ones
+                            Some(GlyphChange::Geometry(old))
+                                if words.is_none() && old.bounds != geometry.bounds =>
+                            {
+                                layout.measure_words(
                                     &self.fonts,
-                                    &geometry,
+                                    &self.font_fallbacks,
                                     &section.text,
-                                )),
+                                )
                             }
-                        }
+                            None | Some(GlyphChange::Geometry(_)) => words,
+                            // words are invalidated by text & scale changes
+                            Some(_) => None,
+                        };
+
+                        let (glyphs, lines) = match change {
+                            None => (glyphs, lines),
+                            Some(change) => layout.recalculate_glyphs_and_lines(
+                                PreviousLayout::new(glyphs, &lines).with_words(words.as_ref()),
+                                change,
+                                &self.fonts,
+                                &self.font_fallbacks,
+                                &geometry,
+                                &section.text,
+                            ),
+                        };
+                        Some((glyphs, lines, words))
                     });
 
-                self.calculate_glyph_cache.insert(
-                    section_hash.full,
-                    Glyphed::new(GlyphedSection {
-                        bounds: layout.bounds_rect(&geometry),
-                        glyphs: recalculated_glyphs.unwrap_or_else(|| {
-                            layout.calculate_glyphs(&self.fonts, &geometry, &section.text)
-                        }),
-                        extra: section.clone_extras(),
-                    }),
-                );
+                let (glyphs, lines, words) = recalculated.unwrap_or_else(|| {
+                    let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+                        &self.fonts,
+                        &self.font_fallbacks,
+                        &geometry,
+                        &section.text,
+                    );
+                    (glyphs, lines, None)
+                });
+                // kept to find text edits & speed up bounds changes next frame
+                let texts = TextPrefixes::new(&self.section_hasher, &section.text, &lines);
+                let mut glyphed = Glyphed::new(GlyphedSection {
+                    bounds: layout.bounds_rect(&geometry),
+                    glyphs,
+                    extra: section.clone_extras(),
+                    lines,
+                });
+                glyphed.texts = texts;
+                glyphed.scales = section.text.iter().map(|t| t.scale).collect();
+                glyphed.words = words;
+                self.calculate_glyph_cache
+                    .insert(section_hash.full, glyphed);
             }
         } else {
             let geometry = SectionGeometry::from(section);
-            let glyphs = layout.calculate_glyphs(&self.fonts, &geometry, &section.text);
+            let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+                &self.fonts,
+                &self.font_fallbacks,
+                &geometry,
+                &section.text,
+            );
             self.calculate_glyph_cache.insert(
                 section_hash.full,
                 Glyphed::new(GlyphedSection {
                     bounds: layout.bounds_rect(&geometry),
                     glyphs,
                     extra: section.text.iter().map(|s| s.extra.clone()).collect(),
+                    lines,
                 }),
             );
         }
@@ -349,11 +438,20 @@ where
             self.keep_in_cache = active;
             self.keep_in_cache.clear();
 
+            let active_words = mem::take(&mut self.keep_in_words_cache);
+            self.measured_words_cache
+                .retain(|key, _| active_words.contains(key));
+
+            self.keep_in_words_cache = active_words;
+            self.keep_in_words_cache.clear();
+
             self.section_buffer.clear();
         } else {
             self.section_buffer.clear();
             self.calculate_glyph_cache.clear();
             self.keep_in_cache.clear();
+            self.measured_words_cache.clear();
+            self.keep_in_words_cache.clear();
         }
 
         mem::swap(
@@ -398,7 +496,7 @@ where
         X: 'a,
     {
         let section = section.into();
-        let layout = section.layout;
+        let layout = section.layout.clone();
         self.keep_cached_custom_layout(section, &layout);
     }
 }
@@ -468,9 +566,9 @@ where
                     .get(section_hash)
                     .iter()
                     .flat_map(|gs| &gs.positioned.glyphs)
+                    .filter(|sg| !sg.is_placeholder())
                 {
-                    self.texture_cache
-                        .queue_glyph(sg.font_id.0, sg.glyph.clone());
+                    queue_glyph(&mut self.texture_cache, sg);
                     some_text = true;
                 }
             }
@@ -479,9 +577,9 @@ where
                 .pre_positioned
                 .iter()
                 .flat_map(|p| &p.positioned.glyphs)
+                .filter(|sg| !sg.is_placeholder())
             {
-                self.texture_cache
-                    .queue_glyph(sg.font_id.0, sg.glyph.clone());
+                queue_glyph(&mut self.texture_cache, sg);
                 some_text = true;
             }
 
@@ -540,6 +638,17 @@ where
     }
 }
 
+/// Queues a glyph to be drawn in its [`VerticalOrientation`].
+#[inline]
+fn queue_glyph(texture_cache: &mut DrawCache, sg: &SectionGlyph) {
+    match sg.orientation {
+        VerticalOrientation::Upright => texture_cache.queue_glyph(sg.font_id.0, sg.glyph.clone()),
+        VerticalOrientation::Sideways => {
+            texture_cache.queue_glyph_sideways(sg.font_id.0, sg.glyph.clone())
+        }
+    }
+}
+
 impl<F: Font + Clone, V, X, H: BuildHasher + Clone> GlyphBrush<V, X, F, H> {
     /// Return a [`GlyphBrushBuilder`](struct.GlyphBrushBuilder.html) prefilled with the
     /// properties of this `GlyphBrush`.
@@ -562,6 +671,7 @@ impl<F: Font + Clone, V, X, H: BuildHasher + Clone> GlyphBrush<V, X, F, H> {
             .cache_glyph_positioning(self.cache_glyph_positioning)
             .cache_redraws(self.cache_redraws)
             .section_hasher(self.section_hasher.clone());
+        builder.font_fallbacks = self.font_fallbacks.clone();
         builder.draw_cache_builder = self.texture_cache.to_builder();
         builder
     }
@@ -614,6 +724,10 @@ impl std::error::Error for BrushError {
 
 #[derive(Debug, Clone, Copy)]
 struct SectionHashDetail {
+    /// hash of text style (- text strings - extra - geo)
+    style: SectionHash,
+    /// hash of text (- scales - extra - geo)
+    unscaled: SectionHash,
     /// hash of text (- extra - geo)
     text: SectionHash,
     /// hash of text + extra + geo
@@ -632,6 +746,16 @@ impl SectionHashDetail {
     {
         let parts = section.to_hashable_parts();
 
+        let mut s = build_hasher.build_hasher();
+        layout.hash(&mut s);
+        parts.hash_text_style(&mut s);
+        let style = s.finish();
+
+        let mut s = build_hasher.build_hasher();
+        layout.hash(&mut s);
+        parts.hash_text_no_scale(&mut s);
+        let unscaled = s.finish();
+
         let mut s = build_hasher.build_hasher();
         layout.hash(&mut s);
         parts.hash_text_no_extra(&mut s);
@@ -642,6 +766,8 @@ impl SectionHashDetail {
         let full = s.finish();
 
         Self {
+            style,
+            unscaled,
             text,
             full,
             geometry: SectionGeometry::from(section),
@@ -649,23 +775,172 @@ impl SectionHashDetail {
     }
 
     /// Hash layout diff, if any (None implies no change or extra-only change)
-    fn layout_diff(self, other: SectionHashDetail) -> Option<GlyphChange> {
+    ///
+    /// `texts` & `scales` are the text prefix hashes & scales of this, the previous, section
+    /// used to find text & scale changes of the `other` section.
+    fn layout_diff<X, H: BuildHasher>(
+        self,
+        other: SectionHashDetail,
+        build_hasher: &H,
+        texts: &TextPrefixes,
+        scales: &[PxScale],
+        section: &Section<'_, X>,
+    ) -> Option<GlyphChange> {
         if self.text == other.text {
             if self.geometry == other.geometry {
                 None
             } else {
                 Some(GlyphChange::Geometry(self.geometry))
             }
+        } else if self.style == other.style && self.geometry == other.geometry {
+            Some(
+                texts
+                    .change(build_hasher, &section.text)
+                    .unwrap_or(GlyphChange::Unknown),
+            )
+        } else if self.unscaled == other.unscaled && self.geometry == other.geometry {
+            Some(scale_change(scales, &section.text).unwrap_or(GlyphChange::Unknown))
         } else {
             Some(GlyphChange::Unknown)
         }
     }
 }
 
+/// Hashes of a section's text before each of its line & section text ends, used to find
+/// the text changes of later sections without keeping a copy of the text.
+#[derive(Debug, Default)]
+struct TextPrefixes {
+    /// Number of section texts.
+    count: usize,
+    /// Ascending `(section_index, byte_index)` ends & the hash of all text before each,
+    /// ending with the end of all text.
+    hashes: Vec<((usize, usize), u64)>,
+}
+
+impl TextPrefixes {
+    fn new<X, H: BuildHasher>(
+        build_hasher: &H,
+        texts: &[Text<'_, X>],
+        lines: &[SectionLine],
+    ) -> Self {
+        let mut ends: Vec<_> = lines
+            .iter()
+            .map(|line| line.end)
+            .chain(texts.iter().enumerate().map(|(idx, t)| (idx, t.text.len())))
+            .collect();
+        ends.sort_unstable();
+        ends.dedup();
+
+        let mut hasher = PrefixHasher::new(build_hasher, texts);
+        let hashes = ends
+            .into_iter()
+            .filter_map(|end| Some((end, hasher.hash_to(end)?)))
+            .collect();
+        Self {
+            count: texts.len(),
+            hashes,
+        }
+    }
+
+    /// Returns the change from these to `new` text strings, starting at the last end
+    /// before which all text is unchanged.
+    fn change<X, H: BuildHasher>(
+        &self,
+        build_hasher: &H,
+        new: &[Text<'_, X>],
+    ) -> Option<GlyphChange> {
+        if self.count != new.len() {
+            return None;
+        }
+        let &(end, _) = self.hashes.last()?;
+
+        let mut hasher = PrefixHasher::new(build_hasher, new);
+        let unchanged = self
+            .hashes
+            .iter()
+            .take_while(|&&(end, hash)| hasher.hash_to(end) == Some(hash))
+            .count();
+
+        // all the previous text remains, so the text has been appended to the last section
+        if unchanged == self.hashes.len() {
+            return Some(GlyphChange::TextAppended(end));
+        }
+        let start = match unchanged {
+            0 => (0, 0),
+            n => self.hashes[n - 1].0,
+        };
+        Some(GlyphChange::TextEdited(start))
+    }
+}
+
+/// Hashes section texts incrementally up to ascending `(section_index, byte_index)` ends.
+struct PrefixHasher<'a, 't, S, X> {
+    hasher: S,
+    texts: &'a [Text<'t, X>],
+    at: (usize, usize),
+}
+
+impl<'a, 't, X, S: Hasher> PrefixHasher<'a, 't, S, X> {
+    #[inline]
+    fn new<H: BuildHasher<Hasher = S>>(build_hasher: &H, texts: &'a [Text<'t, X>]) -> Self {
+        Self {
+            hasher: build_hasher.build_hasher(),
+            texts,
+            at: (0, 0),
+        }
+    }
+
+    /// Returns the hash of all text before `end`, or `None` if `end` is outside the texts,
+    /// within a character or before the previous end.
+    fn hash_to(&mut self, end: (usize, usize)) -> Option<u64> {
+        let (section_index, byte_index) = end;
+        let text = self.texts.get(section_index)?.text;
+        if end < self.at || !text.is_char_boundary(byte_index) {
+            return None;
+        }
+
+        for text in &self.texts[self.at.0..section_index] {
+            self.hasher.write(&text.text.as_bytes()[self.at.1..]);
+            // separate section texts, so moving text between them is a change
+            self.hasher.write_u8(0xff);
+            self.at.1 = 0;
+        }
+        self.hasher.write(&text.as_bytes()[self.at.1..byte_index]);
+        self.at = end;
+        Some(self.hasher.finish())
+    }
+}
+
+/// Returns the change from `old` to `new` text scales if all have been multiplied by the
+/// same factor.
+fn scale_change<X>(old: &[PxScale], new: &[Text<'_, X>]) -> Option<GlyphChange> {
+    if old.len() != new.len() {
+        return None;
+    }
+
+    let factor = new.first()?.scale.y / old.first()?.y;
+    if !factor.is_finite() || factor <= 0.0 {
+        return None;
+    }
+
+    // allow float errors of separately scaled texts
+    let scaled_eq = |old: f32, new: f32| (old * factor - new).abs() <= new.abs() * 1e-6;
+    old.iter()
+        .zip(new)
+        .all(|(old, new)| scaled_eq(old.x, new.scale.x) && scaled_eq(old.y, new.scale.y))
+        .then_some(GlyphChange::Scale(factor))
+}
+
 /// Container for positioned glyphs which can generate and cache vertices
 struct Glyphed<V, X> {
     positioned: GlyphedSection<X>,
     vertices: Vec<V>,
+    /// Text prefix hashes of the section, if cached
+    texts: TextPrefixes,
+    /// Text scales of the section, if cached
+    scales: Vec<PxScale>,
+    /// Measured words of the section, if the bounds have changed
+    words: Option<MeasuredWords>,
 }
 
 impl<V, X: PartialEq> PartialEq for Glyphed<V, X> {
@@ -681,6 +956,9 @@ impl<V, X> Glyphed<V, X> {
         Self {
             positioned: gs,
             vertices: Vec::new(),
+            texts: TextPrefixes::default(),
+            scales: Vec::new(),
+            words: None,
         }
     }
 
@@ -702,11 +980,18 @@ impl<V, X> Glyphed<V, X> {
             bounds,
             ref extra,
             ref glyphs,
+            ..
         } = self.positioned;
 
         self.vertices.reserve(glyphs.len());
         self.vertices.extend(glyphs.iter().filter_map(|sg| {
-            match texture_cache.rect_for(sg.font_id.0, &sg.glyph) {
+            let rect = match sg.orientation {
+                VerticalOrientation::Upright => texture_cache.rect_for(sg.font_id.0, &sg.glyph),
+                VerticalOrientation::Sideways => {
+                    texture_cache.rect_for_sideways(sg.font_id.0, &sg.glyph)
+                }
+            };
+            match rect {
                 None => None,
                 Some((tex_coords, pixel_coords)) => {
                     if pixel_coords.min.x > bounds.max.x
@@ -733,6 +1018,7 @@ impl<V, X> Glyphed<V, X> {
 #[cfg(test)]
 mod hash_diff_test {
     use super::*;
+    use approx::*;
 
     fn section() -> Section<'static> {
         Section {
@@ -745,6 +1031,7 @@ mod hash_diff_test {
                         color: [1.0, 0.9, 0.8, 0.7],
                         z: 0.444,
                     },
+                    ..Text::default()
                 },
                 Text {
                     text: "World",
@@ -754,6 +1041,7 @@ mod hash_diff_test {
                         color: [0.6, 0.5, 0.4, 0.3],
                         z: 0.444,
                     },
+                    ..Text::default()
                 },
             ],
             bounds: (55.5, 66.6),
@@ -762,57 +1050,179 @@ mod hash_diff_test {
         }
     }
 
+    /// Returns the layout diff from the `old` to the `new` section.
+    fn layout_diff(old: &Section<'_>, new: &Section<'_>) -> Option<GlyphChange> {
+        layout_diff_with_lines(old, &[], new)
+    }
+
+    /// Returns the layout diff from the `old` section, laid out in lines ending at
+    /// `line_ends`, to the `new` section.
+    fn layout_diff_with_lines(
+        old: &Section<'_>,
+        line_ends: &[(usize, usize)],
+        new: &Section<'_>,
+    ) -> Option<GlyphChange> {
+        let build_hasher = DefaultSectionHasher::default();
+        let lines: Vec<_> = line_ends
+            .iter()
+            .enumerate()
+            .map(|(index, &end)| SectionLine {
+                index,
+                glyphs: 0..0,
+                start: (0, 0),
+                end,
+                left: 0.0,
+                baseline: 0.0,
+                ascent: 0.0,
+                descent: 0.0,
+                advance_width: 0.0,
+                hard_break: false,
+                rtl: false,
+            })
+            .collect();
+        let texts = TextPrefixes::new(&build_hasher, &old.text, &lines);
+        let scales: Vec<_> = old.text.iter().map(|t| t.scale).collect();
+
+        SectionHashDetail::new(&build_hasher, old, &old.layout).layout_diff(
+            SectionHashDetail::new(&build_hasher, new, &new.layout),
+            &build_hasher,
+            &texts,
+            &scales,
+            new,
+        )
+    }
+
     #[test]
     fn change_screen_position() {
-        let build_hasher = DefaultSectionHasher::default();
         let mut section = section();
-        let hash_deets = SectionHashDetail::new(&build_hasher, &section, &section.layout);
+        let old = section.clone();
 
         section.screen_position.1 += 0.1;
 
-        let diff = hash_deets.layout_diff(SectionHashDetail::new(
-            &build_hasher,
-            &section,
-            &section.layout,
-        ));
+        let diff = layout_diff(&old, &section);
 
         match diff {
-            Some(GlyphChange::Geometry(geo)) => assert_eq!(geo, hash_deets.geometry),
+            Some(GlyphChange::Geometry(geo)) => assert_eq!(geo, SectionGeometry::from(&old)),
             _ => assert!(matches!(diff, Some(GlyphChange::Geometry(..)))),
         }
     }
 
     #[test]
     fn change_extra() {
-        let build_hasher = DefaultSectionHasher::default();
         let mut section = section();
-        let hash_deets = SectionHashDetail::new(&build_hasher, &section, &section.layout);
+        let old = section.clone();
 
         section.text[1].extra.color[2] -= 0.1;
 
-        let diff = hash_deets.layout_diff(SectionHashDetail::new(
-            &build_hasher,
-            &section,
-            &section.layout,
-        ));
+        let diff = layout_diff(&old, &section);
 
         assert!(diff.is_none());
     }
 
     #[test]
     fn change_text() {
-        let build_hasher = DefaultSectionHasher::default();
         let mut section = section();
-        let hash_deets = SectionHashDetail::new(&build_hasher, &section, &section.layout);
+        let old = section.clone();
 
         section.text[1].text = "something else";
 
-        let diff = hash_deets.layout_diff(SectionHashDetail::new(
-            &build_hasher,
-            &section,
-            &section.layout,
-        ));
+        let diff = layout_diff(&old, &section);
+
+        assert!(matches!(diff, Some(GlyphChange::TextEdited((0, 7)))));
+    }
+
+    #[test]
+    fn change_text_appended() {
+        let mut section = section();
+        let old = section.clone();
+
+        section.text[1].text = "World!\nMore";
+
+        let diff = layout_diff(&old, &section);
+
+        assert!(matches!(diff, Some(GlyphChange::TextAppended((1, 5)))));
+    }
+
+    #[test]
+    fn change_text_edited() {
+        let mut section = section();
+        let old = section.clone();
+
+        section.text[0].text = "Help, ";
+        let diff = layout_diff(&old, &section);
+        assert!(matches!(diff, Some(GlyphChange::TextEdited((0, 0)))));
+
+        // edits start after the last unchanged line
+        let diff = layout_diff_with_lines(&old, &[(0, 2), (0, 4), (1, 5)], &section);
+        assert!(matches!(diff, Some(GlyphChange::TextEdited((0, 2)))));
+
+        // line ends within new characters are changes
+        let mut old = section.clone();
+        old.text[0].text = "Hé";
+        section.text[0].text = "Hー";
+        let diff = layout_diff_with_lines(&old, &[(0, 1), (0, 3)], &section);
+        assert!(matches!(diff, Some(GlyphChange::TextEdited((0, 1)))));
+
+        // appending to a section other than the last is an edit
+        section.text[0].text = "Hé, ";
+        let diff = layout_diff(&old, &section);
+        assert!(matches!(diff, Some(GlyphChange::TextEdited((0, 3)))));
+
+        // geometry & text changes are unknown
+        let mut moved = section.clone();
+        moved.text[0].text = "Hello";
+        moved.screen_position.0 += 1.0;
+        let diff = layout_diff(&section, &moved);
+        assert!(matches!(diff, Some(GlyphChange::Unknown)));
+
+        let mut more_text = section.clone();
+        more_text.text.push(Text::new("!"));
+        let diff = layout_diff(&section, &more_text);
+        assert!(matches!(diff, Some(GlyphChange::Unknown)));
+    }
+
+    #[test]
+    fn change_spacing() {
+        let section = section();
+
+        let mut letter_spaced = section.clone();
+        letter_spaced.text[0].letter_spacing = 1.5;
+        let diff = layout_diff(&section, &letter_spaced);
+        assert!(matches!(diff, Some(GlyphChange::Unknown)));
+
+        let mut line_height = section.clone();
+        line_height.text[1].line_height = LineHeight::Multiplier(1.5);
+        let diff = layout_diff(&section, &line_height);
+        assert!(matches!(diff, Some(GlyphChange::Unknown)));
 
+        let mut shifted = section.clone();
+        shifted.text[1].baseline_shift = BaselineShift::Superscript;
+        let diff = layout_diff(&section, &shifted);
+        assert!(matches!(diff, Some(GlyphChange::Unknown)));
+    }
+
+    #[test]
+    fn change_scale() {
+        let section = section();
+
+        let mut scaled = section.clone();
+        scaled.text[0].scale = PxScale::from(20.0 * 1.3);
+        scaled.text[1].scale = PxScale::from(22.0 * 1.3);
+        let diff = layout_diff(&section, &scaled);
+        match diff {
+            Some(GlyphChange::Scale(factor)) => assert_relative_eq!(factor, 1.3),
+            _ => assert!(matches!(diff, Some(GlyphChange::Scale(..)))),
+        }
+
+        // non-uniform scale changes are unknown
+        scaled.text[1].scale = PxScale::from(22.0);
+        let diff = layout_diff(&section, &scaled);
+        assert!(matches!(diff, Some(GlyphChange::Unknown)));
+
+        // as are scale & geometry changes
+        scaled.text[1].scale = PxScale::from(22.0 * 1.3);
+        scaled.screen_position.0 += 1.0;
+        let diff = layout_diff(&section, &scaled);
         assert!(matches!(diff, Some(GlyphChange::Unknown)));
     }
 }
@@ -820,6 +1230,7 @@ mod hash_diff_test {
 #[cfg(test)]
 mod glyph_brush_test {
     use super::*;
+    use approx::*;
 
     #[test]
     fn is_draw_cached() {
@@ -854,4 +1265,106 @@ mod glyph_brush_test {
         assert!(!brush.is_draw_cached(FontId(1), &glyphs[3]));
         assert!(!brush.is_draw_cached(FontId(0), &unqueued_glyph));
     }
+
+    #[test]
+    fn append_text_relayout() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font.clone()).build();
+        let calc = GlyphCalculatorBuilder::using_font(font).build();
+
+        let log = "first message\nsecond message that wraps\n".repeat(3);
+        let section = |text| {
+            Section::default()
+                .add_text(Text::new(text))
+                .with_bounds((120.0, f32::INFINITY))
+        };
+
+        brush.queue(section(&log));
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+
+        let appended = format!("{log}third");
+        brush.queue(section(&appended));
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+
+        let glyphs: Vec<_> = brush.glyphs(section(&appended)).cloned().collect();
+        let lines = brush.section_lines(section(&appended)).to_vec();
+
+        let mut calc = calc.cache_scope();
+        let expected: Vec<_> = calc.glyphs(section(&appended)).cloned().collect();
+        assert_eq!(glyphs.len(), expected.len());
+        for (sg, expected) in glyphs.iter().zip(&expected) {
+            assert_eq!(sg.byte_index, expected.byte_index);
+            assert!((sg.glyph.position.y - expected.glyph.position.y).abs() < 1e-3);
+        }
+        assert_eq!(lines.len(), calc.section_lines(section(&appended)).len());
+    }
+
+    #[test]
+    fn resize_reuses_measured_words() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font.clone()).build();
+        let calc = GlyphCalculatorBuilder::using_font(font).build();
+
+        let text = "resizable panel text that wraps across a few lines";
+        let section = |width| {
+            Section::default()
+                .add_text(Text::new(text))
+                .with_bounds((width, f32::INFINITY))
+        };
+
+        for width in [300.0, 200.0, 100.0] {
+            brush.queue(section(width));
+            brush.process_queued(|_, _| {}, |_| ()).unwrap();
+        }
+
+        assert_eq!(brush.calculate_glyph_cache.len(), 1);
+        let glyphed = brush.calculate_glyph_cache.values().next().unwrap();
+        assert!(glyphed.words.is_some(), "words not measured on resize");
+
+        let mut calc = calc.cache_scope();
+        let expected: Vec<_> = calc.glyphs(section(100.0)).cloned().collect();
+        assert_eq!(glyphed.positioned.glyphs, expected);
+    }
+
+    #[test]
+    fn scale_text_relayout() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font.clone()).build();
+        let calc = GlyphCalculatorBuilder::using_font(font).build();
+
+        let section = |scale: f32| {
+            Section::default()
+                .add_text(Text::new("pop-in\n").with_scale(scale))
+                .add_text(Text::new("text").with_scale(scale * 2.0))
+                .with_screen_position((50.0, 50.0))
+                .with_layout(Layout::default().h_align(HorizontalAlign::Center))
+        };
+
+        for scale in [10.0, 15.0, 20.0] {
+            brush.queue(section(scale));
+            brush.process_queued(|_, _| {}, |_| ()).unwrap();
+        }
+
+        assert_eq!(brush.calculate_glyph_cache.len(), 1);
+        let glyphed = brush.calculate_glyph_cache.values().next().unwrap();
+
+        let mut calc = calc.cache_scope();
+        let expected: Vec<_> = calc.glyphs(section(20.0)).cloned().collect();
+        assert_eq!(glyphed.positioned.glyphs.len(), expected.len());
+        for (sg, expected) in glyphed.positioned.glyphs.iter().zip(&expected) {
+            assert_eq!(sg.glyph.id, expected.glyph.id);
+            assert_eq!(sg.glyph.scale, expected.glyph.scale);
+            assert_relative_eq!(
+                sg.glyph.position.x,
+                expected.glyph.position.x,
+                epsilon = 1e-3
+            );
+            assert_relative_eq!(
+                sg.glyph.position.y,
+                expected.glyph.position.y,
+                epsilon = 1e-3
+            );
+        }
+        assert_eq!(glyphed.positioned.lines.len(), 2);
+    }
 }
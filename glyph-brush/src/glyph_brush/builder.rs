@@ -1,4 +1,4 @@
-use crate::{DefaultSectionHasher, Font, FontId, GlyphBrush};
+use crate::{DefaultSectionHasher, Font, FontFallbacks, FontId, GlyphBrush};
 use glyph_brush_draw_cache::*;
 use glyph_brush_layout::ab_glyph::*;
 use std::hash::BuildHasher;
@@ -16,6 +16,7 @@ use std::hash::BuildHasher;
 #[non_exhaustive]
 pub struct GlyphBrushBuilder<F = FontArc, H = DefaultSectionHasher> {
     pub font_data: Vec<F>,
+    pub font_fallbacks: FontFallbacks,
     pub cache_glyph_positioning: bool,
     pub cache_redraws: bool,
     pub section_hasher: H,
@@ -38,6 +39,7 @@ impl GlyphBrushBuilder<()> {
     pub fn without_fonts() -> GlyphBrushBuilder<()> {
         GlyphBrushBuilder {
             font_data: Vec::new(),
+            font_fallbacks: FontFallbacks::default(),
             cache_glyph_positioning: true,
             cache_redraws: true,
             section_hasher: DefaultSectionHasher::default(),
@@ -85,6 +87,7 @@ impl<F, H> GlyphBrushBuilder<F, H> {
         let font_data = font_fn(self.font_data).into();
         GlyphBrushBuilder {
             font_data,
+            font_fallbacks: self.font_fallbacks,
             cache_glyph_positioning: self.cache_glyph_positioning,
             cache_redraws: self.cache_redraws,
             section_hasher: self.section_hasher,
@@ -101,6 +104,29 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
         FontId(self.font_data.len() - 1)
     }
 
+    /// Sets the fonts to fall back to, in order, for characters `font_id` has no glyphs for.
+    ///
+    /// See [`FontFallbacks`](struct.FontFallbacks.html).
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::{ab_glyph::*, *};
+    /// # type Vertex = ();
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../../fonts/DejaVuSans.ttf")).unwrap();
+    /// # let garamond = FontArc::try_from_slice(include_bytes!("../../../fonts/GaramondNo8-Reg.ttf")).unwrap();
+    /// // Use dejavu for characters garamond is missing
+    /// let glyph_brush: GlyphBrush<Vertex> = GlyphBrushBuilder::using_fonts(vec![garamond, dejavu])
+    ///     .font_fallbacks(FontId(0), [FontId(1)])
+    ///     .build();
+    /// ```
+    pub fn font_fallbacks<I>(mut self, font_id: FontId, fallbacks: I) -> Self
+    where
+        I: IntoIterator<Item = FontId>,
+    {
+        self.font_fallbacks.set(font_id, fallbacks);
+        self
+    }
+
     /// Initial size of 2D texture used as a gpu cache, pixels (width, height).
     /// The GPU cache will dynamically quadruple in size whenever the current size
     /// is insufficient.
@@ -202,6 +228,7 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
         GlyphBrushBuilder {
             section_hasher,
             font_data: self.font_data,
+            font_fallbacks: self.font_fallbacks,
             cache_glyph_positioning: self.cache_glyph_positioning,
             cache_redraws: self.cache_redraws,
             draw_cache_builder: self.draw_cache_builder,
@@ -223,6 +250,7 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
     pub fn build<V, X>(self) -> GlyphBrush<V, X, F, H> {
         GlyphBrush {
             fonts: self.font_data,
+            font_fallbacks: self.font_fallbacks,
             texture_cache: self.draw_cache_builder.build(),
 
             last_draw: <_>::default(),
@@ -233,6 +261,8 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
             frame_seq_id_sections: <_>::default(),
 
             keep_in_cache: <_>::default(),
+            measured_words_cache: <_>::default(),
+            keep_in_words_cache: <_>::default(),
 
             cache_glyph_positioning: self.cache_glyph_positioning,
             cache_redraws: self.cache_redraws && self.cache_glyph_positioning,
@@ -269,6 +299,7 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
 /// Implements:
 /// * `add_font_bytes`
 /// * `add_font`
+/// * `font_fallbacks`
 /// * `initial_cache_size`
 /// * `draw_cache_scale_tolerance`
 /// * `draw_cache_position_tolerance`
@@ -323,6 +354,16 @@ macro_rules! delegate_glyph_brush_builder_fns {
             self.$inner.add_font(font_data)
         }
 
+        /// Sets the fonts to fall back to, in order, for characters `font_id` has no
+        /// glyphs for.
+        pub fn font_fallbacks<I>(mut self, font_id: $crate::FontId, fallbacks: I) -> Self
+        where
+            I: IntoIterator<Item = $crate::FontId>,
+        {
+            self.$inner = self.$inner.font_fallbacks(font_id, fallbacks);
+            self
+        }
+
         /// Initial size of 2D texture used as a gpu cache, pixels (width, height).
         /// The GPU cache will dynamically quadruple in size whenever the current size
         /// is insufficient.
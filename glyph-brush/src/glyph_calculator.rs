@@ -5,7 +5,9 @@ use std::{
     collections::hash_map::Entry,
     fmt,
     hash::{BuildHasher, Hash, Hasher},
-    mem, slice,
+    mem,
+    ops::Range,
+    slice,
     sync::{Mutex, MutexGuard},
 };
 
@@ -73,10 +75,200 @@ pub trait GlyphCruncher<F: Font = FontArc, X: Clone = Extra> {
         S: Into<Cow<'a, Section<'a, X>>>,
     {
         let section = section.into();
-        let layout = section.layout;
+        let layout = section.layout.clone();
         self.glyphs_custom_layout(section, &layout)
     }
 
+    /// Returns the laid out lines of the given section with a custom layout, see
+    /// [`section_lines`](#method.section_lines).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    fn section_lines_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> &'b [SectionLine]
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>;
+
+    /// Returns the laid out lines of the given section, including each line's text range,
+    /// glyph range, baseline & metrics. See [`SectionLine`].
+    ///
+    /// Lines are calculated & cached along with the section's glyphs. Vertical layouts have
+    /// no lines.
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::{*, ab_glyph::*};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// # let mut glyph_brush = GlyphBrushBuilder::using_font(dejavu).build::<(), _>();
+    /// let section = Section::default().add_text(Text::new("first\nsecond"));
+    ///
+    /// for line in glyph_brush.section_lines(&section) {
+    ///     println!("line {} baseline={} glyphs={:?}", line.index, line.baseline, line.glyphs);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn section_lines<'a, 'b, S>(&'b mut self, section: S) -> &'b [SectionLine]
+    where
+        X: 'a,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let layout = section.layout.clone();
+        self.section_lines_custom_layout(section, &layout)
+    }
+
+    /// Returns an iterator over the positioned [`SectionGlyph`]s of the given section laid
+    /// out at the largest scale that fits its bounds, see [`FitLayout`], along with that
+    /// scale. Section text scales are proportionally changed from those of the section.
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::{*, ab_glyph::*};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// # let mut glyph_brush = GlyphBrushBuilder::using_font(dejavu).build::<(), _>();
+    /// let section = Section::default()
+    ///     .add_text(Text::new("Continue"))
+    ///     .with_bounds((100.0, 40.0));
+    /// let fit = FitLayout::new(Layout::default_single_line(), 12.0, 32.0);
+    ///
+    /// let (glyphs, scale) = glyph_brush.glyphs_fitted(&section, &fit);
+    /// assert!(scale.y >= 12.0 && scale.y <= 32.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn glyphs_fitted<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        fit_layout: &FitLayout<L>,
+    ) -> (SectionGlyphIter<'b>, PxScale)
+    where
+        X: 'a,
+        L: LineBreaker,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let glyphs = self.glyphs_custom_layout(&*section, fit_layout);
+        let scale = fit_layout.fitted_scale(&section.text, glyphs);
+        (self.glyphs_custom_layout(section, fit_layout), scale)
+    }
+
+    /// Returns the measured words of the given section with a custom layout, see
+    /// [`GlyphPositioner::measure_words`]. Used to calculate intrinsic sizes.
+    ///
+    /// Words are cached independently of the section's screen position & bounds.
+    fn measured_words_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<&'b MeasuredWords>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>;
+
+    /// Returns the min-content & max-content widths of the given section with a custom
+    /// layout, see [`intrinsic_widths`](#method.intrinsic_widths).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    fn intrinsic_widths_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<IntrinsicWidths>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let words = self.measured_words_custom_layout(&*section, custom_layout)?;
+        custom_layout.intrinsic_widths(words, &section.text)
+    }
+
+    /// Returns the min-content & max-content widths of the given section, e.g. for flexbox
+    /// or grid UI layout, see [`IntrinsicWidths`]. The section bounds are ignored.
+    ///
+    /// Returns `None` for vertical layouts.
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour). Measured words are
+    /// cached regardless of the section geometry, so intrinsic sizes are cheap to recalculate.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::{*, ab_glyph::*};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// # let mut glyph_brush = GlyphBrushBuilder::using_font(dejavu).build::<(), _>();
+    /// let section = Section::default().add_text(Text::new("Some wrapping label text"));
+    ///
+    /// let widths = glyph_brush.intrinsic_widths(&section).unwrap();
+    /// assert!(widths.min_content < widths.max_content);
+    ///
+    /// let height = glyph_brush.height_for_width(&section, widths.min_content);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn intrinsic_widths<'a, S>(&mut self, section: S) -> Option<IntrinsicWidths>
+    where
+        X: 'a,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let layout = section.layout.clone();
+        self.intrinsic_widths_custom_layout(section, &layout)
+    }
+
+    /// Returns the height of the given section laid out with a custom layout within a
+    /// `width` bound, see [`height_for_width`](#method.height_for_width).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    fn height_for_width_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+        width: f32,
+    ) -> Option<f32>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let words = self.measured_words_custom_layout(&*section, custom_layout)?;
+        custom_layout.height_for_width(words, width, &section.text)
+    }
+
+    /// Returns the height of the given section laid out within a `width` bound, without
+    /// positioning glyphs. The section bounds are ignored.
+    ///
+    /// Returns `None` for vertical layouts.
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour). Measured words are
+    /// cached regardless of the section geometry, so heights are cheap to recalculate.
+    #[inline]
+    fn height_for_width<'a, S>(&mut self, section: S, width: f32) -> Option<f32>
+    where
+        X: 'a,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let layout = section.layout.clone();
+        self.height_for_width_custom_layout(section, &layout, width)
+    }
+
     /// Returns the available fonts.
     ///
     /// The `FontId` corresponds to the index of the font data.
@@ -117,9 +309,180 @@ pub trait GlyphCruncher<F: Font = FontArc, X: Clone = Extra> {
         S: Into<Cow<'a, Section<'a, X>>>,
     {
         let section = section.into();
-        let layout = section.layout;
+        let layout = section.layout.clone();
         self.glyph_bounds_custom_layout(section, &layout)
     }
+
+    /// Returns the [`Caret`] nearest to a screen `point` in the given section laid out with
+    /// a custom layout, see [`caret_at_point`](#method.caret_at_point).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    fn caret_at_point_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+        point: Point,
+    ) -> Option<Caret>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let (glyphs, lines) = caret::cached_layout(self, &section, custom_layout);
+        SectionCarets {
+            fonts: self.fonts(),
+            section: &section,
+            glyphs: &glyphs,
+            lines: &lines,
+        }
+        .caret_at(point)
+    }
+
+    /// Returns the [`Caret`] nearest to a screen `point` in the given section, e.g. to
+    /// position a text cursor on click.
+    ///
+    /// The nearest line is hit, then the nearest position between its characters.
+    /// Points after the end of a wrapped line return a [`CaretAffinity::Trailing`] caret.
+    /// Returns `None` if the section has no lines, e.g. with a vertical layout, see
+    /// [`section_lines`](#method.section_lines).
+    ///
+    /// In lines with right-to-left text, points are hit by the visual run of the nearest
+    /// character, so the caret may be logically before or after it.
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::{*, ab_glyph::*};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// # let mut glyph_brush = GlyphBrushBuilder::using_font(dejavu).build::<(), _>();
+    /// let section = Section::default().add_text(Text::new("hello\nworld"));
+    ///
+    /// let caret = glyph_brush.caret_at_point(&section, point(0.0, 100.0));
+    /// assert_eq!(caret, Some(Caret::new(0, 6)));
+    ///
+    /// let cursor = glyph_brush.caret_rect(&section, Caret::new(0, 6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn caret_at_point<'a, S>(&mut self, section: S, point: Point) -> Option<Caret>
+    where
+        X: 'a,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let layout = section.layout.clone();
+        self.caret_at_point_custom_layout(section, &layout, point)
+    }
+
+    /// Returns the caret rectangle of a [`Caret`] in the given section laid out with a custom
+    /// layout, see [`caret_rect`](#method.caret_rect).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    fn caret_rect_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+        caret: Caret,
+    ) -> Option<Rect>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let (glyphs, lines) = caret::cached_layout(self, &section, custom_layout);
+        SectionCarets {
+            fonts: self.fonts(),
+            section: &section,
+            glyphs: &glyphs,
+            lines: &lines,
+        }
+        .caret_rect(caret)
+    }
+
+    /// Returns the zero width rectangle of a [`Caret`] in the given section, spanning the
+    /// ascent & descent of its line.
+    ///
+    /// Carets at the end of a wrapped line are positioned on that line with
+    /// [`CaretAffinity::Trailing`], otherwise at the start of the next line.
+    /// Returns `None` if the section has no lines, e.g. with a vertical layout, see
+    /// [`section_lines`](#method.section_lines).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    #[inline]
+    fn caret_rect<'a, S>(&mut self, section: S, caret: Caret) -> Option<Rect>
+    where
+        X: 'a,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let layout = section.layout.clone();
+        self.caret_rect_custom_layout(section, &layout, caret)
+    }
+
+    /// Returns the selection rectangles of a `(section_index, byte_index)` range in the
+    /// given section laid out with a custom layout, see
+    /// [`selection_rects`](#method.selection_rects).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    fn selection_rects_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+        range: Range<(usize, usize)>,
+    ) -> Vec<Rect>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let (glyphs, lines) = caret::cached_layout(self, &section, custom_layout);
+        SectionCarets {
+            fonts: self.fonts(),
+            section: &section,
+            glyphs: &glyphs,
+            lines: &lines,
+        }
+        .selection_rects(range)
+    }
+
+    /// Returns a rectangle for each line spanned by a `(section_index, byte_index)` range
+    /// in the given section, e.g. to draw a text selection.
+    ///
+    /// Rectangles span the ascent & descent of each line. Selected empty lines have zero
+    /// width rectangles.
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::{*, ab_glyph::*};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// # let mut glyph_brush = GlyphBrushBuilder::using_font(dejavu).build::<(), _>();
+    /// let section = Section::default().add_text(Text::new("hello\nworld"));
+    ///
+    /// // select "llo\nwo"
+    /// let rects = glyph_brush.selection_rects(&section, (0, 2)..(0, 8));
+    /// assert_eq!(rects.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn selection_rects<'a, S>(&mut self, section: S, range: Range<(usize, usize)>) -> Vec<Rect>
+    where
+        X: 'a,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let layout = section.layout.clone();
+        self.selection_rects_custom_layout(section, &layout, range)
+    }
 }
 
 /// Cut down version of a [`GlyphBrush`](struct.GlyphBrush.html) that can calculate pixel bounds,
@@ -158,11 +521,15 @@ pub trait GlyphCruncher<F: Font = FontArc, X: Clone = Extra> {
 /// 'cache frame', if not used then they will be dropped.
 pub struct GlyphCalculator<F = FontArc, X = Extra, H = DefaultSectionHasher> {
     fonts: Vec<F>,
+    font_fallbacks: FontFallbacks,
 
     // cache of section-layout hash -> computed glyphs, this avoid repeated glyph computation
     // for identical layout/sections common to repeated frame rendering
     calculate_glyph_cache: Mutex<FxHashMap<u64, GlyphedSection<X>>>,
 
+    // cache of section text-layout hash -> measured words, independent of the geometry
+    measured_words_cache: Mutex<FxHashMap<u64, Option<MeasuredWords>>>,
+
     section_hasher: H,
 }
 
@@ -176,8 +543,11 @@ impl<F: Font, X, H: BuildHasher + Clone> GlyphCalculator<F, X, H> {
     pub fn cache_scope(&self) -> GlyphCalculatorGuard<'_, F, X, H> {
         GlyphCalculatorGuard {
             fonts: &self.fonts,
+            font_fallbacks: &self.font_fallbacks,
             glyph_cache: self.calculate_glyph_cache.lock().unwrap(),
             cached: FxHashSet::default(),
+            words_cache: self.measured_words_cache.lock().unwrap(),
+            cached_words: FxHashSet::default(),
             section_hasher: self.section_hasher.clone(),
         }
     }
@@ -193,8 +563,11 @@ impl<F: Font, X, H: BuildHasher + Clone> GlyphCalculator<F, X, H> {
 /// [`GlyphCalculator`](struct.GlyphCalculator.html) scoped cache lock.
 pub struct GlyphCalculatorGuard<'brush, F: 'brush = FontArc, X = Extra, H = DefaultSectionHasher> {
     fonts: &'brush Vec<F>,
+    font_fallbacks: &'brush FontFallbacks,
     glyph_cache: MutexGuard<'brush, FxHashMap<u64, GlyphedSection<X>>>,
     cached: FxHashSet<u64>,
+    words_cache: MutexGuard<'brush, FxHashMap<u64, Option<MeasuredWords>>>,
+    cached_words: FxHashSet<u64>,
     section_hasher: H,
 }
 
@@ -213,12 +586,18 @@ impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCalculatorGuard<'_, F, X, H>
 
         if let Entry::Vacant(entry) = self.glyph_cache.entry(section_hash) {
             let geometry = SectionGeometry::from(section);
-            let glyphs = layout.calculate_glyphs(self.fonts, &geometry, &section.text);
+            let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+                self.fonts,
+                self.font_fallbacks,
+                &geometry,
+                &section.text,
+            );
 
             entry.insert(GlyphedSection {
                 bounds: layout.bounds_rect(&geometry),
                 glyphs,
                 extra: section.text.iter().map(|t| t.extra.clone()).collect(),
+                lines,
             });
         }
 
@@ -269,16 +648,24 @@ impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCruncher<F, X>
         self.glyph_cache[&section_hash]
             .glyphs()
             .fold(None, |b: Option<Rect>, sg| {
+                let text = &section.text[sg.section_index];
                 let sfont = self.fonts[sg.font_id.0].as_scaled(sg.glyph.scale);
+                let half_leading = text.line_height.half_leading(&sfont);
                 let pos = sg.glyph.position;
-                let lbound = Rect {
-                    min: point(
-                        pos.x - sfont.h_side_bearing(sg.glyph.id),
-                        pos.y - sfont.ascent(),
-                    ),
-                    max: point(
-                        pos.x + sfont.h_advance(sg.glyph.id),
-                        pos.y - sfont.descent(),
+                let lbound = match text.placeholder {
+                    Some(placeholder) if sg.is_placeholder() => placeholder.rect(sg),
+                    _ => sg.orientation.rotate_rect(
+                        Rect {
+                            min: point(
+                                pos.x - sfont.h_side_bearing(sg.glyph.id),
+                                pos.y - sfont.ascent() - half_leading,
+                            ),
+                            max: point(
+                                pos.x + sfont.h_advance(sg.glyph.id),
+                                pos.y - sfont.descent() + half_leading,
+                            ),
+                        },
+                        pos,
                     ),
                 };
                 b.map(|b| {
@@ -304,6 +691,50 @@ impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCruncher<F, X>
             })
     }
 
+    fn section_lines_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> &'b [SectionLine]
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let section_hash = self.cache_glyphs(&section, custom_layout);
+        self.cached.insert(section_hash);
+
+        &self.glyph_cache[&section_hash].lines
+    }
+
+    fn measured_words_custom_layout<'a, 'b, S, L>(
+        &'b mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<&'b MeasuredWords>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let words_hash = {
+            let mut hasher = self.section_hasher.build_hasher();
+            custom_layout.hash(&mut hasher);
+            section.to_hashable_parts().hash_text_no_extra(&mut hasher);
+            hasher.finish()
+        };
+        self.cached_words.insert(words_hash);
+
+        self.words_cache
+            .entry(words_hash)
+            .or_insert_with(|| {
+                custom_layout.measure_words(self.fonts, self.font_fallbacks, &section.text)
+            })
+            .as_ref()
+    }
+
     #[inline]
     fn fonts(&self) -> &[F] {
         self.fonts
@@ -314,6 +745,8 @@ impl<F, X, H> Drop for GlyphCalculatorGuard<'_, F, X, H> {
     fn drop(&mut self) {
         let cached = mem::take(&mut self.cached);
         self.glyph_cache.retain(|key, _| cached.contains(key));
+        let cached_words = mem::take(&mut self.cached_words);
+        self.words_cache.retain(|key, _| cached_words.contains(key));
     }
 }
 
@@ -330,6 +763,7 @@ impl<F, X, H> Drop for GlyphCalculatorGuard<'_, F, X, H> {
 #[derive(Debug, Clone)]
 pub struct GlyphCalculatorBuilder<F = FontArc, H = DefaultSectionHasher> {
     font_data: Vec<F>,
+    font_fallbacks: FontFallbacks,
     section_hasher: H,
 }
 
@@ -343,6 +777,7 @@ impl<F: Font> GlyphCalculatorBuilder<F> {
     pub fn using_fonts(fonts: Vec<F>) -> Self {
         GlyphCalculatorBuilder {
             font_data: fonts,
+            font_fallbacks: FontFallbacks::default(),
             section_hasher: DefaultSectionHasher::default(),
         }
     }
@@ -357,6 +792,17 @@ impl<F: Font, H: BuildHasher> GlyphCalculatorBuilder<F, H> {
         FontId(self.font_data.len() - 1)
     }
 
+    /// Sets the fonts to fall back to, in order, for characters `font_id` has no glyphs for.
+    ///
+    /// See [`FontFallbacks`](struct.FontFallbacks.html).
+    pub fn font_fallbacks<I>(mut self, font_id: FontId, fallbacks: I) -> Self
+    where
+        I: IntoIterator<Item = FontId>,
+    {
+        self.font_fallbacks.set(font_id, fallbacks);
+        self
+    }
+
     /// Sets the section hasher. `GlyphCalculator` cannot handle absolute section hash collisions
     /// so use a good hash algorithm.
     ///
@@ -366,6 +812,7 @@ impl<F: Font, H: BuildHasher> GlyphCalculatorBuilder<F, H> {
     pub fn section_hasher<T: BuildHasher>(self, section_hasher: T) -> GlyphCalculatorBuilder<F, T> {
         GlyphCalculatorBuilder {
             font_data: self.font_data,
+            font_fallbacks: self.font_fallbacks,
             section_hasher,
         }
     }
@@ -374,7 +821,9 @@ impl<F: Font, H: BuildHasher> GlyphCalculatorBuilder<F, H> {
     pub fn build<X>(self) -> GlyphCalculator<F, X, H> {
         GlyphCalculator {
             fonts: self.font_data,
+            font_fallbacks: self.font_fallbacks,
             calculate_glyph_cache: Mutex::default(),
+            measured_words_cache: Mutex::default(),
             section_hasher: self.section_hasher,
         }
     }
@@ -385,6 +834,7 @@ pub(crate) struct GlyphedSection<X> {
     pub bounds: Rect,
     pub glyphs: Vec<SectionGlyph>,
     pub extra: Vec<X>,
+    pub lines: Vec<SectionLine>,
 }
 
 impl<X> GlyphedSection<X> {
@@ -480,8 +930,10 @@ mod test {
                 byte_index: 0,
                 glyph: glyph.clone(),
                 font_id: FontId(0),
+                orientation: VerticalOrientation::Upright,
             }],
             extra: vec![Extra { color, z: 0.444 }],
+            lines: Vec::new(),
         };
         let mut b = GlyphedSection {
             bounds: Rect {
@@ -493,8 +945,10 @@ mod test {
                 byte_index: 0,
                 glyph,
                 font_id: FontId(0),
+                orientation: VerticalOrientation::Upright,
             }],
             extra: vec![Extra { color, z: 0.444 }],
+            lines: Vec::new(),
         };
 
         assert_eq!(a, b);
@@ -577,4 +1031,203 @@ mod test {
             assert_relative_eq!(sg.glyph.position.y, bounded_sg.glyph.position.y);
         }
     }
+
+    #[test]
+    fn font_fallbacks() {
+        let calc =
+            GlyphCalculatorBuilder::using_fonts(vec![OPEN_SANS_LIGHT.clone(), MONO_FONT.clone()])
+                .font_fallbacks(FontId(0), [FontId(1)])
+                .build();
+        let mut calc = calc.cache_scope();
+
+        let section = Section::default().add_text(Text::new("a\u{2192}\u{2603}"));
+
+        let font_ids: Vec<_> = calc.glyphs(&section).map(|sg| sg.font_id).collect();
+        assert_eq!(font_ids, [FontId(0), FontId(1), FontId(1)]);
+    }
+
+    #[test]
+    fn glyph_bounds_line_height() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let section = Section::default().add_text(
+            Text::new("Hello\nWorld")
+                .with_scale(16.0)
+                .with_line_height(LineHeight::Multiplier(2.0))
+                .with_letter_spacing(1.0),
+        );
+
+        let g_bounds = glyphs.glyph_bounds(&section).expect("None bounds");
+
+        // 2 lines of 32px
+        assert_relative_eq!(g_bounds.min.y, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(g_bounds.max.y, 64.0, epsilon = 1e-4);
+
+        // 5 letter spaced chars, the last without trailing spacing
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let g_width = sfont.h_advance(MONO_FONT.glyph_id('W'));
+        assert_relative_eq!(g_bounds.max.x, g_width * 5.0 + 4.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn glyph_bounds_baseline_shift() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let section = Section::default()
+            .add_text(Text::new("x").with_scale(16.0))
+            .add_text(
+                Text::new("2")
+                    .with_scale(16.0)
+                    .with_baseline_shift(BaselineShift::Px(6.0)),
+            );
+
+        let g_bounds = glyphs.glyph_bounds(&section).expect("None bounds");
+
+        // the line grows to include the raised text
+        let sfont = MONO_FONT.as_scaled(16.0);
+        assert_relative_eq!(g_bounds.min.y, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(
+            g_bounds.max.y,
+            sfont.ascent() - sfont.descent() + 6.0,
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn glyph_bounds_vertical() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let section = Section::default()
+            .add_text(Text::new("提高\n代碼").with_scale(16.0))
+            .with_screen_position((100.0, 20.0))
+            .with_layout(Layout::default_vertical());
+
+        let g_bounds = glyphs.glyph_bounds(&section).expect("None bounds");
+
+        // 2 columns to the left of the render position
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let column_w = sfont.height() + sfont.line_gap();
+        assert!(g_bounds.max.x <= 100.0, "{g_bounds:?}");
+        assert!(g_bounds.min.x >= 100.0 - column_w * 2.0, "{g_bounds:?}");
+        assert!(g_bounds.min.x < 100.0 - column_w, "{g_bounds:?}");
+
+        // 2 characters high
+        assert_relative_eq!(g_bounds.min.y, 20.0);
+        assert_relative_eq!(g_bounds.max.y, 20.0 + sfont.height() * 2.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn glyph_bounds_vertical_sideways() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let section = Section::default()
+            .add_text(Text::new("ab").with_scale(16.0))
+            .with_screen_position((100.0, 20.0))
+            .with_layout(Layout::default_vertical());
+
+        let g_bounds = glyphs.glyph_bounds(&section).expect("None bounds");
+
+        // rotated latin fills a single column advancing down by the horizontal advances
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let a_advance = sfont.h_advance(sfont.glyph_id('a'));
+        assert_relative_eq!(g_bounds.width(), sfont.height(), epsilon = 1e-3);
+        assert!(g_bounds.max.x <= 100.0, "{g_bounds:?}");
+        assert_relative_eq!(g_bounds.min.y, 20.0, epsilon = 1e-3);
+        assert_relative_eq!(g_bounds.max.y, 20.0 + a_advance * 2.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn section_lines() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let section = Section::default()
+            .add_text(Text::new("Hello\nWorld").with_scale(16.0))
+            .with_screen_position((10.0, 20.0));
+
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let line_h = sfont.height() + sfont.line_gap();
+
+        let lines = glyphs.section_lines(&section).to_vec();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].glyphs, 0..5);
+        assert_eq!(lines[0].start, (0, 0));
+        assert!(lines[0].hard_break);
+        assert_eq!(lines[1].glyphs, 5..10);
+        assert_eq!(lines[1].start, (0, 6));
+        assert_eq!(lines[1].end, (0, 11));
+        assert_relative_eq!(lines[0].baseline, 20.0 + sfont.ascent(), epsilon = 1e-4);
+        assert_relative_eq!(
+            lines[1].baseline,
+            lines[0].baseline + line_h,
+            epsilon = 1e-4
+        );
+
+        // cached with the glyphs
+        assert_eq!(glyphs.section_lines(&section), &lines[..]);
+        let line_glyphs: Vec<_> = glyphs.glyphs(&section).collect();
+        assert_eq!(line_glyphs[lines[1].glyphs.clone()][0].byte_index, 6);
+
+        let vertical = section.with_layout(Layout::default_vertical());
+        assert!(glyphs.section_lines(&vertical).is_empty());
+    }
+
+    #[test]
+    fn glyphs_fitted() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let sfont = MONO_FONT.as_scaled(24.0);
+        let width = 8.0 * sfont.h_advance(sfont.glyph_id('a'));
+        let section = Section::default()
+            .add_text(Text::new("Continue").with_scale(12.0))
+            .with_bounds((width, f32::INFINITY));
+        let fit = FitLayout::new(Layout::default_single_line(), 10.0, 40.0);
+
+        let (fitted, scale) = glyphs.glyphs_fitted(&section, &fit);
+        let fitted: Vec<_> = fitted.cloned().collect();
+        assert!(scale.y <= 24.0 && scale.y > 23.9, "{scale:?}");
+        assert_eq!(fitted.len(), 8);
+        assert!(fitted.iter().all(|sg| sg.glyph.scale == scale));
+
+        // cached as a custom layout
+        let custom: Vec<_> = glyphs
+            .glyphs_custom_layout(&section, &fit)
+            .cloned()
+            .collect();
+        assert_eq!(custom, fitted);
+    }
+
+    #[test]
+    fn intrinsic_widths_height_for_width() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let sfont = MONO_FONT.as_scaled(16.0);
+        let advance = sfont.h_advance(sfont.glyph_id('a'));
+        let line_h = sfont.height() + sfont.line_gap();
+        let section = Section::default().add_text(Text::new("aaa aaaaa aa").with_scale(16.0));
+
+        let widths = glyphs.intrinsic_widths(&section).unwrap();
+        assert_relative_eq!(widths.min_content, advance * 5.0);
+        assert_relative_eq!(widths.max_content, advance * 12.0);
+
+        let height = glyphs.height_for_width(&section, widths.min_content);
+        assert_relative_eq!(height.unwrap(), line_h * 3.0, epsilon = 1e-4);
+        let height = glyphs.height_for_width(&section, widths.max_content);
+        assert_relative_eq!(height.unwrap(), line_h, epsilon = 1e-4);
+
+        // words are measured once regardless of the section geometry
+        let bounded = section.clone().with_bounds((50.0, 20.0));
+        assert_eq!(glyphs.intrinsic_widths(&bounded), Some(widths));
+        assert_eq!(glyphs.words_cache.len(), 1);
+
+        let vertical = section.with_layout(Layout::default_vertical());
+        assert_eq!(glyphs.intrinsic_widths(&vertical), None);
+        assert_eq!(glyphs.height_for_width(&vertical, 50.0), None);
+    }
 }
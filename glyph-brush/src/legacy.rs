@@ -149,7 +149,7 @@ impl<'a> From<&crate::Section<'a>> for VariedSection<'a> {
             text: s.text.iter().map(SectionText::from).collect(),
             bounds: s.bounds,
             screen_position: s.screen_position,
-            layout: s.layout,
+            layout: s.layout.clone(),
             // take the first z value, good enough for legacy compatibility
             z: s.text.first().map(|t| t.extra.z).unwrap_or(0.0),
         }
@@ -162,7 +162,7 @@ impl Hash for VariedSection<'_> {
             screen_position: (screen_x, screen_y),
             bounds: (bound_w, bound_h),
             z,
-            layout,
+            ref layout,
             ref text,
         } = *self;
 
@@ -211,7 +211,7 @@ impl VariedSection<'_> {
             screen_position: self.screen_position,
             bounds: self.bounds,
             z: self.z,
-            layout: self.layout,
+            layout: self.layout.clone(),
             text: self.text.iter().map(OwnedSectionText::from).collect(),
         }
     }
@@ -230,7 +230,7 @@ impl<'a> From<&VariedSection<'a>> for crate::Section<'a> {
     #[inline]
     fn from(s: &VariedSection<'a>) -> Self {
         crate::Section::builder()
-            .with_layout(s.layout)
+            .with_layout(s.layout.clone())
             .with_bounds(s.bounds)
             .with_screen_position(s.screen_position)
             .with_text(s.text.iter().map(|t| t.to_text(s.z)).collect())
@@ -258,7 +258,7 @@ impl<'a> From<VariedSection<'a>> for crate::Section<'a> {
 ///     ..Section::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Section<'a> {
     /// Text to render
     pub text: &'a str,
@@ -308,7 +308,7 @@ impl<'a> From<&Section<'a>> for VariedSection<'a> {
             screen_position,
             bounds,
             z,
-            layout,
+            ref layout,
             font_id,
         } = *s;
 
@@ -322,7 +322,7 @@ impl<'a> From<&Section<'a>> for VariedSection<'a> {
             screen_position,
             bounds,
             z,
-            layout,
+            layout: layout.clone(),
         }
     }
 }
@@ -354,7 +354,7 @@ impl<'a> From<&Section<'a>> for crate::Section<'a> {
             screen_position,
             bounds,
             z,
-            layout,
+            ref layout,
             font_id,
         } = *s;
 
@@ -368,7 +368,7 @@ impl<'a> From<&Section<'a>> for crate::Section<'a> {
             )
             .with_screen_position(screen_position)
             .with_bounds(bounds)
-            .with_layout(layout)
+            .with_layout(layout.clone())
     }
 }
 
@@ -424,7 +424,7 @@ impl OwnedVariedSection {
             screen_position: self.screen_position,
             bounds: self.bounds,
             z: self.z,
-            layout: self.layout,
+            layout: self.layout.clone(),
             text: self.text.iter().map(|t| t.into()).collect(),
         }
     }
@@ -30,6 +30,7 @@
 //! # Ok(())
 //! # }
 //! ```
+mod caret;
 mod extra;
 mod glyph_brush;
 mod glyph_calculator;
@@ -37,7 +38,7 @@ mod section;
 
 pub mod legacy;
 
-pub use crate::{extra::*, glyph_brush::*, glyph_calculator::*, section::*};
+pub use crate::{caret::*, extra::*, glyph_brush::*, glyph_calculator::*, section::*};
 pub use glyph_brush_draw_cache::Rectangle;
 pub use glyph_brush_layout::*;
 
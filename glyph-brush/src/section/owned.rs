@@ -57,7 +57,7 @@ impl<X> OwnedSection<X> {
             text,
             screen_position: self.screen_position,
             bounds: self.bounds,
-            layout: self.layout,
+            layout: self.layout.clone(),
         }
     }
 }
@@ -67,7 +67,7 @@ impl<X: Clone> OwnedSection<X> {
         Section {
             screen_position: self.screen_position,
             bounds: self.bounds,
-            layout: self.layout,
+            layout: self.layout.clone(),
             text: self.text.iter().map(|t| t.into()).collect(),
         }
     }
@@ -109,6 +109,17 @@ pub struct OwnedText<X = Extra> {
     /// either `FontId::default()` or the return of
     /// [`add_font`](struct.GlyphBrushBuilder.html#method.add_font).
     pub font_id: FontId,
+    /// Height of lines containing this text. Defaults to [`LineHeight::Normal`].
+    pub line_height: LineHeight,
+    /// Extra pixel spacing added after each character, may be negative. Defaults to 0.
+    pub letter_spacing: f32,
+    /// Extra pixel spacing added after each space, in addition to `letter_spacing`,
+    /// may be negative. Defaults to 0.
+    pub word_spacing: f32,
+    /// Inline object laid out in place of the text, if any. Defaults to `None`.
+    pub placeholder: Option<Placeholder>,
+    /// Vertical offset from the line's baseline. Defaults to [`BaselineShift::Baseline`].
+    pub baseline_shift: BaselineShift,
     // Extra stuff for vertex generation.
     pub extra: X,
 }
@@ -132,12 +143,47 @@ impl<X> OwnedText<X> {
         self
     }
 
+    #[inline]
+    pub fn with_line_height(mut self, line_height: LineHeight) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    #[inline]
+    pub fn with_letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
+    #[inline]
+    pub fn with_word_spacing(mut self, word_spacing: f32) -> Self {
+        self.word_spacing = word_spacing;
+        self
+    }
+
+    #[inline]
+    pub fn with_placeholder(mut self, placeholder: Placeholder) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    #[inline]
+    pub fn with_baseline_shift(mut self, baseline_shift: BaselineShift) -> Self {
+        self.baseline_shift = baseline_shift;
+        self
+    }
+
     #[inline]
     pub fn with_extra<X2>(self, extra: X2) -> OwnedText<X2> {
         OwnedText {
             text: self.text,
             scale: self.scale,
             font_id: self.font_id,
+            line_height: self.line_height,
+            letter_spacing: self.letter_spacing,
+            word_spacing: self.word_spacing,
+            placeholder: self.placeholder,
+            baseline_shift: self.baseline_shift,
             extra,
         }
     }
@@ -169,6 +215,11 @@ impl<X: Default> Default for OwnedText<X> {
             text: String::new(),
             scale: PxScale::from(16.0),
             font_id: <_>::default(),
+            line_height: <_>::default(),
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
+            placeholder: None,
+            baseline_shift: <_>::default(),
             extra: <_>::default(),
         }
     }
@@ -181,6 +232,11 @@ impl<'a, X: Clone> From<&'a OwnedText<X>> for Text<'a, X> {
             text: owned.text.as_str(),
             scale: owned.scale,
             font_id: owned.font_id,
+            line_height: owned.line_height,
+            letter_spacing: owned.letter_spacing,
+            word_spacing: owned.word_spacing,
+            placeholder: owned.placeholder,
+            baseline_shift: owned.baseline_shift,
             extra: owned.extra.clone(),
         }
     }
@@ -193,6 +249,11 @@ impl<X: Clone> From<&Text<'_, X>> for OwnedText<X> {
             text: s.text.into(),
             scale: s.scale,
             font_id: s.font_id,
+            line_height: s.line_height,
+            letter_spacing: s.letter_spacing,
+            word_spacing: s.word_spacing,
+            placeholder: s.placeholder,
+            baseline_shift: s.baseline_shift,
             extra: s.extra.clone(),
         }
     }
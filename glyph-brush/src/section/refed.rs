@@ -106,7 +106,7 @@ impl<'a, X> Section<'a, X> {
             text,
             screen_position: self.screen_position,
             bounds: self.bounds,
-            layout: self.layout,
+            layout: self.layout.clone(),
         }
     }
 }
@@ -131,7 +131,7 @@ impl<X: Hash> Hash for Section<'_, X> {
         let Section {
             screen_position: (screen_x, screen_y),
             bounds: (bound_w, bound_h),
-            layout,
+            ref layout,
             ref text,
         } = *self;
 
@@ -162,6 +162,17 @@ pub struct Text<'a, X = Extra> {
     /// It must be a valid id in the `FontMap` used for layout calls.
     /// The default `FontId(0)` should always be valid.
     pub font_id: FontId,
+    /// Height of lines containing this text. Defaults to [`LineHeight::Normal`].
+    pub line_height: LineHeight,
+    /// Extra pixel spacing added after each character, may be negative. Defaults to 0.
+    pub letter_spacing: f32,
+    /// Extra pixel spacing added after each space, in addition to `letter_spacing`,
+    /// may be negative. Defaults to 0.
+    pub word_spacing: f32,
+    /// Inline object laid out in place of the text, if any. Defaults to `None`.
+    pub placeholder: Option<Placeholder>,
+    /// Vertical offset from the line's baseline. Defaults to [`BaselineShift::Baseline`].
+    pub baseline_shift: BaselineShift,
     /// Extra stuff for vertex generation.
     pub extra: X,
 }
@@ -173,6 +184,11 @@ impl<X: Default> Default for Text<'static, X> {
             text: "",
             scale: PxScale::from(16.0),
             font_id: <_>::default(),
+            line_height: <_>::default(),
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
+            placeholder: None,
+            baseline_shift: <_>::default(),
             extra: <_>::default(),
         }
     }
@@ -185,6 +201,11 @@ impl<'a, X> Text<'a, X> {
             text,
             scale: self.scale,
             font_id: self.font_id,
+            line_height: self.line_height,
+            letter_spacing: self.letter_spacing,
+            word_spacing: self.word_spacing,
+            placeholder: self.placeholder,
+            baseline_shift: self.baseline_shift,
             extra: self.extra,
         }
     }
@@ -201,12 +222,47 @@ impl<'a, X> Text<'a, X> {
         self
     }
 
+    #[inline]
+    pub fn with_line_height(mut self, line_height: LineHeight) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    #[inline]
+    pub fn with_letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
+    #[inline]
+    pub fn with_word_spacing(mut self, word_spacing: f32) -> Self {
+        self.word_spacing = word_spacing;
+        self
+    }
+
+    #[inline]
+    pub fn with_placeholder(mut self, placeholder: Placeholder) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    #[inline]
+    pub fn with_baseline_shift(mut self, baseline_shift: BaselineShift) -> Self {
+        self.baseline_shift = baseline_shift;
+        self
+    }
+
     #[inline]
     pub fn with_extra<X2>(self, extra: X2) -> Text<'a, X2> {
         Text {
             text: self.text,
             scale: self.scale,
             font_id: self.font_id,
+            line_height: self.line_height,
+            letter_spacing: self.letter_spacing,
+            word_spacing: self.word_spacing,
+            placeholder: self.placeholder,
+            baseline_shift: self.baseline_shift,
             extra,
         }
     }
@@ -240,6 +296,11 @@ impl<X> ToSectionText for Text<'_, X> {
             text: self.text,
             scale: self.scale,
             font_id: self.font_id,
+            line_height: self.line_height,
+            letter_spacing: self.letter_spacing,
+            word_spacing: self.word_spacing,
+            placeholder: self.placeholder,
+            baseline_shift: self.baseline_shift,
         }
     }
 }
@@ -252,20 +313,51 @@ fn hash_section_text<X: Hash, H: Hasher>(state: &mut H, text: &[Text<'_, X>]) {
             scale,
             font_id,
             ref extra,
+            ..
         } = *t;
 
         let ord_floats: [OrderedFloat<_>; 2] = [scale.x.into(), scale.y.into()];
 
-        (text, font_id, extra, ord_floats).hash(state);
+        (text, font_id, extra, ord_floats, spacing_hash_key(t)).hash(state);
     }
 }
 
+/// Returns a hashable form of the text's line height, spacing, placeholder & baseline shift.
+#[inline]
+fn spacing_hash_key<X>(t: &Text<'_, X>) -> ([u8; 2], bool, [OrderedFloat<f32>; 7]) {
+    let (kind, line_height) = match t.line_height {
+        LineHeight::Normal => (0, 0.0),
+        LineHeight::Px(px) => (1, px),
+        LineHeight::Multiplier(m) => (2, m),
+    };
+    let (shift_kind, shift) = match t.baseline_shift {
+        BaselineShift::Baseline => (0, 0.0),
+        BaselineShift::Px(px) => (1, px),
+        BaselineShift::Superscript => (2, 0.0),
+        BaselineShift::Subscript => (3, 0.0),
+    };
+    let placeholder = t.placeholder.unwrap_or_default();
+    (
+        [kind, shift_kind],
+        t.placeholder.is_some(),
+        [
+            line_height.into(),
+            t.letter_spacing.into(),
+            t.word_spacing.into(),
+            placeholder.width.into(),
+            placeholder.height.into(),
+            placeholder.baseline_offset.into(),
+            shift.into(),
+        ],
+    )
+}
+
 impl<X: Clone> Section<'_, X> {
     pub fn to_owned(&self) -> OwnedSection<X> {
         OwnedSection {
             screen_position: self.screen_position,
             bounds: self.bounds,
-            layout: self.layout,
+            layout: self.layout.clone(),
             text: self.text.iter().map(OwnedText::from).collect(),
         }
     }
@@ -323,7 +415,29 @@ impl<X: Hash> HashableSectionParts<'_, X> {
 
             let ord_floats: &[OrderedFloat<_>] = &[scale.x.into(), scale.y.into()];
 
-            (text, font_id, ord_floats).hash(state);
+            (text, font_id, ord_floats, spacing_hash_key(t)).hash(state);
+        }
+    }
+
+    /// Hashes the text & layout properties, excluding the scales & extra.
+    #[inline]
+    pub fn hash_text_no_scale<H: Hasher>(&self, state: &mut H) {
+        for t in self.text {
+            let Text { text, font_id, .. } = *t;
+
+            (text, font_id, spacing_hash_key(t)).hash(state);
+        }
+    }
+
+    /// Hashes the text layout properties, excluding the text strings & extra.
+    #[inline]
+    pub fn hash_text_style<H: Hasher>(&self, state: &mut H) {
+        for t in self.text {
+            let Text { scale, font_id, .. } = *t;
+
+            let ord_floats: &[OrderedFloat<_>] = &[scale.x.into(), scale.y.into()];
+
+            (font_id, ord_floats, spacing_hash_key(t)).hash(state);
         }
     }
 
@@ -0,0 +1,241 @@
+use crate::{lines::Line, SectionText};
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level, ParagraphInfo};
+
+/// Resolved Unicode Bidirectional Algorithm (UAX #9) embedding levels for all section text.
+///
+/// Only constructed for text that requires reordering, pure left-to-right text skips all
+/// bidi handling.
+#[derive(Clone)]
+pub(crate) struct Bidi {
+    /// Start offset of each section's text in the concatenated text.
+    section_offsets: Vec<usize>,
+    /// Original bidi class of each byte of the concatenated text.
+    classes: Vec<BidiClass>,
+    /// Resolved embedding level of each byte of the concatenated text.
+    levels: Vec<Level>,
+    paragraphs: Vec<ParagraphInfo>,
+}
+
+impl Bidi {
+    /// Returns resolved levels for the sections' text, or `None` if the text contains
+    /// nothing right-to-left.
+    pub(crate) fn new<'a>(sections: impl Iterator<Item = SectionText<'a>> + Clone) -> Option<Self> {
+        let maybe_rtl = sections.clone().flat_map(|s| s.text.chars()).any(|c| {
+            use BidiClass::*;
+            matches!(bidi_class(c), R | AL | AN | RLE | RLO | RLI | FSI)
+        });
+        if !maybe_rtl {
+            return None;
+        }
+
+        let mut text = String::new();
+        let mut section_offsets = Vec::new();
+        for section in sections {
+            section_offsets.push(text.len());
+            text.push_str(section.text);
+        }
+
+        let info = BidiInfo::new(&text, None);
+        if !info.has_rtl() {
+            return None;
+        }
+
+        Some(Self {
+            section_offsets,
+            classes: info.original_classes,
+            levels: info.levels,
+            paragraphs: info.paragraphs,
+        })
+    }
+
+    #[inline]
+    fn offset(&self, section_index: usize, byte_index: usize) -> usize {
+        self.section_offsets[section_index] + byte_index
+    }
+
+    /// Returns `true` if the character is resolved to a right-to-left embedding level.
+    #[inline]
+    pub(crate) fn is_rtl(&self, section_index: usize, byte_index: usize) -> bool {
+        self.levels[self.offset(section_index, byte_index)].is_rtl()
+    }
+
+    fn paragraph(&self, offset: usize) -> &ParagraphInfo {
+        let idx = self
+            .paragraphs
+            .partition_point(|p| p.range.end <= offset)
+            .min(self.paragraphs.len() - 1);
+        &self.paragraphs[idx]
+    }
+
+    /// Reorders line glyphs from logical into visual order, repositioning them so the
+    /// visible glyphs still start from zero. Also flags the line's paragraph direction.
+    ///
+    /// Synthetic glyphs take the level of the preceding glyph.
+    pub(crate) fn reorder(&self, line: &mut Line) {
+        let Some(first) = line.glyphs.iter().find(|sg| !sg.is_synthetic()) else {
+            return;
+        };
+        let para_level = self
+            .paragraph(self.offset(first.section_index, first.byte_index))
+            .level;
+        line.rtl = para_level.is_rtl();
+
+        let mut level = para_level;
+        let mut levels: Vec<Level> = line
+            .glyphs
+            .iter()
+            .map(|sg| {
+                if !sg.is_synthetic() {
+                    level = self.levels[self.offset(sg.section_index, sg.byte_index)];
+                }
+                level
+            })
+            .collect();
+
+        // Rule L1: reset separators & whitespace preceding separators or the end of the line
+        let mut trailing = true;
+        for (idx, sg) in line.glyphs.iter().enumerate().rev() {
+            use BidiClass::*;
+            if sg.is_synthetic() {
+                trailing = false;
+                continue;
+            }
+            match self.classes[self.offset(sg.section_index, sg.byte_index)] {
+                S | B => {
+                    levels[idx] = para_level;
+                    trailing = true;
+                }
+                WS | FSI | LRI | RLI | PDI if trailing => levels[idx] = para_level,
+                _ => trailing = false,
+            }
+        }
+
+        if levels.iter().all(|l| l.is_ltr()) {
+            return;
+        }
+
+        // Clusters of consecutive glyphs from the same character, e.g. shaped marks, are
+        // reordered as a unit keeping their relative positions.
+        // Each cluster occupies the logical space until the next one.
+        let mut clusters: Vec<(usize, Level)> = Vec::new();
+        for (idx, sg) in line.glyphs.iter().enumerate() {
+            match clusters.last().map(|(start, _)| &line.glyphs[*start]) {
+                Some(prev)
+                    if prev.section_index == sg.section_index
+                        && prev.byte_index == sg.byte_index => {}
+                _ => clusters.push((idx, levels[idx])),
+            }
+        }
+        let cluster_levels: Vec<Level> = clusters.iter().map(|(_, level)| *level).collect();
+
+        let mut caret = 0.0;
+        let mut reordered = Vec::with_capacity(line.glyphs.len());
+        for cluster_idx in BidiInfo::reorder_visual(&cluster_levels) {
+            let start = clusters[cluster_idx].0;
+            let end = clusters
+                .get(cluster_idx + 1)
+                .map_or(line.glyphs.len(), |c| c.0);
+            let start_x = line.glyphs[start].glyph.position.x;
+            let end_x = line
+                .glyphs
+                .get(end)
+                .map_or(line.advance_width, |sg| sg.glyph.position.x);
+
+            for sg in &line.glyphs[start..end] {
+                let mut sg = sg.clone();
+                sg.glyph.position.x += caret - start_x;
+                reordered.push(sg);
+            }
+            caret += end_x - start_x;
+        }
+
+        if line.rtl {
+            // trailing whitespace is now visually leftmost, hang it outside the line
+            let trail_width = line.advance_width - line.rightmost;
+            if trail_width > 0.0 {
+                for sg in &mut reordered {
+                    sg.glyph.position.x -= trail_width;
+                }
+            }
+        }
+
+        line.glyphs = reordered;
+    }
+}
+
+/// Returns the mirrored character to use in right-to-left text, if any.
+#[inline]
+pub(crate) fn mirrored(c: char) -> Option<char> {
+    unicode_bidi_mirroring::get_mirrored(c)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        FontFallbacks, GlyphPositioner, HorizontalAlign, Indents, Layout, SectionGeometry,
+        SectionText,
+    };
+    use ab_glyph::*;
+    use approx::assert_relative_eq;
+    use std::sync::LazyLock;
+
+    static A_FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap()
+    });
+
+    #[test]
+    fn rtl_hanging_indent() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('\u{5d0}'));
+        let space = A_FONT.glyph_id(' ');
+        let bound_w = advance * 7.5 + 30.0;
+        let layout = |h_align: HorizontalAlign| {
+            let (glyphs, lines) = Layout::default_wrap()
+                .h_align(h_align)
+                .indents(Indents {
+                    first_line: 30.0,
+                    hanging: 10.0,
+                    ..<_>::default()
+                })
+                .calculate_glyphs_and_lines(
+                    &[&*A_FONT],
+                    &FontFallbacks::default(),
+                    &SectionGeometry {
+                        bounds: (bound_w, f32::INFINITY),
+                        ..<_>::default()
+                    },
+                    &[SectionText {
+                        text: "\u{5d0}\u{5d1}\u{5d2} \u{5d3}\u{5d4}\u{5d5} \u{5d6}\u{5d7}\u{5d8}",
+                        scale: PxScale::from(20.0),
+                        ..<_>::default()
+                    }],
+                );
+            assert_eq!(lines.len(), 2);
+            assert!(lines.iter().all(|l| l.rtl));
+
+            // (left, right) visible extent of each line
+            lines
+                .iter()
+                .map(|line| {
+                    glyphs[line.glyphs.clone()]
+                        .iter()
+                        .filter(|sg| sg.glyph.id != space)
+                        .fold((f32::INFINITY, f32::NEG_INFINITY), |(l, r), sg| {
+                            let x = sg.glyph.position.x;
+                            (l.min(x), r.max(x + sfont.h_advance(sg.glyph.id)))
+                        })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // indents apply on the right, the start side of a right-to-left paragraph
+        let start = layout(HorizontalAlign::Start);
+        assert_relative_eq!(start[0].1, bound_w - 30.0, epsilon = 1e-3);
+        assert_relative_eq!(start[1].1, bound_w - 10.0, epsilon = 1e-3);
+
+        // leaving the left edge unindented
+        let left = layout(HorizontalAlign::Left);
+        assert_relative_eq!(left[0].0, 0.0);
+        assert_relative_eq!(left[1].0, 0.0);
+    }
+}
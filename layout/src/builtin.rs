@@ -1,6 +1,20 @@
 use super::{BuiltInLineBreaker, GlyphPositioner, LineBreaker, SectionGeometry, ToSectionText};
-use crate::{characters::Characters, GlyphChange, SectionGlyph};
+use crate::{
+    bidi::Bidi,
+    characters::Characters,
+    linebreak::WordBreaker,
+    lines::{Line, Lines},
+    optimal::OptimalBreaks,
+    vertical,
+    words::{IntrinsicWidths, MeasuredWords, WhiteSpace, Word},
+    BaselineShift, BreakStrategy, Exclusion, FontFallbacks, GlyphChange, LineHeight, OverflowWrap,
+    PreviousLayout, SectionGlyph, SectionLine, SectionText, TabStops, TabWidth, WordBreak,
+};
 use ab_glyph::*;
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
 
 /// Built-in [`GlyphPositioner`](trait.GlyphPositioner.html) implementations.
 ///
@@ -12,22 +26,53 @@ use ab_glyph::*;
 /// # use glyph_brush_layout::*;
 /// let layout = Layout::default().h_align(HorizontalAlign::Right);
 /// ```
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum Layout<L: LineBreaker> {
     /// Renders a single line from left-to-right according to the inner alignment.
     /// Hard breaking will end the line, partially hitting the width bound will end the line.
+    ///
+    /// With an [`Ellipsis`](enum.Ellipsis.html) overflow the line is instead truncated
+    /// per character to fit the width bound when it would exceed it, or is ended early
+    /// by a hard break. With [`WhiteSpace::Pre`](enum.WhiteSpace.html#variant.Pre) the
+    /// line isn't ended by the width bound.
+    ///
+    /// Only the `overflow`, `tab_stops` & `white_space` [`LayoutOptions`] apply.
     SingleLine {
         line_breaker: L,
         h_align: HorizontalAlign,
         v_align: VerticalAlign,
+        options: LayoutOptions,
     },
     /// Renders multiple lines from left-to-right according to the inner alignment.
     /// Hard breaking characters will cause advancement to another line.
     /// A characters hitting the width bound will also cause another line to start.
+    ///
+    /// Lines are also clamped, truncated, indented, flowed around exclusions & broken
+    /// according to the [`LayoutOptions`].
     Wrap {
         line_breaker: L,
         h_align: HorizontalAlign,
         v_align: VerticalAlign,
+        options: LayoutOptions,
+    },
+    /// Renders columns from top-to-bottom, progressing right-to-left, e.g. for Japanese
+    /// tategaki. Hard breaking characters will cause advancement to another column.
+    /// A character hitting the height bound will also cause another column to start.
+    ///
+    /// `h_align` positions the block of columns, `Start` & `Justify` align as `Right`
+    /// and `End` as `Left`. `v_align` aligns characters within each column, baseline
+    /// alignments align as `Top` or `Bottom` & optical centering as `Center`.
+    ///
+    /// Upright characters, like CJK, advance by the font's vertical metrics when available,
+    /// otherwise by the font's ascent to descent height. Sideways characters, like Latin,
+    /// advance by their horizontal advance & are marked
+    /// [`VerticalOrientation::Sideways`](enum.VerticalOrientation.html), to be drawn rotated
+    /// 90° clockwise about their glyph position, see
+    /// [`SectionGlyph::orientation`](struct.SectionGlyph.html#structfield.orientation).
+    Vertical {
+        line_breaker: L,
+        h_align: HorizontalAlign,
+        v_align: VerticalAlign,
     },
 }
 
@@ -45,6 +90,7 @@ impl Layout<BuiltInLineBreaker> {
             line_breaker: BuiltInLineBreaker::default(),
             h_align: HorizontalAlign::Left,
             v_align: VerticalAlign::Top,
+            options: LayoutOptions::default(),
         }
     }
 
@@ -54,75 +100,216 @@ impl Layout<BuiltInLineBreaker> {
             line_breaker: BuiltInLineBreaker::default(),
             h_align: HorizontalAlign::Left,
             v_align: VerticalAlign::Top,
+            options: LayoutOptions::default(),
+        }
+    }
+
+    #[inline]
+    pub fn default_vertical() -> Self {
+        Layout::Vertical {
+            line_breaker: BuiltInLineBreaker::default(),
+            h_align: HorizontalAlign::Right,
+            v_align: VerticalAlign::Top,
         }
     }
 }
 
 impl<L: LineBreaker> Layout<L> {
     /// Returns an identical `Layout` but with the input `h_align`
-    pub fn h_align(self, h_align: HorizontalAlign) -> Self {
-        use crate::Layout::*;
-        match self {
-            SingleLine {
-                line_breaker,
-                v_align,
-                ..
-            } => SingleLine {
-                line_breaker,
-                v_align,
-                h_align,
-            },
-            Wrap {
-                line_breaker,
-                v_align,
-                ..
-            } => Wrap {
-                line_breaker,
-                v_align,
-                h_align,
-            },
+    pub fn h_align(mut self, h_align: HorizontalAlign) -> Self {
+        match &mut self {
+            Layout::SingleLine { h_align: h, .. }
+            | Layout::Wrap { h_align: h, .. }
+            | Layout::Vertical { h_align: h, .. } => *h = h_align,
         }
+        self
     }
 
     /// Returns an identical `Layout` but with the input `v_align`
-    pub fn v_align(self, v_align: VerticalAlign) -> Self {
+    pub fn v_align(mut self, v_align: VerticalAlign) -> Self {
+        match &mut self {
+            Layout::SingleLine { v_align: v, .. }
+            | Layout::Wrap { v_align: v, .. }
+            | Layout::Vertical { v_align: v, .. } => *v = v_align,
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `options`.
+    ///
+    /// Vertical layouts are unaffected.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// let options = LayoutOptions::default()
+    ///     .with_break_strategy(BreakStrategy::Optimal)
+    ///     .with_overflow_wrap(OverflowWrap::Anywhere);
+    /// let layout = Layout::default_wrap().options(options);
+    /// ```
+    pub fn options(mut self, options: LayoutOptions) -> Self {
+        match &mut self {
+            Layout::SingleLine { options: o, .. } | Layout::Wrap { options: o, .. } => *o = options,
+            Layout::Vertical { .. } => {}
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` with `update` applied to its options, vertical layouts
+    /// have none.
+    #[inline]
+    fn update_options(mut self, update: impl FnOnce(&mut LayoutOptions)) -> Self {
+        if let Layout::SingleLine { options, .. } | Layout::Wrap { options, .. } = &mut self {
+            update(options);
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `overflow`.
+    ///
+    /// Vertical layouts are unaffected.
+    pub fn overflow(self, overflow: Overflow) -> Self {
+        self.update_options(|o| o.overflow = overflow)
+    }
+
+    /// Returns an identical `Layout` but with the input overflow `max_lines`.
+    ///
+    /// Vertical layouts are unaffected.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// let layout = Layout::default().max_lines(2).ellipsis(Ellipsis::End("…"));
+    /// ```
+    pub fn max_lines(self, max_lines: usize) -> Self {
+        self.update_options(|o| o.overflow.max_lines = Some(max_lines))
+    }
+
+    /// Returns an identical `Layout` but with the input overflow `ellipsis`.
+    ///
+    /// Vertical layouts are unaffected.
+    pub fn ellipsis(self, ellipsis: Ellipsis) -> Self {
+        self.update_options(|o| o.overflow.ellipsis = Some(ellipsis))
+    }
+
+    /// Returns an identical `Layout` but with the input `tab_stops`.
+    ///
+    /// Vertical layouts are unaffected.
+    pub fn tab_stops(self, tab_stops: TabStops) -> Self {
+        self.update_options(|o| o.tab_stops = tab_stops)
+    }
+
+    /// Returns an identical `Layout` but with the input default tab stop `width`.
+    ///
+    /// Vertical layouts are unaffected.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// let layout = Layout::default().tab_width(TabWidth::Spaces(4));
+    /// ```
+    pub fn tab_width(self, width: TabWidth) -> Self {
+        self.update_options(|o| o.tab_stops.width = width)
+    }
+
+    /// Returns an identical `Layout` but with the input paragraph `indents`.
+    ///
+    /// Single line & vertical layouts are unaffected.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// // list item with the bullet hanging in the left margin
+    /// let layout = Layout::default().indents(Indents {
+    ///     left: 24.0,
+    ///     first_line: -16.0,
+    ///     ..<_>::default()
+    /// });
+    /// ```
+    pub fn indents(mut self, indents: Indents) -> Self {
+        if let Layout::Wrap { options, .. } = &mut self {
+            options.indents = indents;
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `exclusions`, see
+    /// [`LayoutOptions::exclusions`](struct.LayoutOptions.html#structfield.exclusions).
+    ///
+    /// Single line & vertical layouts are unaffected.
+    pub fn exclusions(mut self, exclusions: impl Into<Arc<[Exclusion]>>) -> Self {
+        if let Layout::Wrap { options, .. } = &mut self {
+            options.exclusions = exclusions.into();
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `break_strategy`.
+    ///
+    /// Single line & vertical layouts are unaffected.
+    pub fn break_strategy(mut self, break_strategy: BreakStrategy) -> Self {
+        if let Layout::Wrap { options, .. } = &mut self {
+            options.break_strategy = break_strategy;
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `word_break`.
+    ///
+    /// Single line & vertical layouts are unaffected.
+    pub fn word_break(mut self, word_break: WordBreak) -> Self {
+        if let Layout::Wrap { options, .. } = &mut self {
+            options.word_break = word_break;
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `overflow_wrap`.
+    ///
+    /// Single line & vertical layouts are unaffected.
+    pub fn overflow_wrap(mut self, overflow_wrap: OverflowWrap) -> Self {
+        if let Layout::Wrap { options, .. } = &mut self {
+            options.overflow_wrap = overflow_wrap;
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `white_space`.
+    ///
+    /// Vertical layouts are unaffected.
+    pub fn white_space(self, white_space: WhiteSpace) -> Self {
+        self.update_options(|o| o.white_space = white_space)
+    }
+
+    /// Returns an identical `Layout` but with the input `line_breaker`
+    pub fn line_breaker<L2: LineBreaker>(self, line_breaker: L2) -> Layout<L2> {
         use crate::Layout::*;
         match self {
             SingleLine {
-                line_breaker,
                 h_align,
+                v_align,
+                options,
                 ..
             } => SingleLine {
                 line_breaker,
                 v_align,
                 h_align,
+                options,
             },
             Wrap {
-                line_breaker,
                 h_align,
+                v_align,
+                options,
                 ..
             } => Wrap {
                 line_breaker,
                 v_align,
                 h_align,
+                options,
             },
-        }
-    }
-
-    /// Returns an identical `Layout` but with the input `line_breaker`
-    pub fn line_breaker<L2: LineBreaker>(self, line_breaker: L2) -> Layout<L2> {
-        use crate::Layout::*;
-        match self {
-            SingleLine {
-                h_align, v_align, ..
-            } => SingleLine {
-                line_breaker,
-                v_align,
-                h_align,
-            },
-            Wrap {
+            Vertical {
                 h_align, v_align, ..
-            } => Wrap {
+            } => Vertical {
                 line_breaker,
                 v_align,
                 h_align,
@@ -131,18 +318,146 @@ impl<L: LineBreaker> Layout<L> {
     }
 }
 
-impl<L: LineBreaker> GlyphPositioner for Layout<L> {
-    fn calculate_glyphs<F, S>(
+impl<L: LineBreaker> Layout<L> {
+    /// Returns the inner `LineBreaker`.
+    #[inline]
+    pub(crate) fn get_line_breaker(&self) -> L {
+        match *self {
+            Layout::SingleLine {
+                ref line_breaker, ..
+            }
+            | Layout::Wrap {
+                ref line_breaker, ..
+            }
+            | Layout::Vertical {
+                ref line_breaker, ..
+            } => line_breaker.clone(),
+        }
+    }
+
+    /// Returns the inner `LineBreaker` applying the layout's `WordBreak`, only wrapping
+    /// layouts have one.
+    #[inline]
+    pub(crate) fn get_word_breaker(&self) -> WordBreaker<L> {
+        let word_break = match self {
+            Layout::Wrap { options, .. } => options.word_break,
+            Layout::SingleLine { .. } | Layout::Vertical { .. } => WordBreak::Normal,
+        };
+        WordBreaker {
+            line_breaker: self.get_line_breaker(),
+            word_break,
+        }
+    }
+
+    /// Returns the inner `LayoutOptions`, vertical layouts have none.
+    #[inline]
+    pub(crate) fn get_options(&self) -> Option<&LayoutOptions> {
+        match self {
+            Layout::SingleLine { options, .. } | Layout::Wrap { options, .. } => Some(options),
+            Layout::Vertical { .. } => None,
+        }
+    }
+
+    /// Returns the inner `Overflow`, vertical layouts have the default.
+    #[inline]
+    pub(crate) fn get_overflow(&self) -> Overflow {
+        self.get_options().map(|o| o.overflow).unwrap_or_default()
+    }
+
+    /// Returns the inner `TabStops`, vertical layouts have the default.
+    #[inline]
+    pub(crate) fn get_tab_stops(&self) -> TabStops {
+        self.get_options()
+            .map(|o| o.tab_stops.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the inner `WhiteSpace`, vertical layouts have the default.
+    #[inline]
+    pub(crate) fn get_white_space(&self) -> WhiteSpace {
+        self.get_options()
+            .map(|o| o.white_space)
+            .unwrap_or_default()
+    }
+
+    /// Returns the lines `words` fill within the `bound_w`, before any overflow is applied.
+    /// Single line layouts lay out only the first. `WhiteSpace::Pre` lines don't wrap.
+    ///
+    /// Vertical layouts don't use horizontal `Word`s & fill lines as single line layouts.
+    pub(crate) fn fill_lines<'a, W>(
+        &self,
+        words: W,
+        bound_w: f32,
+        texts: &'a [&'a str],
+    ) -> Lines<'a, OptimalBreaks<W>>
+    where
+        W: Iterator<Item = Word>,
+    {
+        let bound_w = match self.get_white_space() {
+            WhiteSpace::Pre => f32::INFINITY,
+            _ => bound_w,
+        };
+        match self {
+            Layout::Wrap { options, .. } => {
+                let LayoutOptions {
+                    ref tab_stops,
+                    indents,
+                    ref exclusions,
+                    break_strategy,
+                    overflow_wrap,
+                    ..
+                } = *options;
+                // lines beside exclusions are filled greedily
+                let break_strategy = match exclusions.is_empty() {
+                    true => break_strategy,
+                    false => BreakStrategy::Greedy,
+                };
+                let words = OptimalBreaks::new(words, break_strategy, bound_w, indents);
+                let mut lines = Lines::new(
+                    words,
+                    bound_w,
+                    tab_stops.clone(),
+                    indents,
+                    exclusions.clone(),
+                    texts,
+                );
+                lines.set_overflow_wrap(overflow_wrap);
+                lines
+            }
+            _ => {
+                let words =
+                    OptimalBreaks::new(words, BreakStrategy::Greedy, bound_w, Indents::default());
+                let tab_stops = self.get_tab_stops();
+                Lines::new(
+                    words,
+                    bound_w,
+                    tab_stops,
+                    Indents::default(),
+                    Arc::default(),
+                    texts,
+                )
+            }
+        }
+    }
+
+    /// Positions the glyphs of `words` wrapping & aligning according to the layout.
+    ///
+    /// Vertical layouts don't use horizontal `Word`s & are laid out separately.
+    ///
+    /// `texts` are the section texts, used to find decimal tab stop separators.
+    pub(crate) fn layout_words<F, W>(
         &self,
         fonts: &[F],
         geometry: &SectionGeometry,
-        sections: &[S],
-    ) -> Vec<SectionGlyph>
+        words: W,
+        texts: &[&str],
+        bidi: Option<&Bidi>,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
     where
         F: Font,
-        S: ToSectionText,
+        W: Iterator<Item = Word>,
     {
-        use crate::Layout::{SingleLine, Wrap};
+        use crate::Layout::{SingleLine, Vertical, Wrap};
 
         let SectionGeometry {
             screen_position,
@@ -154,92 +469,257 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
             SingleLine {
                 h_align,
                 v_align,
-                line_breaker,
-            } => Characters::new(
-                fonts,
-                sections.iter().map(|s| s.to_section_text()),
-                line_breaker,
-            )
-            .words()
-            .lines(bound_w)
-            .next()
-            .map(|line| line.aligned_on_screen(screen_position, h_align, v_align))
-            .unwrap_or_default(),
+                ref options,
+                ..
+            } => {
+                let overflow = options.overflow;
+                // ellipsis truncation fits characters, rather than words, to the width bound
+                let lines_w = match overflow.ellipsis {
+                    Some(_) => f32::INFINITY,
+                    None => bound_w,
+                };
+                let mut lines = self.fill_lines(words, lines_w, texts);
+                lines
+                    .next()
+                    .filter(|_| overflow.max_lines != Some(0))
+                    .map(|mut line| {
+                        if let Some(ellipsis) = overflow.ellipsis {
+                            let in_bounds = line.rightmost < bound_w
+                                || approx::relative_eq!(line.rightmost, bound_w);
+                            if !in_bounds || lines.next().is_some() {
+                                line.truncate(fonts, ellipsis, bound_w);
+                            }
+                        }
+                        // a single line is never stretched, as if it were the last line of a paragraph
+                        let h_align = match h_align {
+                            HorizontalAlign::Justify => HorizontalAlign::Start,
+                            h_align => h_align,
+                        };
+                        let box_w = match bound_w.is_finite() {
+                            true => bound_w,
+                            false => line.rightmost,
+                        };
+                        let (glyphs, section_line) = line.aligned_on_screen(
+                            fonts,
+                            screen_position,
+                            h_align,
+                            v_align,
+                            box_w,
+                            bidi,
+                        );
+                        (glyphs, vec![section_line])
+                    })
+                    .unwrap_or_default()
+            }
 
             Wrap {
                 h_align,
                 v_align,
-                line_breaker,
+                ref options,
+                ..
             } => {
+                let overflow = options.overflow;
                 let mut out = vec![];
-                let mut caret = screen_position;
                 let v_align_top = v_align == VerticalAlign::Top;
 
-                let lines = Characters::new(
-                    fonts,
-                    sections.iter().map(|s| s.to_section_text()),
-                    line_breaker,
-                )
-                .words()
-                .lines(bound_w);
+                let lines = self.fill_lines(words, bound_w, texts);
+                let max_lines = overflow.max_lines.unwrap_or(usize::MAX);
+                let lines: Box<dyn Iterator<Item = Line>> = match overflow.ellipsis {
+                    None => Box::new(lines.take(max_lines)),
+                    Some(ellipsis) => Box::new(
+                        lines
+                            .truncated(fonts, ellipsis, max_lines, bound_w, bound_h)
+                            .into_iter(),
+                    ),
+                };
+
+                // unbounded direction dependent alignment happens within the widest line
+                let (lines, box_w): (Box<dyn Iterator<Item = Line>>, _) =
+                    if bound_w.is_finite() || !h_align.is_direction_dependent(bidi.is_some()) {
+                        (Box::new(lines), bound_w)
+                    } else {
+                        let lines: Vec<_> = lines.collect();
+                        let box_w = lines
+                            .iter()
+                            .map(|l| l.rightmost + l.inset_width())
+                            .fold(0.0, f32::max);
+                        (Box::new(lines.into_iter()), box_w)
+                    };
+
+                let mut section_lines: Vec<SectionLine> = vec![];
+                // first & last line baselines & the first line's optical top, from the top
+                let mut first_baseline = 0.0;
+                let mut optical_top = 0.0;
+                let mut last_baseline = 0.0;
+                let mut height: f32 = 0.0;
 
                 for line in lines {
                     // top align can bound check & exit early
-                    if v_align_top && caret.1 >= screen_position.1 + bound_h {
+                    if v_align_top && line.top >= bound_h {
                         break;
                     }
 
-                    let line_height = line.line_height();
-                    out.extend(line.aligned_on_screen(caret, h_align, VerticalAlign::Top));
-                    caret.1 += line_height;
+                    let caret = (screen_position.0, screen_position.1 + line.top);
+                    let baseline = line.top + line.max_v_metrics.ascent;
+                    if section_lines.is_empty() {
+                        first_baseline = baseline;
+                        optical_top = baseline - line.optical_height(fonts, v_align);
+                    }
+                    last_baseline = baseline;
+
+                    height = height.max(line.top + line.line_height());
+                    let (glyphs, mut section_line) = line.aligned_on_screen(
+                        fonts,
+                        caret,
+                        h_align,
+                        VerticalAlign::Top,
+                        box_w,
+                        bidi,
+                    );
+                    section_line.index = section_lines.len();
+                    section_line.glyphs = out.len()..out.len() + glyphs.len();
+                    out.extend(glyphs);
+                    section_lines.push(section_line);
                 }
 
-                if !out.is_empty() {
+                if !section_lines.is_empty() {
                     match v_align {
                         // already aligned
                         VerticalAlign::Top => {}
                         // convert from top
-                        VerticalAlign::Center | VerticalAlign::Bottom => {
-                            let shift_up = if v_align == VerticalAlign::Center {
-                                (caret.1 - screen_position.1) / 2.0
-                            } else {
-                                caret.1 - screen_position.1
+                        _ => {
+                            let shift_up = match v_align {
+                                VerticalAlign::Center => height / 2.0,
+                                VerticalAlign::Bottom => height,
+                                VerticalAlign::Baseline => first_baseline,
+                                VerticalAlign::LastBaseline => last_baseline,
+                                _ => (optical_top + last_baseline) / 2.0,
                             };
 
                             let (min_x, max_x) = h_align.x_bounds(screen_position.0, bound_w);
                             let (min_y, max_y) = v_align.y_bounds(screen_position.1, bound_h);
 
-                            out = out
-                                .drain(..)
-                                .filter_map(|mut sg| {
-                                    // shift into position
-                                    sg.glyph.position.y -= shift_up;
-
-                                    // filter away out-of-bounds glyphs
-                                    let sfont = fonts[sg.font_id].as_scaled(sg.glyph.scale);
-                                    let h_advance = sfont.h_advance(sg.glyph.id);
-                                    let h_side_bearing = sfont.h_side_bearing(sg.glyph.id);
-                                    let height = sfont.height();
-
-                                    Some(sg).filter(|sg| {
-                                        sg.glyph.position.x - h_side_bearing <= max_x
-                                            && sg.glyph.position.x + h_advance >= min_x
-                                            && sg.glyph.position.y - height <= max_y
-                                            && sg.glyph.position.y + height >= min_y
-                                    })
-                                })
-                                .collect();
+                            let in_bounds = |sg: &SectionGlyph| {
+                                let sfont = fonts[sg.font_id].as_scaled(sg.glyph.scale);
+                                let h_advance = sfont.h_advance(sg.glyph.id);
+                                let h_side_bearing = sfont.h_side_bearing(sg.glyph.id);
+                                let height = sfont.height();
+
+                                sg.glyph.position.x - h_side_bearing <= max_x
+                                    && sg.glyph.position.x + h_advance >= min_x
+                                    && sg.glyph.position.y - height <= max_y
+                                    && sg.glyph.position.y + height >= min_y
+                            };
+
+                            let mut glyphs = out.into_iter();
+                            out = Vec::with_capacity(glyphs.len());
+                            for section_line in &mut section_lines {
+                                section_line.baseline -= shift_up;
+                                let start = out.len();
+                                out.extend(
+                                    glyphs
+                                        .by_ref()
+                                        .take(section_line.glyphs.len())
+                                        .map(|mut sg| {
+                                            // shift into position
+                                            sg.glyph.position.y -= shift_up;
+                                            sg
+                                        })
+                                        // filter away out-of-bounds glyphs
+                                        .filter(in_bounds),
+                                );
+                                section_line.glyphs = start..out.len();
+                            }
                         }
                     }
                 }
 
-                out
+                (out, section_lines)
             }
+
+            Vertical { .. } => unreachable!("vertical layouts don't lay out words"),
+        }
+    }
+}
+
+impl<L: LineBreaker> GlyphPositioner for Layout<L> {
+    #[inline]
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_glyphs_with_fallbacks(fonts, &FontFallbacks::default(), geometry, sections)
+    }
+
+    #[inline]
+    fn calculate_glyphs_with_fallbacks<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections)
+            .0
+    }
+
+    /// Vertical layouts have no lines.
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        if let Layout::Vertical {
+            ref line_breaker,
+            h_align,
+            v_align,
+        } = *self
+        {
+            let glyphs = vertical::calculate_glyphs(
+                fonts,
+                fallbacks,
+                geometry,
+                sections,
+                line_breaker.clone(),
+                h_align,
+                v_align,
+            );
+            return (glyphs, Vec::new());
         }
+
+        let bidi = Bidi::new(sections.iter().map(|s| s.to_section_text()));
+
+        let words = Characters::new(
+            fonts,
+            sections.iter().map(|s| s.to_section_text()),
+            self.get_word_breaker(),
+            bidi.as_ref(),
+            fallbacks,
+        )
+        .words(self.get_white_space());
+        let texts: Vec<_> = sections.iter().map(|s| s.to_section_text().text).collect();
+
+        self.layout_words(fonts, geometry, words, &texts, bidi.as_ref())
     }
 
     fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
-        use crate::Layout::{SingleLine, Wrap};
+        use crate::Layout::{SingleLine, Vertical, Wrap};
 
         let SectionGeometry {
             screen_position: (screen_x, screen_y),
@@ -253,6 +733,9 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
             | SingleLine {
                 h_align, v_align, ..
             } => (h_align, v_align),
+            Vertical {
+                h_align, v_align, ..
+            } => (h_align.vertical(), v_align),
         };
 
         let (x_min, x_max) = h_align.x_bounds(screen_x, bound_w);
@@ -264,7 +747,7 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
         }
     }
 
-    #[allow(clippy::float_cmp)]
+    #[inline]
     fn recalculate_glyphs<F, S, P>(
         &self,
         previous: P,
@@ -273,6 +756,31 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
         geometry: &SectionGeometry,
         sections: &[S],
     ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        self.recalculate_glyphs_with_fallbacks(
+            previous,
+            change,
+            fonts,
+            &FontFallbacks::default(),
+            geometry,
+            sections,
+        )
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn recalculate_glyphs_with_fallbacks<F, S, P>(
+        &self,
+        previous: P,
+        change: GlyphChange,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
     where
         F: Font,
         S: ToSectionText,
@@ -280,60 +788,814 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
     {
         match change {
             GlyphChange::Geometry(old) if old.bounds == geometry.bounds => {
-                // position change
-                let adjustment = point(
-                    geometry.screen_position.0 - old.screen_position.0,
-                    geometry.screen_position.1 - old.screen_position.1,
-                );
-
-                let mut glyphs: Vec<_> = previous.into_iter().collect();
-                glyphs
-                    .iter_mut()
-                    .for_each(|sg| sg.glyph.position += adjustment);
-                glyphs
+                reposition(previous, &old, geometry)
             }
-            _ => self.calculate_glyphs(fonts, geometry, sections),
+            GlyphChange::Scale(factor) => self
+                .rescale(previous, None, factor, geometry, sections)
+                .map(|(glyphs, _)| glyphs)
+                .unwrap_or_else(|| {
+                    self.calculate_glyphs_with_fallbacks(fonts, fallbacks, geometry, sections)
+                }),
+            _ => self.calculate_glyphs_with_fallbacks(fonts, fallbacks, geometry, sections),
         }
     }
-}
-
-/// Describes horizontal alignment preference for positioning & bounds.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum HorizontalAlign {
-    /// Leftmost character is immediately to the right of the render position.<br/>
-    /// Bounds start from the render position and advance rightwards.
-    Left,
-    /// Leftmost & rightmost characters are equidistant to the render position.<br/>
-    /// Bounds start from the render position and advance equally left & right.
-    Center,
-    /// Rightmost character is immetiately to the left of the render position.<br/>
-    /// Bounds start from the render position and advance leftwards.
-    Right,
-}
 
-impl HorizontalAlign {
-    #[inline]
-    pub(crate) fn x_bounds(self, screen_x: f32, bound_w: f32) -> (f32, f32) {
-        let (min, max) = match self {
-            HorizontalAlign::Left => (screen_x, screen_x + bound_w),
-            HorizontalAlign::Center => (screen_x - bound_w / 2.0, screen_x + bound_w / 2.0),
-            HorizontalAlign::Right => (screen_x - bound_w, screen_x),
-        };
+    /// Vertical layouts don't measure words.
+    fn measure_words<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        sections: &[S],
+    ) -> Option<MeasuredWords>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        if let Layout::Vertical { .. } = self {
+            return None;
+        }
 
-        (min.floor(), max.ceil())
+        let bidi = Bidi::new(sections.iter().map(|s| s.to_section_text()));
+        let words = Characters::new(
+            fonts,
+            sections.iter().map(|s| s.to_section_text()),
+            self.get_word_breaker(),
+            bidi.as_ref(),
+            fallbacks,
+        )
+        .words(self.get_white_space())
+        .collect();
+
+        Some(MeasuredWords { words, bidi })
     }
-}
 
-/// Describes vertical alignment preference for positioning & bounds. Currently a placeholder
-/// for future functionality.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum VerticalAlign {
-    /// Characters/bounds start underneath the render position and progress downwards.
-    Top,
-    /// Characters/bounds center at the render position and progress outward equally.
-    Center,
-    /// Characters/bounds start above the render position and progress upward.
+    /// Vertical layouts have no intrinsic widths. Single lines & `WhiteSpace::Pre` lines
+    /// don't wrap, so have the max-content width of their widest line as min-content.
+    fn intrinsic_widths<S>(&self, words: &MeasuredWords, sections: &[S]) -> Option<IntrinsicWidths>
+    where
+        S: ToSectionText,
+    {
+        let (indents, overflow_wrap, max_paragraphs) = match self {
+            Layout::SingleLine { .. } => (Indents::default(), None, 1),
+            Layout::Wrap { options, .. } => {
+                let overflow_wrap =
+                    Some(options.overflow_wrap).filter(|_| options.white_space != WhiteSpace::Pre);
+                (options.indents, overflow_wrap, usize::MAX)
+            }
+            Layout::Vertical { .. } => return None,
+        };
+        let Indents {
+            left,
+            right,
+            first_line,
+            hanging,
+        } = indents;
+
+        let max_content = match words.words.iter().any(|w| w.tab.is_some()) {
+            // tab stops depend on the position of the tab in the line
+            true => {
+                let texts: Vec<_> = sections.iter().map(|s| s.to_section_text().text).collect();
+                self.fill_lines(words.words.iter().cloned(), f32::INFINITY, &texts)
+                    .take(max_paragraphs)
+                    .map(|line| line.rightmost + line.inset_width())
+                    .fold(0.0, f32::max)
+            }
+            // paragraphs fill a line each, ending in a hard break that keeps trailing spaces
+            false => words
+                .words
+                .split_inclusive(|w| w.hard_break)
+                .take(max_paragraphs)
+                .map(|paragraph| {
+                    let width: f32 = paragraph.iter().map(|w| w.layout_width).sum();
+                    left + right + first_line + width
+                })
+                .fold(0.0, f32::max),
+        };
+
+        let min_content = match overflow_wrap {
+            None => max_content,
+            Some(overflow_wrap) => {
+                let mut min_content: f32 = 0.0;
+                let mut paragraph_start = true;
+                for word in &words.words {
+                    let indent = match paragraph_start {
+                        true => first_line,
+                        false => hanging,
+                    };
+                    let width = match overflow_wrap {
+                        OverflowWrap::Normal => word.line_end_width(),
+                        OverflowWrap::Anywhere => word.widest_cluster(),
+                    };
+                    min_content = min_content.max(left + right + indent + width);
+                    paragraph_start = word.hard_break;
+                }
+                min_content
+            }
+        };
+
+        Some(IntrinsicWidths {
+            min_content,
+            max_content: max_content.max(min_content),
+        })
+    }
+
+    /// Vertical layouts have no height for a width.
+    fn height_for_width<S>(&self, words: &MeasuredWords, width: f32, sections: &[S]) -> Option<f32>
+    where
+        S: ToSectionText,
+    {
+        let (single_line, lines_w) = match self {
+            // ellipsis truncation fits characters, rather than words, to the width bound
+            Layout::SingleLine { options, .. } if options.overflow.ellipsis.is_some() => {
+                (true, f32::INFINITY)
+            }
+            Layout::SingleLine { .. } => (true, width),
+            Layout::Wrap { .. } => (false, width),
+            Layout::Vertical { .. } => return None,
+        };
+        let max_lines = match single_line {
+            true => 1,
+            false => usize::MAX,
+        };
+        let max_lines = max_lines.min(self.get_overflow().max_lines.unwrap_or(usize::MAX));
+
+        let texts: Vec<_> = sections.iter().map(|s| s.to_section_text().text).collect();
+        let height = self
+            .fill_lines(words.words.iter().cloned(), lines_w, &texts)
+            .take(max_lines)
+            .map(|line| line.top + line.line_height())
+            .fold(0.0, f32::max);
+        Some(height)
+    }
+
+    /// Bounds changes re-use any previous words only re-filling & aligning lines.
+    ///
+    /// Text changes to top aligned wrapping layouts, without ellipsis truncation or
+    /// exclusions, reuse the lines before the line preceding the change. Lines are reused
+    /// up to the paragraph containing the change instead when its line breaks may depend
+    /// on later text, i.e. with optimal breaks, first line indents, right-to-left text or
+    /// lines wrapped within words.
+    ///
+    /// Uniform scale changes to layouts without a height bound, that don't wrap at the
+    /// width bound, scale the previous glyphs & lines about the screen position.
+    #[allow(clippy::float_cmp)]
+    fn recalculate_glyphs_and_lines<F, S, P>(
+        &self,
+        previous: PreviousLayout<'_, P>,
+        change: GlyphChange,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        match change {
+            GlyphChange::Geometry(old) if old.bounds == geometry.bounds => (
+                reposition(previous.glyphs, &old, geometry),
+                reposition_lines(previous.lines, &old, geometry),
+            ),
+            GlyphChange::Geometry(_) if previous.words.is_some() => {
+                let measured = previous.words.unwrap();
+                let texts: Vec<_> = sections.iter().map(|s| s.to_section_text().text).collect();
+                self.layout_words(
+                    fonts,
+                    geometry,
+                    measured.words.iter().cloned(),
+                    &texts,
+                    measured.bidi.as_ref(),
+                )
+            }
+            GlyphChange::TextAppended(changed) | GlyphChange::TextEdited(changed) => self
+                .relayout(
+                    previous.glyphs,
+                    previous.lines,
+                    changed,
+                    geometry,
+                    sections,
+                    |layout, geometry, texts| {
+                        layout.calculate_glyphs_and_lines(fonts, fallbacks, geometry, texts)
+                    },
+                )
+                .unwrap_or_else(|| {
+                    self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections)
+                }),
+            GlyphChange::Scale(factor) => self
+                .rescale(
+                    previous.glyphs,
+                    Some(previous.lines),
+                    factor,
+                    geometry,
+                    sections,
+                )
+                .unwrap_or_else(|| {
+                    self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections)
+                }),
+            _ => self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections),
+        }
+    }
+}
+
+impl<L: LineBreaker> Layout<L> {
+    /// Scales the `previous` glyphs & lines about the screen position after every section
+    /// text scale has been multiplied by `factor`.
+    ///
+    /// Without `previous_lines` only layouts with an unbounded width are scaled, otherwise
+    /// also those without soft wraps that still fit the width after scaling.
+    ///
+    /// Returns `None` if the layout may not scale uniformly with the text, e.g. with a
+    /// height bound or pixel sized spacing.
+    pub(crate) fn rescale<S, P>(
+        &self,
+        previous: P,
+        previous_lines: Option<&[SectionLine]>,
+        factor: f32,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Option<(Vec<SectionGlyph>, Vec<SectionLine>)>
+    where
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        let (h_align, overflow, tab_stops, indents) = match *self {
+            Layout::SingleLine {
+                h_align,
+                ref options,
+                ..
+            } => (
+                h_align,
+                options.overflow,
+                options.tab_stops.clone(),
+                Indents::default(),
+            ),
+            Layout::Wrap { ref options, .. } if !options.exclusions.is_empty() => return None,
+            Layout::Wrap {
+                h_align,
+                ref options,
+                ..
+            } => (
+                h_align,
+                options.overflow,
+                options.tab_stops.clone(),
+                options.indents,
+            ),
+            Layout::Vertical { .. } => return None,
+        };
+        let (bound_w, bound_h) = geometry.bounds;
+        if !factor.is_finite()
+            || factor <= 0.0
+            || bound_h.is_finite()
+            || indents != Indents::default()
+        {
+            return None;
+        }
+
+        let scaling_tabs =
+            tab_stops.stops.is_empty() && matches!(tab_stops.width, TabWidth::Spaces(_));
+        let sections: Vec<_> = sections.iter().map(|s| s.to_section_text()).collect();
+        if sections.iter().any(|s| {
+            s.letter_spacing != 0.0
+                || s.word_spacing != 0.0
+                || s.placeholder.is_some()
+                || matches!(s.line_height, LineHeight::Px(_))
+                || matches!(s.baseline_shift, BaselineShift::Px(_))
+                || (!scaling_tabs && s.text.contains('\t'))
+        }) {
+            return None;
+        }
+
+        if bound_w.is_finite() {
+            // wrapping & truncation depend on the width
+            let lines = previous_lines?;
+            if overflow.ellipsis.is_some()
+                || h_align.is_direction_dependent(true)
+                || lines
+                    .iter()
+                    .any(|line| !line.hard_break || line.advance_width * factor > bound_w)
+            {
+                return None;
+            }
+        }
+
+        let (screen_x, screen_y) = geometry.screen_position;
+        let scale_x = |x: f32| screen_x + (x - screen_x) * factor;
+        let scale_y = |y: f32| screen_y + (y - screen_y) * factor;
+
+        let glyphs = previous
+            .into_iter()
+            .map(|mut sg| {
+                let position = sg.glyph.position;
+                sg.glyph.position = point(scale_x(position.x), scale_y(position.y));
+                sg.glyph.scale = sections[sg.section_index].scale;
+                sg
+            })
+            .collect();
+        let lines = previous_lines
+            .unwrap_or_default()
+            .iter()
+            .map(|line| SectionLine {
+                left: scale_x(line.left),
+                baseline: scale_y(line.baseline),
+                ascent: line.ascent * factor,
+                descent: line.descent * factor,
+                advance_width: line.advance_width * factor,
+                ..line.clone()
+            })
+            .collect();
+        Some((glyphs, lines))
+    }
+
+    /// Lays out the text from the line preceding the line containing the `changed`
+    /// `(section_index, byte_index)`, reusing the previous lines before it. Lays out from
+    /// the first line of the paragraph containing the change instead if the preceding
+    /// line's breaks may depend on later text.
+    ///
+    /// The remaining text is laid out by `calculate`, with an equivalent layout, geometry
+    /// & sections starting from the first relaid line.
+    ///
+    /// Returns `None` if no lines can be reused, or the layout's line positions depend
+    /// on later lines.
+    pub(crate) fn relayout<S, P, C>(
+        &self,
+        previous: P,
+        previous_lines: &[SectionLine],
+        changed: (usize, usize),
+        geometry: &SectionGeometry,
+        sections: &[S],
+        calculate: C,
+    ) -> Option<(Vec<SectionGlyph>, Vec<SectionLine>)>
+    where
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+        C: FnOnce(
+            &Self,
+            &SectionGeometry,
+            &[SectionText<'_>],
+        ) -> (Vec<SectionGlyph>, Vec<SectionLine>),
+    {
+        let Layout::Wrap {
+            ref line_breaker,
+            h_align,
+            v_align: VerticalAlign::Top,
+            ref options,
+        } = *self
+        else {
+            return None;
+        };
+        // exclusions are positioned from the top of all the text
+        if options.overflow.ellipsis.is_some() || !options.exclusions.is_empty() {
+            return None;
+        }
+        let (bound_w, bound_h) = geometry.bounds;
+        if !bound_w.is_finite() && h_align.is_direction_dependent(true) {
+            return None;
+        }
+
+        let last = previous_lines.last()?;
+        // previous layout ended at the bounds or max lines, so will this one
+        if last.hard_break && last.end < changed {
+            return Some((previous.into_iter().collect(), previous_lines.to_vec()));
+        }
+
+        let sections: Vec<_> = sections.iter().map(|s| s.to_section_text()).collect();
+        // the line containing the change, or appended to
+        let changed_line = previous_lines
+            .iter()
+            .position(|line| line.end > changed)
+            .unwrap_or(previous_lines.len() - 1);
+        // a hard break before the change will remain, so will all the lines up to it
+        let paragraph_start = previous_lines[..changed_line]
+            .iter()
+            .rposition(|line| line.hard_break)
+            .map_or(0, |idx| idx + 1);
+        // greedily filled lines before the line preceding the change remain, unless that
+        // line may be the end of a word continuing from, or broken over, earlier lines
+        let lines_independent = options.break_strategy == BreakStrategy::Greedy
+            && options.indents.first_line == options.indents.hanging
+            && Bidi::new(sections.iter().copied()).is_none();
+        let reuse = match changed_line.checked_sub(1) {
+            Some(preceding)
+                if lines_independent
+                    && char_before(&sections, previous_lines[preceding].end)
+                        .is_some_and(char::is_whitespace) =>
+            {
+                preceding.max(paragraph_start)
+            }
+            _ => paragraph_start,
+        };
+        if reuse == 0 {
+            return None;
+        }
+
+        let glyph_end = previous_lines[reuse - 1].glyphs.end;
+        let mut glyphs: Vec<_> = previous.into_iter().take(glyph_end).collect();
+        let mut lines = previous_lines[..reuse].to_vec();
+        let next_line = &previous_lines[reuse];
+
+        let (section_index, byte_index) = next_line.start;
+        let mut texts = Vec::with_capacity(sections.len().saturating_sub(section_index));
+        for (idx, mut section) in sections.iter().copied().enumerate().skip(section_index) {
+            if idx == section_index {
+                section.text = section.text.get(byte_index..)?;
+            }
+            texts.push(section);
+        }
+
+        let top = next_line.baseline - next_line.ascent;
+        let geometry = SectionGeometry {
+            screen_position: (geometry.screen_position.0, top),
+            bounds: (bound_w, bound_h - (top - geometry.screen_position.1)),
+        };
+        let layout = Layout::Wrap {
+            line_breaker: line_breaker.clone(),
+            h_align,
+            v_align: VerticalAlign::Top,
+            options: LayoutOptions {
+                overflow: Overflow {
+                    max_lines: options
+                        .overflow
+                        .max_lines
+                        .map(|max| max.saturating_sub(reuse)),
+                    ellipsis: None,
+                },
+                ..options.clone()
+            },
+        };
+        let (new_glyphs, new_lines) = calculate(&layout, &geometry, &texts);
+
+        // offset text positions from the start of the relaid text
+        let offset = |(idx, byte): (usize, usize)| match idx {
+            0 => (section_index, byte_index + byte),
+            idx => (section_index + idx, byte),
+        };
+        glyphs.extend(new_glyphs.into_iter().map(|mut sg| {
+            if !sg.is_synthetic() {
+                (sg.section_index, sg.byte_index) = offset((sg.section_index, sg.byte_index));
+            } else {
+                sg.section_index += section_index;
+            }
+            sg
+        }));
+        lines.extend(new_lines.into_iter().map(|mut line| {
+            line.index += reuse;
+            line.glyphs = line.glyphs.start + glyph_end..line.glyphs.end + glyph_end;
+            line.start = offset(line.start);
+            line.end = offset(line.end);
+            line
+        }));
+        Some((glyphs, lines))
+    }
+}
+
+/// Returns the character before the `(section_index, byte_index)` of the `sections` text.
+fn char_before(
+    sections: &[SectionText<'_>],
+    (section_index, byte_index): (usize, usize),
+) -> Option<char> {
+    let before = sections
+        .get(section_index)?
+        .text
+        .get(..byte_index)?
+        .chars()
+        .next_back();
+    before.or_else(|| {
+        sections[..section_index]
+            .iter()
+            .rev()
+            .find_map(|s| s.text.chars().next_back())
+    })
+}
+
+/// Moves `previous` glyphs after a screen position only change.
+pub(crate) fn reposition<P>(
+    previous: P,
+    old: &SectionGeometry,
+    geometry: &SectionGeometry,
+) -> Vec<SectionGlyph>
+where
+    P: IntoIterator<Item = SectionGlyph>,
+{
+    let adjustment = point(
+        geometry.screen_position.0 - old.screen_position.0,
+        geometry.screen_position.1 - old.screen_position.1,
+    );
+
+    let mut glyphs: Vec<_> = previous.into_iter().collect();
+    glyphs
+        .iter_mut()
+        .for_each(|sg| sg.glyph.position += adjustment);
+    glyphs
+}
+
+/// Moves `previous` lines after a screen position only change.
+pub(crate) fn reposition_lines(
+    previous: &[SectionLine],
+    old: &SectionGeometry,
+    geometry: &SectionGeometry,
+) -> Vec<SectionLine> {
+    let (dx, dy) = (
+        geometry.screen_position.0 - old.screen_position.0,
+        geometry.screen_position.1 - old.screen_position.1,
+    );
+
+    let mut lines = previous.to_vec();
+    lines.iter_mut().for_each(|line| {
+        line.left += dx;
+        line.baseline += dy;
+    });
+    lines
+}
+
+/// Options of [`Layout::SingleLine`](enum.Layout.html#variant.SingleLine) &
+/// [`Layout::Wrap`](enum.Layout.html#variant.Wrap) layouts. Single lines only use the
+/// `overflow`, `tab_stops` & `white_space` options.
+///
+/// Built from the default with the `with_*` methods, or set on a layout with the
+/// equivalent `Layout` methods, as new options may be added.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// let options = LayoutOptions::default()
+///     .with_overflow(Overflow {
+///         max_lines: Some(3),
+///         ellipsis: Some(Ellipsis::End("…")),
+///     })
+///     .with_white_space(WhiteSpace::Normal);
+/// let layout = Layout::default_wrap().options(options);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub struct LayoutOptions {
+    /// Line clamping & ellipsis truncation of overflowing text.
+    pub overflow: Overflow,
+    /// Stops tab characters advance to.
+    pub tab_stops: TabStops,
+    /// Paragraph margins & indents, wrapping layouts only.
+    pub indents: Indents,
+    /// Regions text flows around within a finite width bound, wrapping layouts only.
+    ///
+    /// Lines of paragraphs beside exclusions are filled greedily, whatever the
+    /// `break_strategy`. Exclusions don't scale with the text, so layouts with exclusions
+    /// fully recalculate [`GlyphChange::Scale`](enum.GlyphChange.html#variant.Scale)
+    /// changes.
+    pub exclusions: Arc<[Exclusion]>,
+    /// How the line breaks of each paragraph are chosen, wrapping layouts only.
+    pub break_strategy: BreakStrategy,
+    /// Where lines may break between letters, wrapping layouts only.
+    pub word_break: WordBreak,
+    /// Whether words too wide for a line of their own are broken, wrapping layouts only.
+    pub overflow_wrap: OverflowWrap,
+    /// How spaces & newlines are laid out.
+    pub white_space: WhiteSpace,
+}
+
+impl LayoutOptions {
+    /// Returns identical options but with the input `overflow`.
+    #[inline]
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Returns identical options but with the input `tab_stops`.
+    #[inline]
+    pub fn with_tab_stops(mut self, tab_stops: TabStops) -> Self {
+        self.tab_stops = tab_stops;
+        self
+    }
+
+    /// Returns identical options but with the input paragraph `indents`.
+    #[inline]
+    pub fn with_indents(mut self, indents: Indents) -> Self {
+        self.indents = indents;
+        self
+    }
+
+    /// Returns identical options but with the input `exclusions`.
+    #[inline]
+    pub fn with_exclusions(mut self, exclusions: impl Into<Arc<[Exclusion]>>) -> Self {
+        self.exclusions = exclusions.into();
+        self
+    }
+
+    /// Returns identical options but with the input `break_strategy`.
+    #[inline]
+    pub fn with_break_strategy(mut self, break_strategy: BreakStrategy) -> Self {
+        self.break_strategy = break_strategy;
+        self
+    }
+
+    /// Returns identical options but with the input `word_break`.
+    #[inline]
+    pub fn with_word_break(mut self, word_break: WordBreak) -> Self {
+        self.word_break = word_break;
+        self
+    }
+
+    /// Returns identical options but with the input `overflow_wrap`.
+    #[inline]
+    pub fn with_overflow_wrap(mut self, overflow_wrap: OverflowWrap) -> Self {
+        self.overflow_wrap = overflow_wrap;
+        self
+    }
+
+    /// Returns identical options but with the input `white_space`.
+    #[inline]
+    pub fn with_white_space(mut self, white_space: WhiteSpace) -> Self {
+        self.white_space = white_space;
+        self
+    }
+}
+
+/// Paragraph margins & indents of [`Layout::Wrap`](enum.Layout.html#variant.Wrap) lines.
+/// Paragraphs are separated by hard breaks.
+///
+/// Lines are inset from the width bound box, or render position when unbounded, on the
+/// left & right by the margins. The first & following lines of each paragraph are further
+/// inset on the paragraph's start side by the `first_line` or `hanging` indent.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // first line indented within 10px margins
+/// let layout = Layout::default().indents(Indents {
+///     left: 10.0,
+///     right: 10.0,
+///     first_line: 30.0,
+///     hanging: 0.0,
+/// });
+/// ```
+///
+/// Indents are compared & hashed by their bits, so `0.0` & `-0.0` differ.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Indents {
+    /// Left margin of all lines.
+    pub left: f32,
+    /// Right margin of all lines.
+    pub right: f32,
+    /// Start indent of the first line of each paragraph. Negative values outdent into
+    /// the margin.
+    pub first_line: f32,
+    /// Start indent of the lines following the first of each paragraph, e.g. for lists.
+    pub hanging: f32,
+}
+
+impl Indents {
+    #[inline]
+    fn to_bits(self) -> [u32; 4] {
+        [self.left, self.right, self.first_line, self.hanging].map(f32::to_bits)
+    }
+}
+
+impl PartialEq for Indents {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl Eq for Indents {}
+
+impl Hash for Indents {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bits().hash(state);
+    }
+}
+
+/// Describes how text overflowing the layout is handled.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// let layout = Layout::default_single_line().overflow(Overflow {
+///     ellipsis: Some(Ellipsis::Middle("...")),
+///     ..<_>::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct Overflow {
+    /// Max number of lines to render, further lines are dropped. Defaults to unlimited.
+    pub max_lines: Option<usize>,
+    /// Truncation marking overflowing text. Defaults to none.
+    pub ellipsis: Option<Ellipsis>,
+}
+
+/// Truncates an overflowing line, inserting the ellipsis text where characters are removed.
+///
+/// Ellipsis glyphs use the font & scale of the adjacent glyph & take its `section_index`.
+/// Their `byte_index` is [`SectionGlyph::SYNTHETIC_BYTE_INDEX`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Ellipsis {
+    /// Removes characters from the start of the line, e.g. `"…ng text"`.
+    Start(&'static str),
+    /// Removes characters from the middle of the line, keeping the start & end,
+    /// e.g. for file paths `"/home/…/file.txt"`.
+    Middle(&'static str),
+    /// Removes characters from the end of the line, e.g. `"Long te…"`.
+    End(&'static str),
+}
+
+impl Ellipsis {
+    #[inline]
+    pub(crate) fn text(self) -> &'static str {
+        match self {
+            Ellipsis::Start(text) | Ellipsis::Middle(text) | Ellipsis::End(text) => text,
+        }
+    }
+}
+
+/// Describes horizontal alignment preference for positioning & bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HorizontalAlign {
+    /// Leftmost character is immediately to the right of the render position.<br/>
+    /// Bounds start from the render position and advance rightwards.
+    Left,
+    /// Leftmost & rightmost characters are equidistant to the render position.<br/>
+    /// Bounds start from the render position and advance equally left & right.
+    Center,
+    /// Rightmost character is immetiately to the left of the render position.<br/>
+    /// Bounds start from the render position and advance leftwards.
+    Right,
+    /// Wrapped lines are stretched to fill the width bound by widening inter-word whitespace.
+    /// The last line of each paragraph, single-word lines & single-line layouts are aligned
+    /// as [`HorizontalAlign::Start`].<br/>
+    /// Bounds start from the render position and advance rightwards.
+    Justify,
+    /// Lines align to the start of their paragraph's direction, left for left-to-right
+    /// paragraphs and right for right-to-left paragraphs. Lines align within the width bound,
+    /// or the widest line if unbounded.<br/>
+    /// Bounds start from the render position and advance rightwards.
+    Start,
+    /// Lines align to the end of their paragraph's direction, right for left-to-right
+    /// paragraphs and left for right-to-left paragraphs. Lines align within the width bound,
+    /// or the widest line if unbounded.<br/>
+    /// Bounds start from the render position and advance rightwards.
+    End,
+}
+
+impl HorizontalAlign {
+    /// Returns `true` if alignment depends on the paragraph direction & so the width bound.
+    #[inline]
+    pub(crate) fn is_direction_dependent(self, has_rtl: bool) -> bool {
+        match self {
+            HorizontalAlign::Left | HorizontalAlign::Center | HorizontalAlign::Right => false,
+            HorizontalAlign::Start | HorizontalAlign::Justify => has_rtl,
+            HorizontalAlign::End => true,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn x_bounds(self, screen_x: f32, bound_w: f32) -> (f32, f32) {
+        let (min, max) = match self {
+            HorizontalAlign::Left
+            | HorizontalAlign::Justify
+            | HorizontalAlign::Start
+            | HorizontalAlign::End => (screen_x, screen_x + bound_w),
+            HorizontalAlign::Center => (screen_x - bound_w / 2.0, screen_x + bound_w / 2.0),
+            HorizontalAlign::Right => (screen_x - bound_w, screen_x),
+        };
+
+        (min.floor(), max.ceil())
+    }
+}
+
+/// Describes vertical alignment preference for positioning & bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VerticalAlign {
+    /// Characters/bounds start underneath the render position and progress downwards.
+    Top,
+    /// Characters/bounds center at the render position and progress outward equally.
+    Center,
+    /// Characters/bounds start above the render position and progress upward.
     Bottom,
+    /// The first line's baseline is at the render position, characters progress downwards.
+    /// Bounds extend the height bound both above & below the render position.
+    Baseline,
+    /// The last line's baseline is at the render position, characters progress upward.
+    /// Bounds extend the height bound both above & below the render position.
+    LastBaseline,
+    /// Characters center optically at the render position, between the top of the first
+    /// line's capital letters & the last line's baseline. So, for example, button labels
+    /// appear centered regardless of descenders.
+    /// Bounds extend the height bound both above & below the render position.
+    ///
+    /// The cap-height is read from each font's OS/2 table `sCapHeight`, otherwise measured
+    /// from the top of the font's `'H'` glyph outline, or is the ascent if unavailable.
+    CapHeightCenter,
+    /// Characters center optically at the render position, between the top of the first
+    /// line's lowercase letters & the last line's baseline.
+    /// Bounds extend the height bound both above & below the render position.
+    ///
+    /// The x-height is read from each font's OS/2 table `sxHeight`, otherwise measured from
+    /// the top of the font's `'x'` glyph outline, or is the ascent if unavailable.
+    XHeightCenter,
 }
 
 impl VerticalAlign {
@@ -343,6 +1605,11 @@ impl VerticalAlign {
             VerticalAlign::Top => (screen_y, screen_y + bound_h),
             VerticalAlign::Center => (screen_y - bound_h / 2.0, screen_y + bound_h / 2.0),
             VerticalAlign::Bottom => (screen_y - bound_h, screen_y),
+            // text position relative to the render position depends on font metrics
+            VerticalAlign::Baseline
+            | VerticalAlign::LastBaseline
+            | VerticalAlign::CapHeightCenter
+            | VerticalAlign::XHeightCenter => (screen_y - bound_h, screen_y + bound_h),
         };
 
         (min.floor(), max.ceil())
@@ -362,6 +1629,11 @@ mod bounds_test {
         assert_eq!(VerticalAlign::Top.y_bounds(0.0, inf()), (0.0, inf()));
         assert_eq!(VerticalAlign::Center.y_bounds(0.0, inf()), (-inf(), inf()));
         assert_eq!(VerticalAlign::Bottom.y_bounds(0.0, inf()), (-inf(), 0.0));
+        assert_eq!(
+            VerticalAlign::Baseline.y_bounds(0.0, inf()),
+            (-inf(), inf())
+        );
+        assert_eq!(VerticalAlign::Baseline.y_bounds(5.0, 10.0), (-5.0, 15.0));
     }
 
     #[test]
@@ -378,7 +1650,11 @@ mod bounds_test {
 #[cfg(test)]
 mod layout_test {
     use super::*;
-    use crate::{BuiltInLineBreaker::*, FontId, SectionText};
+    use crate::{
+        BuiltInLineBreaker::*, FontFallbacks, FontId, LineHeight, Placeholder, SectionText,
+        TabStop, VerticalOrientation,
+    };
+
     use approx::assert_relative_eq;
     use ordered_float::OrderedFloat;
     use std::{collections::*, f32, sync::LazyLock};
@@ -905,6 +2181,7 @@ mod layout_test {
                 text: "hello world",
                 scale: PxScale::from(20.0),
                 font_id: FontId(0),
+                ..<_>::default()
             }],
         );
 
@@ -937,22 +2214,412 @@ mod layout_test {
         );
     }
 
-    /// Chinese sentence squeezed into a vertical pipe meaning each character is on
-    /// a separate line.
     #[test]
-    fn wrap_word_chinese() {
-        let glyphs = Layout::default().calculate_glyphs(
-            &*FONT_MAP,
-            &SectionGeometry {
-                bounds: (25.0, f32::INFINITY),
-                ..<_>::default()
-            },
-            &[SectionText {
-                text: "提高代碼執行率",
-                scale: PxScale::from(20.0),
-                font_id: FontId(1),
-            }],
-        );
+    fn recalculate_text_changes() {
+        let layout = Layout::default_wrap();
+        let geometry = SectionGeometry {
+            screen_position: (10.0, 20.0),
+            bounds: (120.0, f32::INFINITY),
+        };
+        let text = |text| SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            ..SectionText::default()
+        };
+        let fallbacks = FontFallbacks::default();
+
+        let old = [
+            text("first paragraph\nsecond para"),
+            text("graph wraps\nthird"),
+        ];
+        let (mut glyphs, lines) =
+            layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry, &old);
+        // mark a reused glyph
+        glyphs[0].glyph.id = GlyphId(999);
+
+        let changes = [
+            (
+                [
+                    text("first paragraph\nsecond para"),
+                    text("graph wraps\nthird\nfourth"),
+                ],
+                GlyphChange::TextAppended((1, 17)),
+            ),
+            (
+                [
+                    text("first paragraph\nsecond para"),
+                    text("graph WRAPS\nthird"),
+                ],
+                GlyphChange::TextEdited((1, 6)),
+            ),
+        ];
+
+        for (new, change) in changes {
+            let (expected, expected_lines) =
+                layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry, &new);
+            let (recalc, recalc_lines) = layout.recalculate_glyphs_and_lines(
+                PreviousLayout::new(glyphs.clone(), &lines),
+                change,
+                &*FONT_MAP,
+                &fallbacks,
+                &geometry,
+                &new,
+            );
+
+            assert_eq!(
+                recalc[0].glyph.id,
+                GlyphId(999),
+                "first paragraph not reused"
+            );
+            assert_eq!(recalc.len(), expected.len());
+            for (sg, expected) in recalc.iter().zip(&expected).skip(1) {
+                assert_eq!(sg.glyph.id, expected.glyph.id);
+                assert_eq!(
+                    (sg.section_index, sg.byte_index),
+                    (expected.section_index, expected.byte_index)
+                );
+                assert_relative_eq!(sg.glyph.position.x, expected.glyph.position.x);
+                assert_relative_eq!(sg.glyph.position.y, expected.glyph.position.y);
+            }
+
+            assert_eq!(recalc_lines.len(), expected_lines.len());
+            for (line, expected) in recalc_lines.iter().zip(&expected_lines) {
+                assert_relative_eq!(line.baseline, expected.baseline);
+                assert_eq!(
+                    SectionLine {
+                        baseline: expected.baseline,
+                        ..line.clone()
+                    },
+                    *expected
+                );
+            }
+        }
+
+        // center aligned lines depend on later lines
+        let (recalc, _) = Layout::default_wrap()
+            .v_align(VerticalAlign::Center)
+            .recalculate_glyphs_and_lines(
+                PreviousLayout::new(glyphs.clone(), &lines),
+                GlyphChange::TextEdited((1, 6)),
+                &*FONT_MAP,
+                &fallbacks,
+                &geometry,
+                &old,
+            );
+        assert_ne!(recalc[0].glyph.id, GlyphId(999));
+    }
+
+    #[test]
+    fn recalculate_text_changes_within_paragraph() {
+        let fallbacks = FontFallbacks::default();
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let geometry = SectionGeometry {
+            screen_position: (10.0, 20.0),
+            bounds: (advance * 8.5, f32::INFINITY),
+        };
+        let text = |text| {
+            [SectionText {
+                text,
+                scale: PxScale::from(20.0),
+                ..SectionText::default()
+            }]
+        };
+
+        let layout = Layout::default_wrap();
+        let old = text("aaa bbb ccc ddd eee fff ggg");
+        let (mut glyphs, lines) =
+            layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry, &old);
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().rev().skip(1).all(|line| !line.hard_break));
+        // mark the last glyph of the 2nd line & the first of the 3rd
+        let marked = [lines[1].glyphs.end - 1, lines[2].glyphs.start];
+        for idx in marked {
+            glyphs[idx].glyph.id = GlyphId(999);
+        }
+
+        let changes = [
+            (
+                text("aaa bbb ccc ddd eee fff ggg hhh"),
+                GlyphChange::TextAppended((0, 27)),
+                [true, false],
+            ),
+            (
+                text("aaa bbb ccc ddd eee f ggg"),
+                GlyphChange::TextEdited((0, 20)),
+                [false, false],
+            ),
+        ];
+        for (new, change, reused) in changes {
+            let (expected, expected_lines) =
+                layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry, &new);
+            let (recalc, recalc_lines) = layout.recalculate_glyphs_and_lines(
+                PreviousLayout::new(glyphs.clone(), &lines),
+                change,
+                &*FONT_MAP,
+                &fallbacks,
+                &geometry,
+                &new,
+            );
+
+            assert_eq!(recalc.len(), expected.len());
+            for (idx, (sg, expected)) in recalc.iter().zip(&expected).enumerate() {
+                match marked.iter().position(|&m| m == idx) {
+                    Some(m) => assert_eq!(sg.glyph.id == GlyphId(999), reused[m], "glyph {idx}"),
+                    None => assert_eq!(sg.glyph.id, expected.glyph.id),
+                }
+                assert_eq!(sg.byte_index, expected.byte_index);
+                assert_relative_eq!(sg.glyph.position.x, expected.glyph.position.x);
+                assert_relative_eq!(sg.glyph.position.y, expected.glyph.position.y);
+            }
+            assert_eq!(recalc_lines, expected_lines);
+        }
+
+        // lines broken within words depend on the rest of the word
+        let layout = Layout::default_wrap().overflow_wrap(OverflowWrap::Anywhere);
+        let old = text("aaa bbbbbbbbbbbbbbbb");
+        let (mut glyphs, lines) =
+            layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry, &old);
+        assert_eq!(lines.len(), 3);
+        glyphs[0].glyph.id = GlyphId(999);
+        let (recalc, _) = layout.recalculate_glyphs_and_lines(
+            PreviousLayout::new(glyphs, &lines),
+            GlyphChange::TextAppended((0, 20)),
+            &*FONT_MAP,
+            &fallbacks,
+            &geometry,
+            &text("aaa bbbbbbbbbbbbbbbbb"),
+        );
+        assert_ne!(recalc[0].glyph.id, GlyphId(999));
+    }
+
+    #[test]
+    fn recalculate_bounds_with_measured_words() {
+        let layout = Layout::default_wrap().h_align(HorizontalAlign::Center);
+        let fallbacks = FontFallbacks::default();
+        let sections = [SectionText {
+            text: "hello world, a few more words\nwrap\ttab",
+            scale: PxScale::from(20.0),
+            ..SectionText::default()
+        }];
+        let geometry_1 = SectionGeometry {
+            screen_position: (100.0, 0.0),
+            bounds: (200.0, f32::INFINITY),
+        };
+        let geometry_2 = SectionGeometry {
+            bounds: (90.0, f32::INFINITY),
+            ..geometry_1
+        };
+
+        let words = layout.measure_words(&*FONT_MAP, &fallbacks, &sections);
+        assert!(words.is_some());
+        let (glyphs, lines) =
+            layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry_1, &sections);
+
+        let (recalc, recalc_lines) = layout.recalculate_glyphs_and_lines(
+            PreviousLayout::new(glyphs, &lines).with_words(words.as_ref()),
+            GlyphChange::Geometry(geometry_1),
+            &*FONT_MAP,
+            &fallbacks,
+            &geometry_2,
+            &sections,
+        );
+        let (expected, expected_lines) =
+            layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry_2, &sections);
+
+        assert!(recalc_lines.len() > lines.len());
+        assert_eq!(recalc_lines, expected_lines);
+        assert_eq!(recalc, expected);
+
+        // vertical layouts don't measure words
+        let words = Layout::default_vertical().measure_words(&*FONT_MAP, &fallbacks, &sections);
+        assert!(words.is_none());
+    }
+
+    #[test]
+    fn intrinsic_widths_height_for_width() {
+        let fallbacks = FontFallbacks::default();
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        let line_height = sfont.height() + sfont.line_gap();
+        let sections = [SectionText {
+            text: "aa aaaa a\naaaaaaa aa",
+            scale: PxScale::from(20.0),
+            ..SectionText::default()
+        }];
+
+        let layout = Layout::default_wrap();
+        let words = layout
+            .measure_words(&*FONT_MAP, &fallbacks, &sections)
+            .unwrap();
+        let widths = layout.intrinsic_widths(&words, &sections).unwrap();
+        assert_relative_eq!(widths.min_content, advance * 7.0);
+        assert_relative_eq!(widths.max_content, advance * 10.0);
+
+        // consistent with the laid out lines
+        for (width, line_count) in [(widths.min_content, 4), (widths.max_content, 2)] {
+            let geometry = SectionGeometry {
+                bounds: (width, f32::INFINITY),
+                ..<_>::default()
+            };
+            let (_, lines) =
+                layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry, &sections);
+            assert_eq!(lines.len(), line_count);
+
+            let height = layout.height_for_width(&words, width, &sections).unwrap();
+            assert_relative_eq!(height, line_height * line_count as f32, epsilon = 1e-4);
+        }
+
+        // indents inset & overflow-wrap breaks between characters
+        let indented = layout
+            .clone()
+            .indents(Indents {
+                left: 5.0,
+                right: 5.0,
+                first_line: 10.0,
+                hanging: 0.0,
+            })
+            .overflow_wrap(OverflowWrap::Anywhere);
+        let widths = indented.intrinsic_widths(&words, &sections).unwrap();
+        assert_relative_eq!(widths.min_content, 20.0 + advance);
+        assert_relative_eq!(widths.max_content, 20.0 + advance * 10.0);
+
+        // single lines only lay out the first line
+        let single_line = Layout::default_single_line();
+        let widths = single_line.intrinsic_widths(&words, &sections).unwrap();
+        assert_relative_eq!(widths.min_content, advance * 9.0);
+        assert_relative_eq!(widths.max_content, advance * 9.0);
+        let height = single_line
+            .height_for_width(&words, 10.0, &sections)
+            .unwrap();
+        assert_relative_eq!(height, line_height, epsilon = 1e-4);
+
+        // clamped lines
+        let height = layout
+            .max_lines(3)
+            .height_for_width(&words, widths.min_content, &sections);
+        assert_relative_eq!(height.unwrap(), line_height * 3.0, epsilon = 1e-4);
+
+        let vertical = Layout::default_vertical();
+        assert!(vertical.intrinsic_widths(&words, &sections).is_none());
+        assert!(vertical.height_for_width(&words, 10.0, &sections).is_none());
+    }
+
+    #[test]
+    fn recalculate_uniform_scale() {
+        let fallbacks = FontFallbacks::default();
+        let sections = |scale: f32| {
+            [
+                SectionText {
+                    text: "hello world\n",
+                    scale: PxScale::from(scale),
+                    ..SectionText::default()
+                },
+                SectionText {
+                    text: "tab\tbigger",
+                    scale: PxScale::from(scale * 1.5),
+                    ..SectionText::default()
+                },
+            ]
+        };
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 50.0),
+            bounds: (400.0, f32::INFINITY),
+        };
+
+        for layout in [
+            Layout::default_wrap().h_align(HorizontalAlign::Center),
+            Layout::default_single_line().v_align(VerticalAlign::Bottom),
+        ] {
+            let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+                &*FONT_MAP,
+                &fallbacks,
+                &geometry,
+                &sections(20.0),
+            );
+            let (recalc, recalc_lines) = layout.recalculate_glyphs_and_lines(
+                PreviousLayout::new(glyphs, &lines),
+                GlyphChange::Scale(1.5),
+                &*FONT_MAP,
+                &fallbacks,
+                &geometry,
+                &sections(30.0),
+            );
+            let (expected, expected_lines) = layout.calculate_glyphs_and_lines(
+                &*FONT_MAP,
+                &fallbacks,
+                &geometry,
+                &sections(30.0),
+            );
+
+            assert_eq!(recalc.len(), expected.len());
+            for (recalc, expected) in recalc.iter().zip(&expected) {
+                assert_eq!(recalc.glyph.id, expected.glyph.id);
+                assert_eq!(recalc.glyph.scale, expected.glyph.scale);
+                assert_relative_eq!(
+                    recalc.glyph.position.x,
+                    expected.glyph.position.x,
+                    epsilon = 1e-3
+                );
+                assert_relative_eq!(
+                    recalc.glyph.position.y,
+                    expected.glyph.position.y,
+                    epsilon = 1e-3
+                );
+            }
+            assert_eq!(recalc_lines.len(), expected_lines.len());
+            for (recalc, expected) in recalc_lines.iter().zip(&expected_lines) {
+                assert_eq!(recalc.glyphs, expected.glyphs);
+                assert_relative_eq!(recalc.left, expected.left, epsilon = 1e-3);
+                assert_relative_eq!(recalc.baseline, expected.baseline, epsilon = 1e-3);
+                assert_relative_eq!(recalc.advance_width, expected.advance_width, epsilon = 1e-3);
+            }
+        }
+
+        // scaling up would wrap "hello world"
+        let layout = Layout::default_wrap();
+        let (mut glyphs, lines) =
+            layout.calculate_glyphs_and_lines(&*FONT_MAP, &fallbacks, &geometry, &sections(20.0));
+        glyphs[0].glyph.id = GlyphId(999);
+        let (recalc, _) = layout.recalculate_glyphs_and_lines(
+            PreviousLayout::new(glyphs.clone(), &lines),
+            GlyphChange::Scale(4.0),
+            &*FONT_MAP,
+            &fallbacks,
+            &geometry,
+            &sections(80.0),
+        );
+        let expected = layout.calculate_glyphs(&*FONT_MAP, &geometry, &sections(80.0));
+        assert_eq!(recalc, expected);
+
+        // without lines only unbounded widths are scaled
+        let recalc = layout.recalculate_glyphs(
+            glyphs,
+            GlyphChange::Scale(1.5),
+            &*FONT_MAP,
+            &geometry,
+            &sections(30.0),
+        );
+        let expected = layout.calculate_glyphs(&*FONT_MAP, &geometry, &sections(30.0));
+        assert_eq!(recalc, expected);
+    }
+
+    /// Chinese sentence squeezed into a vertical pipe meaning each character is on
+    /// a separate line.
+    #[test]
+    fn wrap_word_chinese() {
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry {
+                bounds: (25.0, f32::INFINITY),
+                ..<_>::default()
+            },
+            &[SectionText {
+                text: "提高代碼執行率",
+                scale: PxScale::from(20.0),
+                font_id: FontId(1),
+                ..<_>::default()
+            }],
+        );
 
         assert_glyph_order!(glyphs, "提高代碼執行率", font = &*CJK_FONT);
 
@@ -1063,4 +2730,1220 @@ mod layout_test {
             no_space_f.glyph.position.x,
         );
     }
+
+    #[test]
+    fn wrap_justify() {
+        let text = "The quick brown fox jumps over the lazy dog.\nShort line";
+        let geometry = SectionGeometry {
+            bounds: (150.0, f32::INFINITY),
+            ..<_>::default()
+        };
+        let sections = &[SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+
+        let left = Layout::default().calculate_glyphs(&*FONT_MAP, &geometry, sections);
+        let justified = Layout::default()
+            .h_align(HorizontalAlign::Justify)
+            .calculate_glyphs(&*FONT_MAP, &geometry, sections);
+
+        assert_glyph_order!(
+            justified,
+            "The quick brown fox jumps over the lazy dog.Short line"
+        );
+
+        let mut lines: BTreeMap<OrderedFloat<f32>, Vec<&SectionGlyph>> = BTreeMap::new();
+        for sg in &justified {
+            lines
+                .entry(OrderedFloat(sg.glyph.position.y))
+                .or_default()
+                .push(sg);
+        }
+        assert!(lines.len() > 3, "{lines:?}");
+
+        let sfont = A_FONT.as_scaled(20.0);
+        let space = A_FONT.glyph_id(' ');
+        let paragraph_ends = [text.find('.').unwrap(), text.len() - 1];
+        for line in lines.values() {
+            let last = line.iter().rev().find(|sg| sg.glyph.id != space).unwrap();
+            let right = last.glyph.position.x + sfont.h_advance(last.glyph.id);
+            if paragraph_ends.contains(&last.byte_index) {
+                // last line of each paragraph is left aligned
+                assert!(right < 150.0, "{right}");
+            } else {
+                assert_relative_eq!(right, 150.0, epsilon = 1e-3);
+            }
+            // first word is never shifted
+            assert_relative_eq!(line[0].glyph.position.x, 0.0);
+        }
+
+        // last lines are identical to left aligned ones
+        let last_para = text.find('S').unwrap();
+        for (j, l) in justified.iter().zip(&left) {
+            if j.byte_index >= last_para {
+                assert_eq!(j, l);
+            }
+        }
+    }
+
+    #[test]
+    fn wrap_indents() {
+        let text = "The quick brown fox jumps over the lazy dog.\nShort line";
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 0.0),
+            bounds: (200.0, f32::INFINITY),
+        };
+        let sections = &[SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let indents = Indents {
+            left: 10.0,
+            right: 20.0,
+            first_line: 30.0,
+            hanging: 5.0,
+        };
+        let layout = Layout::default().indents(indents);
+
+        let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+            &*FONT_MAP,
+            &FontFallbacks::default(),
+            &geometry,
+            sections,
+        );
+        assert!(lines.len() > 3, "{lines:?}");
+
+        let sfont = A_FONT.as_scaled(20.0);
+        let space = A_FONT.glyph_id(' ');
+        for line in &lines {
+            let indent = match line.index == 0 || lines[line.index - 1].hard_break {
+                true => 30.0,
+                false => 5.0,
+            };
+            assert_relative_eq!(line.left, 110.0 + indent);
+
+            // fits within the right margin, ignoring trailing whitespace
+            let last = glyphs[line.glyphs.clone()]
+                .iter()
+                .rfind(|sg| sg.glyph.id != space)
+                .unwrap();
+            let right = last.glyph.position.x + sfont.h_advance(last.glyph.id);
+            assert!(right <= 280.0 + 1e-3, "{right}");
+        }
+        assert!(!lines[1].hard_break);
+        assert!(lines[lines.len() - 2].hard_break);
+
+        // right aligned lines end at the right margin
+        let (glyphs, lines) = layout
+            .h_align(HorizontalAlign::Right)
+            .calculate_glyphs_and_lines(&*FONT_MAP, &FontFallbacks::default(), &geometry, sections);
+        let last_line = lines.last().unwrap();
+        let last = &glyphs[last_line.glyphs.end - 1];
+        let right = last.glyph.position.x + sfont.h_advance(last.glyph.id);
+        assert_relative_eq!(right, 100.0 - 20.0, epsilon = 1e-3);
+
+        // single lines are unaffected
+        let single_line = Layout::default_single_line().indents(indents);
+        assert_eq!(single_line, Layout::default_single_line());
+    }
+
+    #[test]
+    fn wrap_optimal_breaks() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let geometry = SectionGeometry {
+            bounds: (advance * 10.5, f32::INFINITY),
+            ..<_>::default()
+        };
+        let sections = &[SectionText {
+            text: "aaaa bb cc dddddd eeeee",
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let starts = |layout: Layout<BuiltInLineBreaker>| {
+            let (_, lines) = layout.calculate_glyphs_and_lines(
+                &*FONT_MAP,
+                &FontFallbacks::default(),
+                &geometry,
+                sections,
+            );
+            lines.iter().map(|l| l.start.1).collect::<Vec<_>>()
+        };
+
+        // greedy leaves "dddddd" alone on a loose line
+        assert_eq!(starts(Layout::default()), [0, 11, 18]);
+        assert_eq!(
+            starts(Layout::default().break_strategy(BreakStrategy::Optimal)),
+            [0, 8, 18]
+        );
+    }
+
+    #[test]
+    fn wrap_word_break_modes() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let geometry = SectionGeometry {
+            bounds: (advance * 4.5, f32::INFINITY),
+            ..<_>::default()
+        };
+        let sections = &[SectionText {
+            text: "aaaaaaaaaa bb",
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let starts = |layout: Layout<BuiltInLineBreaker>| {
+            let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+                &*FONT_MAP,
+                &FontFallbacks::default(),
+                &geometry,
+                sections,
+            );
+            assert_glyph_order!(glyphs, "aaaaaaaaaa bb");
+            lines.iter().map(|l| l.start.1).collect::<Vec<_>>()
+        };
+
+        // the long word overflows the bounds
+        assert_eq!(starts(Layout::default()), [0, 11]);
+        // broken only as it doesn't fit on a line of its own
+        assert_eq!(
+            starts(Layout::default().overflow_wrap(OverflowWrap::Anywhere)),
+            [0, 4, 8, 11]
+        );
+        // breaking between any letters fills the last line
+        assert_eq!(
+            starts(Layout::default().word_break(WordBreak::BreakAll)),
+            [0, 4, 8, 12]
+        );
+    }
+
+    #[test]
+    fn wrap_overflow_wrap_positions() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let glyphs = Layout::default()
+            .overflow_wrap(OverflowWrap::Anywhere)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (advance * 3.5, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text: "bb aaaaaaa",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            );
+        assert_glyph_order!(glyphs, "bb aaaaaaa");
+
+        // the word moves to its own line before breaking
+        let rows: Vec<_> = glyphs
+            .iter()
+            .map(|sg| {
+                (
+                    (sg.glyph.position.x / advance).round() as u32,
+                    OrderedFloat(sg.glyph.position.y),
+                )
+            })
+            .collect();
+        let ys: BTreeSet<_> = rows.iter().map(|(_, y)| *y).collect();
+        assert_eq!(ys.len(), 4, "{rows:?}");
+        let xs: Vec<_> = rows.iter().map(|(x, _)| *x).collect();
+        assert_eq!(xs, [0, 1, 2, 0, 1, 2, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn white_space_modes() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let sections = &[SectionText {
+            text: "  aa   bb\r\n  cc  ",
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let layout = |white_space: WhiteSpace, bound_w: f32| {
+            let (glyphs, lines) = Layout::default()
+                .white_space(white_space)
+                .calculate_glyphs_and_lines(
+                    &*FONT_MAP,
+                    &FontFallbacks::default(),
+                    &SectionGeometry {
+                        bounds: (bound_w, f32::INFINITY),
+                        ..<_>::default()
+                    },
+                    sections,
+                );
+            let bytes: Vec<_> = glyphs.iter().map(|sg| sg.byte_index).collect();
+            let starts: Vec<_> = lines.iter().map(|l| l.start.1).collect();
+            (glyphs, bytes, starts)
+        };
+
+        // spaces collapse & the newline is a space, glyphs keep their original byte index
+        let (glyphs, bytes, starts) = layout(WhiteSpace::Normal, f32::INFINITY);
+        assert_eq!(bytes, [2, 3, 4, 7, 8, 9, 13, 14, 15]);
+        assert_eq!(starts, [0]);
+        assert_eq!(glyphs[0].glyph.position.x, 0.0);
+        let space = A_FONT.glyph_id(' ');
+        assert_eq!(glyphs[5].glyph.id, space);
+        assert_eq!(layout(WhiteSpace::Normal, advance * 3.5).2, [0, 7, 11]);
+
+        // the trailing space is removed at the end of the line
+        let layout_normal = Layout::default().white_space(WhiteSpace::Normal);
+        let words = layout_normal
+            .measure_words(&*FONT_MAP, &FontFallbacks::default(), sections)
+            .unwrap();
+        let space_advance = A_FONT.as_scaled(20.0).h_advance(space);
+        let widths = layout_normal.intrinsic_widths(&words, sections).unwrap();
+        assert_relative_eq!(widths.max_content, advance * 6.0 + space_advance * 2.0);
+
+        // newlines are kept, "\r\n" is a single newline
+        let (_, bytes, starts) = layout(WhiteSpace::PreLine, f32::INFINITY);
+        assert_eq!(bytes, [2, 3, 4, 7, 8, 13, 14, 15]);
+        assert_eq!(starts, [0, 11]);
+        assert_eq!(layout(WhiteSpace::PreLine, advance * 3.5).2, [0, 7, 11]);
+
+        // everything is kept
+        let (_, bytes, starts) = layout(WhiteSpace::PreWrap, advance * 3.5);
+        assert_eq!(bytes, [0, 1, 2, 3, 4, 5, 6, 7, 8, 11, 12, 13, 14, 15, 16]);
+        assert_eq!(starts, [0, 2, 7, 11, 13]);
+
+        // lines only break at newlines
+        let (_, bytes, starts) = layout(WhiteSpace::Pre, advance * 3.5);
+        assert_eq!(bytes, [0, 1, 2, 3, 4, 5, 6, 7, 8, 11, 12, 13, 14, 15, 16]);
+        assert_eq!(starts, [0, 11]);
+    }
+
+    #[test]
+    fn wrap_exclusions() {
+        static EXCLUSIONS: &[Exclusion] = &[
+            // icon at the top left
+            Exclusion::Rect(Rect {
+                min: Point { x: 0.0, y: 0.0 },
+                max: Point { x: 60.0, y: 50.0 },
+            }),
+            // pull quote in the middle
+            Exclusion::Rect(Rect {
+                min: Point { x: 120.0, y: 80.0 },
+                max: Point { x: 180.0, y: 120.0 },
+            }),
+        ];
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(6);
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 0.0),
+            bounds: (300.0, f32::INFINITY),
+        };
+        let sections = &[SectionText {
+            text: &text,
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+
+        let (glyphs, lines) = Layout::default()
+            .exclusions(EXCLUSIONS)
+            .calculate_glyphs_and_lines(&*FONT_MAP, &FontFallbacks::default(), &geometry, sections);
+        assert_eq!(glyphs.len(), text.len());
+
+        let sfont = A_FONT.as_scaled(20.0);
+        let line_h = sfont.height() + sfont.line_gap();
+        let space = A_FONT.glyph_id(' ');
+        let mut split_rows = 0;
+        for (idx, line) in lines.iter().enumerate() {
+            let top = line.baseline - line.ascent;
+            let bottom = top + line_h;
+            // lines flow beside the icon
+            if top < 50.0 {
+                assert!(line.left >= 160.0 - 1e-3, "{line:?}");
+            } else if line.left < 160.0 {
+                assert_relative_eq!(line.left, 100.0);
+            }
+
+            // & either side of the pull quote
+            if top < 120.0 && bottom > 80.0 {
+                let right = glyphs[line.glyphs.clone()]
+                    .iter()
+                    .filter(|sg| sg.glyph.id != space)
+                    .map(|sg| sg.glyph.position.x + sfont.h_advance(sg.glyph.id))
+                    .fold(f32::MIN, f32::max);
+                match line.left < 220.0 {
+                    true => assert!(right <= 220.0 + 1e-3, "{right}"),
+                    false => assert!(line.left >= 280.0 - 1e-3),
+                }
+                if idx > 0 && lines[idx - 1].baseline == line.baseline {
+                    split_rows += 1;
+                }
+            }
+        }
+        assert!(split_rows > 0, "{lines:?}");
+
+        // text continues in order
+        for pair in lines.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+    }
+
+    /// Returns glyph byte indices sorted left-to-right on screen.
+    fn visual_byte_order(glyphs: &[SectionGlyph]) -> Vec<usize> {
+        let mut glyphs: Vec<_> = glyphs.iter().collect();
+        glyphs.sort_by_key(|sg| OrderedFloat(sg.glyph.position.x));
+        glyphs.iter().map(|sg| sg.byte_index).collect()
+    }
+
+    #[test]
+    fn bidi_ltr_paragraph_rtl_run() {
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "ab \u{5d0}\u{5d1}\u{5d2} cd",
+                scale: PxScale::from(20.0),
+                ..<_>::default()
+            }],
+        );
+
+        assert_eq!(glyphs.len(), 9);
+        assert_eq!(visual_byte_order(&glyphs), [0, 1, 2, 7, 5, 3, 9, 10, 11]);
+        assert_relative_eq!(glyphs[0].glyph.position.x, 0.0);
+    }
+
+    #[test]
+    fn bidi_rtl_paragraph_start_end() {
+        let sections = &[SectionText {
+            text: "\u{5d0}\u{5d1}\u{5d2} abc",
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let geometry = SectionGeometry {
+            bounds: (200.0, f32::INFINITY),
+            ..<_>::default()
+        };
+        let sfont = A_FONT.as_scaled(20.0);
+
+        let start = Layout::default()
+            .h_align(HorizontalAlign::Start)
+            .calculate_glyphs(&*FONT_MAP, &geometry, sections);
+
+        // right-to-left paragraph reads "abc" to the left of the hebrew
+        assert_eq!(visual_byte_order(&start), [7, 8, 9, 6, 4, 2, 0]);
+
+        // & starts on the right of the bounds
+        let rightmost = start.iter().find(|sg| sg.byte_index == 0).unwrap();
+        assert_relative_eq!(
+            rightmost.glyph.position.x + sfont.h_advance(rightmost.glyph.id),
+            200.0,
+            epsilon = 1e-3
+        );
+
+        let end = Layout::default()
+            .h_align(HorizontalAlign::End)
+            .calculate_glyphs(&*FONT_MAP, &geometry, sections);
+        assert_eq!(visual_byte_order(&end), [7, 8, 9, 6, 4, 2, 0]);
+        let leftmost = end.iter().find(|sg| sg.byte_index == 7).unwrap();
+        assert_relative_eq!(leftmost.glyph.position.x, 0.0);
+
+        // left-to-right paragraphs are the other way around
+        let ltr_end = Layout::default()
+            .h_align(HorizontalAlign::End)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &geometry,
+                &[SectionText {
+                    text: "abc",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            );
+        let last = ltr_end.last().unwrap();
+        assert_relative_eq!(
+            last.glyph.position.x + sfont.h_advance(last.glyph.id),
+            200.0,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn bidi_mirror_brackets() {
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "(\u{5d0}\u{5d1})",
+                scale: PxScale::from(20.0),
+                ..<_>::default()
+            }],
+        );
+
+        // logical order is retained in the byte indices
+        assert_eq!(glyphs[0].byte_index, 5);
+        assert_eq!(glyphs[3].byte_index, 0);
+
+        // logical '(' is mirrored & drawn on the right
+        assert_eq!(glyphs[3].glyph.id, A_FONT.glyph_id(')'));
+        assert_eq!(glyphs[0].glyph.id, A_FONT.glyph_id('('));
+    }
+
+    #[test]
+    fn font_fallback_per_cluster() {
+        let garamond =
+            FontRef::try_from_slice(include_bytes!("../../fonts/GaramondNo8-Reg.ttf")).unwrap();
+        let dejavu = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let fonts = [garamond, dejavu];
+
+        let mut fallbacks = FontFallbacks::default();
+        fallbacks.set(FontId(0), [FontId(1)]);
+
+        let sections = [SectionText {
+            text: "a\u{2764}b e\u{301}",
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+
+        let glyphs = Layout::default().calculate_glyphs_with_fallbacks(
+            &fonts,
+            &fallbacks,
+            &SectionGeometry::default(),
+            &sections,
+        );
+
+        let font_ids: Vec<_> = glyphs.iter().map(|sg| sg.font_id.0).collect();
+        // garamond lacks '❤' & the combining acute, which stays with its base 'e'
+        assert_eq!(font_ids, [0, 1, 0, 0, 1, 1]);
+        assert_eq!(glyphs[1].glyph.id, fonts[1].glyph_id('\u{2764}'));
+        assert_eq!(glyphs[5].glyph.id, fonts[1].glyph_id('\u{301}'));
+
+        // without fallbacks all glyphs use the section font
+        let glyphs =
+            Layout::default().calculate_glyphs(&fonts, &SectionGeometry::default(), &sections);
+        assert!(glyphs.iter().all(|sg| sg.font_id == FontId(0)));
+        assert_eq!(glyphs[1].glyph.id, GlyphId(0));
+    }
+
+    #[test]
+    fn line_height_half_leading() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let sections = |line_height| {
+            [SectionText {
+                text: "a\nb",
+                scale: PxScale::from(20.0),
+                line_height,
+                ..<_>::default()
+            }]
+        };
+
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry::default(),
+            &sections(LineHeight::Px(40.0)),
+        );
+        let half_leading = (40.0 - sfont.ascent() + sfont.descent()) / 2.0;
+        assert_relative_eq!(glyphs[0].glyph.position.y, sfont.ascent() + half_leading);
+        assert_relative_eq!(
+            glyphs[1].glyph.position.y - glyphs[0].glyph.position.y,
+            40.0
+        );
+
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry::default(),
+            &sections(LineHeight::Multiplier(1.5)),
+        );
+        assert_relative_eq!(
+            glyphs[1].glyph.position.y - glyphs[0].glyph.position.y,
+            30.0
+        );
+
+        // normal line height is unchanged
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry::default(),
+            &sections(LineHeight::Normal),
+        );
+        assert_relative_eq!(glyphs[0].glyph.position.y, sfont.ascent());
+        assert_relative_eq!(
+            glyphs[1].glyph.position.y - glyphs[0].glyph.position.y,
+            sfont.height() + sfont.line_gap()
+        );
+    }
+
+    #[test]
+    fn letter_and_word_spacing() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "ab c",
+                scale: PxScale::from(20.0),
+                letter_spacing: 2.0,
+                word_spacing: 5.0,
+                ..<_>::default()
+            }],
+        );
+        assert_glyph_order!(glyphs, "ab c");
+
+        let x: Vec<_> = glyphs.iter().map(|sg| sg.glyph.position.x).collect();
+        assert_relative_eq!(x[1], advance + 2.0);
+        assert_relative_eq!(x[2], (advance + 2.0) * 2.0);
+        assert_relative_eq!(x[3], (advance + 2.0) * 3.0 + 5.0);
+
+        // spacing is included in wrapping width checks
+        let sections = |letter_spacing| {
+            [SectionText {
+                text: "aaaa",
+                scale: PxScale::from(20.0),
+                letter_spacing,
+                ..<_>::default()
+            }]
+        };
+        let geometry = SectionGeometry {
+            bounds: (advance * 4.0 + 1.0, f32::INFINITY),
+            ..<_>::default()
+        };
+        let layout = Layout::default().line_breaker(AnyCharLineBreaker);
+
+        let glyphs = layout.calculate_glyphs(&*FONT_MAP, &geometry, &sections(0.0));
+        assert_relative_eq!(glyphs[3].glyph.position.y, glyphs[0].glyph.position.y);
+
+        let glyphs = layout.calculate_glyphs(&*FONT_MAP, &geometry, &sections(1.0));
+        assert!(glyphs[3].glyph.position.y > glyphs[0].glyph.position.y);
+    }
+
+    #[test]
+    fn baseline_shifts() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let sections = |baseline_shift| {
+            [
+                SectionText {
+                    text: "x",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                },
+                SectionText {
+                    text: "2",
+                    scale: PxScale::from(20.0),
+                    baseline_shift,
+                    ..<_>::default()
+                },
+            ]
+        };
+        let lines = |baseline_shift| {
+            Layout::default()
+                .calculate_glyphs_and_lines(
+                    &*FONT_MAP,
+                    &FontFallbacks::default(),
+                    &SectionGeometry::default(),
+                    &sections(baseline_shift),
+                )
+                .1
+        };
+
+        let (glyphs, raised) = Layout::default().calculate_glyphs_and_lines(
+            &*FONT_MAP,
+            &FontFallbacks::default(),
+            &SectionGeometry::default(),
+            &sections(BaselineShift::Px(5.0)),
+        );
+        // shifted runs raise the line's ascent
+        assert_relative_eq!(glyphs[0].glyph.position.y, sfont.ascent() + 5.0);
+        assert_relative_eq!(glyphs[0].glyph.position.y - glyphs[1].glyph.position.y, 5.0);
+        assert_relative_eq!(raised[0].ascent, sfont.ascent() + 5.0);
+        assert_relative_eq!(raised[0].descent, sfont.descent());
+
+        // & lowered runs its descent
+        let sub = lines(BaselineShift::Px(-5.0));
+        assert_relative_eq!(sub[0].ascent, sfont.ascent());
+        assert_relative_eq!(sub[0].descent, sfont.descent() - 5.0);
+
+        // presets use the font's OS/2 table offsets
+        let sup = lines(BaselineShift::Superscript);
+        let sup_offset = BaselineShift::Superscript.px(&sfont);
+        assert!(sup_offset > 0.0);
+        assert_relative_eq!(sup[0].ascent, sfont.ascent() + sup_offset);
+
+        let sub = lines(BaselineShift::Subscript);
+        let sub_offset = BaselineShift::Subscript.px(&sfont);
+        assert!(sub_offset < 0.0);
+        assert_relative_eq!(sub[0].descent, sfont.descent() + sub_offset);
+
+        let normal = lines(BaselineShift::Baseline);
+        assert_relative_eq!(normal[0].ascent, sfont.ascent());
+        assert_relative_eq!(normal[0].descent, sfont.descent());
+    }
+
+    #[test]
+    fn placeholders() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        let icon = Placeholder {
+            width: 30.0,
+            height: 40.0,
+            baseline_offset: 4.0,
+        };
+        let sections = [
+            SectionText {
+                text: "a ",
+                scale: PxScale::from(20.0),
+                ..<_>::default()
+            },
+            SectionText {
+                text: "\u{FFFC}",
+                scale: PxScale::from(20.0),
+                placeholder: Some(icon),
+                ..<_>::default()
+            },
+            SectionText {
+                text: "a",
+                scale: PxScale::from(20.0),
+                ..<_>::default()
+            },
+        ];
+
+        let glyphs =
+            Layout::default().calculate_glyphs(&*FONT_MAP, &SectionGeometry::default(), &sections);
+        assert_eq!(glyphs.len(), 4);
+        assert!(glyphs[2].is_placeholder());
+        assert_eq!(glyphs[2].section_index, 1);
+
+        // placeholder height is included in the line height
+        let baseline = glyphs[0].glyph.position.y;
+        assert_relative_eq!(baseline, 36.0);
+        let rect = icon.rect(&glyphs[2]);
+        assert_relative_eq!(rect.min.y, 0.0);
+        assert_relative_eq!(rect.max.y, baseline + 4.0);
+
+        // followed directly by the next word, occupying its width
+        assert_relative_eq!(glyphs[3].glyph.position.x, rect.max.x);
+        assert_relative_eq!(rect.width(), 30.0);
+
+        // placeholders wrap as unbreakable words
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry {
+                bounds: (advance * 2.0 + 20.0, f32::INFINITY),
+                ..<_>::default()
+            },
+            &sections,
+        );
+        assert_relative_eq!(glyphs[2].glyph.position.x, 0.0);
+        assert!(glyphs[2].glyph.position.y > baseline);
+        assert_relative_eq!(glyphs[3].glyph.position.x, 30.0);
+    }
+
+    fn ellipsized(layout: Layout<BuiltInLineBreaker>, text: &str, bound_w: f32) -> Vec<usize> {
+        layout
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (bound_w, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text,
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            )
+            .iter()
+            .map(|sg| sg.byte_index)
+            .collect()
+    }
+
+    #[test]
+    fn single_line_ellipsis() {
+        const SYNTH: usize = SectionGlyph::SYNTHETIC_BYTE_INDEX;
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let layout = Layout::default_single_line();
+
+        let end = ellipsized(
+            layout.clone().ellipsis(Ellipsis::End("…")),
+            "hello world",
+            advance * 6.0,
+        );
+        assert_eq!(end, [0, 1, 2, 3, 4, SYNTH]);
+
+        let start = ellipsized(
+            layout.clone().ellipsis(Ellipsis::Start("…")),
+            "hello world",
+            advance * 6.0,
+        );
+        assert_eq!(start, [SYNTH, 6, 7, 8, 9, 10]);
+
+        let middle = ellipsized(
+            layout.clone().ellipsis(Ellipsis::Middle("…")),
+            "/home/user/file.txt",
+            advance * 11.0,
+        );
+        assert_eq!(middle, [0, 1, 2, 3, 4, SYNTH, 14, 15, 16, 17, 18]);
+
+        // text ended by a hard break is truncated too
+        let hard_break = ellipsized(
+            layout.clone().ellipsis(Ellipsis::End("...")),
+            "hello\nworld",
+            f32::INFINITY,
+        );
+        assert_eq!(hard_break, [0, 1, 2, 3, 4, SYNTH, SYNTH, SYNTH]);
+
+        // fitting text is untouched
+        let fits = ellipsized(layout.ellipsis(Ellipsis::End("…")), "hello", advance * 6.0);
+        assert_eq!(fits, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn single_line_ellipsis_positions() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+
+        let glyphs = Layout::default_single_line()
+            .ellipsis(Ellipsis::Start("…"))
+            .h_align(HorizontalAlign::Right)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (advance * 4.0, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text: "hello world",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            );
+
+        assert_glyph_order!(glyphs[1..], "rld");
+        assert!(glyphs[0].is_synthetic());
+        assert_eq!(glyphs[0].glyph.id, A_FONT.glyph_id('…'));
+        assert_relative_eq!(glyphs[0].glyph.position.x, -advance * 4.0, epsilon = 1e-3);
+        assert_relative_eq!(glyphs[1].glyph.position.x, -advance * 3.0, epsilon = 1e-3);
+        assert_relative_eq!(glyphs[0].glyph.position.y, sfont.ascent());
+    }
+
+    #[test]
+    fn wrap_max_lines() {
+        const SYNTH: usize = SectionGlyph::SYNTHETIC_BYTE_INDEX;
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let text = "aa bb cc dd";
+
+        let clamped = ellipsized(Layout::default().max_lines(2), text, advance * 3.0);
+        assert_eq!(clamped, [0, 1, 2, 3, 4, 5]);
+
+        let ellipsized_end = ellipsized(
+            Layout::default().max_lines(2).ellipsis(Ellipsis::End("…")),
+            text,
+            advance * 3.0,
+        );
+        // trailing whitespace is replaced by the ellipsis
+        assert_eq!(ellipsized_end, [0, 1, 2, 3, 4, SYNTH]);
+
+        // lines up to the limit are untouched
+        let fits = ellipsized(
+            Layout::default().max_lines(4).ellipsis(Ellipsis::End("…")),
+            text,
+            advance * 3.0,
+        );
+        assert_eq!(fits, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+        let none = ellipsized(Layout::default().max_lines(0), text, advance * 3.0);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn wrap_ellipsis_height_bound() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let line_height = sfont.height() + sfont.line_gap();
+
+        let glyphs = Layout::default()
+            .ellipsis(Ellipsis::End("…"))
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (f32::INFINITY, line_height * 2.5),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text: "one\ntwo\nthree",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            );
+
+        assert_glyph_order!(glyphs[..6], "onetwo");
+        assert_eq!(glyphs.len(), 7);
+        assert!(glyphs[6].is_synthetic());
+        assert_relative_eq!(glyphs[6].glyph.position.y, glyphs[5].glyph.position.y);
+    }
+
+    #[test]
+    fn wrap_hyphenation() {
+        let patterns =
+            crate::HyphenationPatterns::new("hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n");
+        const SYNTH: usize = SectionGlyph::SYNTHETIC_BYTE_INDEX;
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        let layout = Layout::default().line_breaker(crate::HyphenatingLineBreaker::new(patterns));
+
+        let calculate = |bound_w: f32| {
+            layout.calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (bound_w, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text: "hyphenation",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            )
+        };
+
+        // "hyphen-" fits exactly, "hyphena-" would not
+        let glyphs = calculate(advance * 7.0);
+        let bytes: Vec<_> = glyphs.iter().map(|sg| sg.byte_index).collect();
+        assert_eq!(bytes, [0, 1, 2, 3, 4, 5, SYNTH, 6, 7, 8, 9, 10]);
+
+        let hyphen = &glyphs[6];
+        assert_eq!(hyphen.glyph.id, A_FONT.glyph_id('-'));
+        assert_relative_eq!(hyphen.glyph.position.x, advance * 6.0);
+        assert_relative_eq!(hyphen.glyph.position.y, glyphs[0].glyph.position.y);
+        assert_relative_eq!(glyphs[7].glyph.position.x, 0.0);
+        assert!(glyphs[7].glyph.position.y > hyphen.glyph.position.y);
+
+        // no hyphen when the word is not broken
+        let glyphs = calculate(f32::INFINITY);
+        let bytes: Vec<_> = glyphs.iter().map(|sg| sg.byte_index).collect();
+        assert_eq!(bytes, (0..11).collect::<Vec<_>>());
+
+        // the hyphen, wider than the letters after it, isn't needed if the word fits
+        let narrow = crate::HyphenationPatterns::new("")
+            .with_exceptions("aaaa-l")
+            .with_min_lengths(1, 1);
+        let glyphs = Layout::default()
+            .line_breaker(crate::HyphenatingLineBreaker::new(narrow))
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (advance * 3.5, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text: "x aaaal",
+                    scale: PxScale::from(20.0),
+                    letter_spacing: -advance / 2.0,
+                    ..<_>::default()
+                }],
+            );
+        assert_glyph_order!(glyphs, "x aaaal");
+        assert!(glyphs
+            .iter()
+            .all(|sg| sg.glyph.position.y == glyphs[0].glyph.position.y));
+    }
+
+    #[test]
+    fn tab_stops() {
+        let stops = [
+            TabStop::right(200.0),
+            TabStop::center(300.0),
+            TabStop::decimal(400.0, '.'),
+        ];
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+
+        let x_positions = |layout: Layout<BuiltInLineBreaker>, text: &str| -> Vec<f32> {
+            layout
+                .calculate_glyphs(
+                    &*FONT_MAP,
+                    &SectionGeometry::default(),
+                    &[SectionText {
+                        text,
+                        scale: PxScale::from(20.0),
+                        ..<_>::default()
+                    }],
+                )
+                .iter()
+                .map(|sg| sg.glyph.position.x)
+                .collect()
+        };
+
+        // default stops every 8 spaces, tabs are not rendered
+        let xs = x_positions(Layout::default(), "a\tb\tc");
+        assert_eq!(xs.len(), 3);
+        assert_relative_eq!(xs[1], advance * 8.0);
+        assert_relative_eq!(xs[2], advance * 16.0);
+
+        let xs = x_positions(Layout::default().tab_width(TabWidth::Px(50.0)), "aaa\tb");
+        assert_relative_eq!(xs[3], 50.0);
+
+        let layout = Layout::default_single_line().tab_stops(TabStops {
+            stops: stops.into(),
+            width: TabWidth::Px(50.0),
+        });
+        let xs = x_positions(layout.clone(), "a\tbb\tcc\t1.25\tz");
+        // right: "bb" ends at 200
+        assert_relative_eq!(xs[2], 200.0 - advance, epsilon = 1e-3);
+        // center: "cc" centered on 300
+        assert_relative_eq!(xs[3], 300.0 - advance, epsilon = 1e-3);
+        // decimal: "." at 400
+        assert_relative_eq!(xs[6], 400.0, epsilon = 1e-3);
+        assert_relative_eq!(xs[5], 400.0 - advance, epsilon = 1e-3);
+        // default stops after the explicit ones
+        assert_relative_eq!(xs[9], 450.0, epsilon = 1e-3);
+
+        // decimal without a separator ends at the stop
+        let layout = layout.tab_stops(TabStops {
+            stops: stops[2..].into(),
+            ..<_>::default()
+        });
+        let xs = x_positions(layout, "\t42");
+        assert_relative_eq!(xs[1], 400.0 - advance, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn v_align_baselines() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let glyphs = |layout: Layout<BuiltInLineBreaker>, text: &str| {
+            layout.calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    screen_position: (0.0, 100.0),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text,
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            )
+        };
+
+        for layout in [Layout::default_single_line(), Layout::default_wrap()] {
+            let baseline = glyphs(layout.clone().v_align(VerticalAlign::Baseline), "Hxy\nab");
+            assert_relative_eq!(baseline[0].glyph.position.y, 100.0, epsilon = 1e-3);
+
+            let cap_height =
+                A_FONT.outline(A_FONT.glyph_id('H')).unwrap().bounds.min.y * sfont.v_scale_factor();
+            assert!(cap_height > 0.0);
+            let cap = glyphs(
+                layout.clone().v_align(VerticalAlign::CapHeightCenter),
+                "Hxy",
+            );
+            assert_relative_eq!(
+                cap[0].glyph.position.y - cap_height / 2.0,
+                100.0,
+                epsilon = 1e-3
+            );
+
+            let x_height =
+                A_FONT.outline(A_FONT.glyph_id('x')).unwrap().bounds.min.y * sfont.v_scale_factor();
+            let x = glyphs(layout.v_align(VerticalAlign::XHeightCenter), "Hxy");
+            assert_relative_eq!(
+                x[0].glyph.position.y - x_height / 2.0,
+                100.0,
+                epsilon = 1e-3
+            );
+        }
+
+        let last = glyphs(
+            Layout::default_wrap().v_align(VerticalAlign::LastBaseline),
+            "Hxy\nab",
+        );
+        assert_relative_eq!(last[3].glyph.position.y, 100.0, epsilon = 1e-3);
+        assert_relative_eq!(
+            last[0].glyph.position.y,
+            100.0 - sfont.height() - sfont.line_gap(),
+            epsilon = 1e-3
+        );
+
+        // multiple lines center between the first cap top & last baseline
+        let cap_height =
+            A_FONT.outline(A_FONT.glyph_id('H')).unwrap().bounds.min.y * sfont.v_scale_factor();
+        let cap = glyphs(
+            Layout::default_wrap().v_align(VerticalAlign::CapHeightCenter),
+            "Hxy\nab",
+        );
+        let cap_top = cap[0].glyph.position.y - cap_height;
+        assert_relative_eq!(
+            (cap_top + cap[3].glyph.position.y) / 2.0,
+            100.0,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn v_align_optical_os2() {
+        // OS/2 cap-height & x-height below the `H` & `x` outline tops of 1462 & 1087 units
+        let mut data = include_bytes!("../../fonts/OpenSans-Light.ttf").to_vec();
+        let record = data.windows(4).position(|w| w == b"OS/2").unwrap();
+        let os2 = u32::from_be_bytes(data[record + 8..record + 12].try_into().unwrap()) as usize;
+        data[os2 + 86..os2 + 88].copy_from_slice(&900_i16.to_be_bytes());
+        data[os2 + 88..os2 + 90].copy_from_slice(&1200_i16.to_be_bytes());
+        let font = FontVec::try_from_vec(data).unwrap();
+        let sfont = font.as_scaled(20.0);
+
+        let baseline_y = |v_align| {
+            Layout::default_single_line()
+                .v_align(v_align)
+                .calculate_glyphs(
+                    &[&font],
+                    &SectionGeometry {
+                        screen_position: (0.0, 100.0),
+                        ..<_>::default()
+                    },
+                    &[SectionText {
+                        text: "Hx",
+                        scale: PxScale::from(20.0),
+                        ..<_>::default()
+                    }],
+                )[0]
+            .glyph
+            .position
+            .y
+        };
+
+        let cap_height = 1200.0 * sfont.v_scale_factor();
+        let y = baseline_y(VerticalAlign::CapHeightCenter);
+        assert_relative_eq!(y - cap_height / 2.0, 100.0, epsilon = 1e-3);
+
+        let x_height = 900.0 * sfont.v_scale_factor();
+        let y = baseline_y(VerticalAlign::XHeightCenter);
+        assert_relative_eq!(y - x_height / 2.0, 100.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn bidi_ellipsis() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+
+        let glyphs = Layout::default_single_line()
+            .ellipsis(Ellipsis::End("…"))
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (advance * 4.0, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text: "\u{5d0}\u{5d1}\u{5d2}\u{5d3}\u{5d4}",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            );
+
+        // right-to-left text is truncated at its logical end, visually leftmost
+        assert_eq!(
+            visual_byte_order(&glyphs)[0],
+            SectionGlyph::SYNTHETIC_BYTE_INDEX
+        );
+        assert_eq!(visual_byte_order(&glyphs)[1..], [4, 2, 0]);
+    }
+
+    #[test]
+    fn vertical_wrap_columns() {
+        let sfont = CJK_FONT.as_scaled(20.0);
+        let advance = sfont.ascent() - sfont.descent();
+        let column_w = sfont.height() + sfont.line_gap();
+
+        let glyphs = Layout::default_vertical().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry {
+                bounds: (f32::INFINITY, advance * 3.5),
+                ..<_>::default()
+            },
+            &[SectionText {
+                text: "提高代碼執行率",
+                scale: PxScale::from(20.0),
+                font_id: FontId(1),
+                ..<_>::default()
+            }],
+        );
+        assert_eq!(glyphs.len(), 7);
+
+        let pos: Vec<_> = glyphs.iter().map(|sg| sg.glyph.position).collect();
+
+        // characters progress downwards, 3 per column
+        assert_relative_eq!(pos[0].y, sfont.ascent());
+        assert_relative_eq!(pos[1].y - pos[0].y, advance);
+        assert_relative_eq!(pos[3].y, pos[0].y);
+        assert_relative_eq!(pos[6].y, pos[0].y);
+        assert_relative_eq!(pos[2].x, pos[0].x);
+
+        // columns progress leftwards from the render position
+        let h_advance = sfont.h_advance(glyphs[0].glyph.id);
+        assert_relative_eq!(pos[0].x + h_advance / 2.0, -column_w / 2.0, epsilon = 1e-3);
+        assert_relative_eq!(pos[0].x - pos[3].x, column_w, epsilon = 1e-3);
+        assert_relative_eq!(pos[3].x - pos[6].x, column_w, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn vertical_sideways_latin() {
+        let sfont = A_FONT.as_scaled(20.0);
+
+        let glyphs = Layout::default_vertical().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "ab提",
+                scale: PxScale::from(20.0),
+                ..<_>::default()
+            }],
+        );
+        assert_glyph_order!(glyphs, "ab提");
+        let orientations: Vec<_> = glyphs.iter().map(|sg| sg.orientation).collect();
+        assert_eq!(
+            orientations,
+            [
+                VerticalOrientation::Sideways,
+                VerticalOrientation::Sideways,
+                VerticalOrientation::Upright,
+            ]
+        );
+
+        // sideways baseline is centered in the column
+        let column_w = sfont.height() + sfont.line_gap();
+        let baseline_x = -column_w / 2.0 - (sfont.ascent() + sfont.descent()) / 2.0;
+        assert_relative_eq!(glyphs[0].glyph.position.x, baseline_x, epsilon = 1e-3);
+        assert_relative_eq!(glyphs[1].glyph.position.x, baseline_x, epsilon = 1e-3);
+
+        // advancing by the horizontal advance
+        assert_relative_eq!(glyphs[0].glyph.position.y, 0.0);
+        let a_advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        assert_relative_eq!(glyphs[1].glyph.position.y, a_advance);
+
+        // followed by upright characters, without vertical metrics advancing by ascent to descent
+        let upright = &glyphs[2].glyph;
+        assert_relative_eq!(upright.position.y, a_advance * 2.0 + sfont.ascent());
+    }
+
+    #[test]
+    fn vertical_hard_breaks_width_bound() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let column_w = sfont.height() + sfont.line_gap();
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 50.0),
+            bounds: (column_w * 1.5, f32::INFINITY),
+        };
+
+        let glyphs = Layout::default_vertical()
+            .v_align(VerticalAlign::Bottom)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &geometry,
+                &[SectionText {
+                    text: "ab\ncde\nfg",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            );
+
+        // 3rd column starts outside the width bound
+        assert_glyph_order!(glyphs, "abcde");
+
+        // columns are bottom aligned
+        let a_advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        assert_relative_eq!(glyphs[0].glyph.position.y, 50.0 - a_advance * 2.0);
+        assert_relative_eq!(glyphs[2].glyph.position.y, 50.0 - a_advance * 3.0);
+        assert!(glyphs[2].glyph.position.x < glyphs[0].glyph.position.x);
+
+        let bounds = Layout::default_vertical().bounds_rect(&geometry);
+        assert_relative_eq!(bounds.max.x, 100.0);
+        assert_relative_eq!(bounds.min.x, (100.0 - column_w * 1.5).floor());
+        assert_relative_eq!(bounds.min.y, 50.0);
+    }
 }
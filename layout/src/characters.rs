@@ -1,7 +1,8 @@
 use crate::{
+    bidi::{self, Bidi},
     linebreak::{EolLineBreak, LineBreak, LineBreaker},
-    words::Words,
-    FontId, SectionText,
+    words::{WhiteSpace, Words},
+    FallbackResolver, FontFallbacks, FontId, LineHeight, Placeholder, SectionGlyph, SectionText,
 };
 use ab_glyph::*;
 use std::{
@@ -20,10 +21,27 @@ pub(crate) struct Character<'b, F: Font> {
     pub control: bool,
     /// Equivalent to `char::is_whitespace()`.
     pub whitespace: bool,
+    /// Character is a tab `'\t'`.
+    pub tab: bool,
+    /// Character is a space or tab, collapsible by a `WhiteSpace` mode.
+    pub space: bool,
+    /// Character is a newline `'\n'` or carriage return `'\r'`.
+    pub newline: bool,
     /// Index of the `SectionText` this character is from.
     pub section_index: usize,
     /// Position of the char within the `SectionText` text.
     pub byte_index: usize,
+    /// Shaped advance width, including any kerning, replacing the font's advance.
+    ///
+    /// Shaped glyphs are also positioned relative to the caret by their `glyph.position`.
+    pub advance: Option<f32>,
+    /// Extra advance after this character from letter & word spacing.
+    pub spacing: f32,
+    pub line_height: LineHeight,
+    /// Inline object this character lays out, replacing the section text.
+    pub placeholder: Option<Placeholder>,
+    /// Pixel distance the character is raised above the baseline, negative if lowered.
+    pub baseline_shift: f32,
 }
 
 /// `Character` iterator
@@ -36,15 +54,18 @@ where
     fonts: &'b [F],
     section_text: Enumerate<S>,
     line_breaker: L,
-    part_info: Option<PartInfo<'a>>,
+    bidi: Option<&'b Bidi>,
+    fallbacks: &'b FontFallbacks,
+    part_info: Option<PartInfo<'a, 'b>>,
 }
 
-struct PartInfo<'a> {
+struct PartInfo<'a, 'b> {
     section_index: usize,
     section: SectionText<'a>,
     info_chars: CharIndices<'a>,
     line_breaks: Box<dyn Iterator<Item = LineBreak> + 'a>,
     next_break: Option<LineBreak>,
+    fallback: FallbackResolver<'b>,
 }
 
 impl<'a, 'b, L, F, S> Characters<'a, 'b, L, F, S>
@@ -54,20 +75,28 @@ where
     S: Iterator<Item = SectionText<'a>>,
 {
     /// Returns a new `Characters` iterator.
-    pub(crate) fn new(fonts: &'b [F], section_text: S, line_breaker: L) -> Self {
+    ///
+    /// `bidi` levels are used to mirror characters in right-to-left runs.
+    pub(crate) fn new(
+        fonts: &'b [F],
+        section_text: S,
+        line_breaker: L,
+        bidi: Option<&'b Bidi>,
+        fallbacks: &'b FontFallbacks,
+    ) -> Self {
         Self {
             fonts,
             section_text: section_text.enumerate(),
             line_breaker,
+            bidi,
+            fallbacks,
             part_info: None,
         }
     }
 
-    /// Wraps into a `Words` iterator.
-    pub(crate) fn words(self) -> Words<'a, 'b, L, F, S> {
-        Words {
-            characters: self.peekable(),
-        }
+    /// Wraps into a `Words` iterator laying out spaces according to `white_space`.
+    pub(crate) fn words(self, white_space: WhiteSpace) -> Words<'b, F, Self> {
+        Words::new(self, white_space)
     }
 }
 
@@ -90,6 +119,14 @@ where
                 }
             }
             let (section_index, section) = index_and_section;
+            if let Some(placeholder) = section.placeholder {
+                return Some(placeholder_character(
+                    self.fonts,
+                    section_index,
+                    &section,
+                    placeholder,
+                ));
+            }
             let line_breaks = self.line_breaker.line_breaks(section.text);
             self.part_info = Some(PartInfo {
                 section_index,
@@ -97,21 +134,18 @@ where
                 info_chars: index_and_section.1.text.char_indices(),
                 line_breaks,
                 next_break: None,
+                fallback: FallbackResolver::new(self.fallbacks, section.font_id),
             });
         }
 
         {
             let PartInfo {
                 section_index,
-                section:
-                    SectionText {
-                        scale,
-                        font_id,
-                        text,
-                    },
+                section,
                 info_chars,
                 line_breaks,
                 next_break,
+                fallback,
             } = self.part_info.as_mut().unwrap();
 
             if let Some((byte_index, c)) = info_chars.next() {
@@ -125,13 +159,26 @@ where
                     }
                 }
 
-                let scale_font: PxScaleFont<&'b F> = self.fonts[*font_id].as_scaled(*scale);
+                let font_id = fallback.font_id(self.fonts, section.text, byte_index);
+                let scale_font: PxScaleFont<&'b F> = self.fonts[font_id].as_scaled(section.scale);
+
+                let mut glyph = scale_font.scaled_glyph(c);
 
-                let glyph = scale_font.scaled_glyph(c);
+                // Rule L4: mirror characters, like brackets, in right-to-left runs
+                if let Some(mirror) = self
+                    .bidi
+                    .filter(|bidi| bidi.is_rtl(*section_index, byte_index))
+                    .and_then(|_| bidi::mirrored(c))
+                {
+                    let mirror_id = scale_font.glyph_id(mirror);
+                    if mirror_id.0 != 0 {
+                        glyph.id = mirror_id;
+                    }
+                }
 
                 let c_len = c.len_utf8();
                 let mut line_break = next_break.filter(|b| b.offset() == byte_index + c_len);
-                if line_break.is_some() && byte_index + c_len == text.len() {
+                if line_break.is_some() && byte_index + c_len == section.text.len() {
                     // handle inherent end-of-str breaks
                     line_break = line_break.and(c.eol_line_break(&self.line_breaker));
                 }
@@ -139,13 +186,21 @@ where
                 return Some(Character {
                     glyph,
                     scale_font,
-                    font_id: *font_id,
+                    font_id,
                     line_break,
                     control: c.is_control(),
                     whitespace: c.is_whitespace(),
+                    tab: c == '\t',
+                    space: matches!(c, ' ' | '\t'),
+                    newline: matches!(c, '\n' | '\r'),
 
                     section_index: *section_index,
                     byte_index,
+                    advance: None,
+                    spacing: spacing(section, c),
+                    line_height: section.line_height,
+                    placeholder: None,
+                    baseline_shift: section.baseline_shift.px(&scale_font),
                 });
             }
         }
@@ -163,8 +218,46 @@ where
 {
 }
 
+/// Returns the extra advance after a character from the section's letter & word spacing.
+#[inline]
+pub(crate) fn spacing(section: &SectionText<'_>, c: char) -> f32 {
+    match c {
+        ' ' | '\u{a0}' => section.letter_spacing + section.word_spacing,
+        _ => section.letter_spacing,
+    }
+}
+
+/// Returns the single character laying out a `placeholder` section, breakable after.
+#[inline]
+pub(crate) fn placeholder_character<'b, F: Font>(
+    fonts: &'b [F],
+    section_index: usize,
+    section: &SectionText<'_>,
+    placeholder: Placeholder,
+) -> Character<'b, F> {
+    let scale_font = fonts[section.font_id].as_scaled(section.scale);
+    Character {
+        glyph: SectionGlyph::PLACEHOLDER_ID.with_scale(section.scale),
+        scale_font,
+        font_id: section.font_id,
+        line_break: Some(LineBreak::Soft(section.text.len())),
+        control: false,
+        whitespace: false,
+        tab: false,
+        space: false,
+        newline: false,
+        section_index,
+        byte_index: 0,
+        advance: Some(placeholder.width),
+        spacing: 0.0,
+        line_height: section.line_height,
+        placeholder: Some(placeholder),
+        baseline_shift: section.baseline_shift.px(&scale_font),
+    }
+}
+
 #[inline]
-fn valid_section(s: &SectionText<'_>) -> bool {
+pub(crate) fn valid_section(s: &SectionText<'_>) -> bool {
     let PxScale { x, y } = s.scale;
     x > 0.0 && y > 0.0
 }
@@ -0,0 +1,200 @@
+use ab_glyph::{Point, Rect};
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// Area that text of wrapping layouts flows around, e.g. an icon or portrait.
+///
+/// Positions are relative to the left of the width bound box & the top of the text, i.e.
+/// the render position of left & top aligned layouts. Positions are compared & hashed by
+/// their bits, so `0.0` & `-0.0` differ.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::{ab_glyph::*, *};
+/// # let icon_size = 64.0;
+/// let icon = Exclusion::Rect(Rect {
+///     min: point(0.0, 0.0),
+///     max: point(icon_size, icon_size),
+/// });
+///
+/// let layout = Layout::default().exclusions([icon]);
+/// ```
+#[derive(Debug, Clone)]
+pub enum Exclusion {
+    Rect(Rect),
+    /// Polygon of vertices in order. The whole horizontal extent of the polygon within
+    /// a line is excluded, so concave parts are not filled.
+    Polygon(Arc<[Point]>),
+}
+
+impl Exclusion {
+    /// Returns the horizontal extent of the exclusion between `top` & `bottom`, if any.
+    ///
+    /// Exclusions that are not finite are ignored.
+    pub(crate) fn x_range(&self, top: f32, bottom: f32) -> Option<(f32, f32)> {
+        match *self {
+            Exclusion::Rect(Rect { min, max }) => Some((min.x, max.x))
+                .filter(|_| min.y < bottom && max.y > top)
+                .filter(|(min_x, max_x)| min_x < max_x && is_finite(min) && is_finite(max)),
+            Exclusion::Polygon(ref points) => {
+                let (min_y, max_y) = points
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), p| {
+                        (min.min(p.y), max.max(p.y))
+                    });
+                if min_y >= bottom || max_y <= top || !points.iter().copied().all(is_finite) {
+                    return None;
+                }
+                let mut range: Option<(f32, f32)> = None;
+                let mut include = |x: f32| {
+                    let (min, max) = range.get_or_insert((x, x));
+                    *min = min.min(x);
+                    *max = max.max(x);
+                };
+                for (idx, a) in points.iter().enumerate() {
+                    let b = points[(idx + 1) % points.len()];
+                    let (upper, lower) = match a.y <= b.y {
+                        true => (*a, b),
+                        false => (b, *a),
+                    };
+                    // clip the edge to the band
+                    let (y0, y1) = (upper.y.max(top), lower.y.min(bottom));
+                    if y0 > y1 {
+                        continue;
+                    }
+                    match lower.y - upper.y {
+                        dy if dy > 0.0 => {
+                            let x_at = |y: f32| upper.x + (lower.x - upper.x) * (y - upper.y) / dy;
+                            include(x_at(y0));
+                            include(x_at(y1));
+                        }
+                        // horizontal edge
+                        _ => {
+                            include(upper.x);
+                            include(lower.x);
+                        }
+                    }
+                }
+                range.filter(|(min, max)| min < max)
+            }
+        }
+    }
+}
+
+#[inline]
+fn is_finite(p: Point) -> bool {
+    p.x.is_finite() && p.y.is_finite()
+}
+
+impl PartialEq for Exclusion {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let bits = |p: &Point| (p.x.to_bits(), p.y.to_bits());
+        match (self, other) {
+            (Exclusion::Rect(a), Exclusion::Rect(b)) => {
+                bits(&a.min) == bits(&b.min) && bits(&a.max) == bits(&b.max)
+            }
+            (Exclusion::Polygon(a), Exclusion::Polygon(b)) => {
+                a.iter().map(bits).eq(b.iter().map(bits))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Exclusion {}
+
+impl Hash for Exclusion {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            Exclusion::Rect(Rect { min, max }) => {
+                (0_u8, [min.x, min.y, max.x, max.y].map(f32::to_bits)).hash(state)
+            }
+            Exclusion::Polygon(ref points) => {
+                1_u8.hash(state);
+                for p in points.iter() {
+                    (p.x.to_bits(), p.y.to_bits()).hash(state);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the segments of `left..right` not excluded between `top` & `bottom`, in
+/// left-to-right order, or `None` if no exclusions are within the band.
+pub(crate) fn segments(
+    exclusions: &[Exclusion],
+    (left, right): (f32, f32),
+    (top, bottom): (f32, f32),
+) -> Option<Vec<(f32, f32)>> {
+    let mut excluded: Vec<_> = exclusions
+        .iter()
+        .filter_map(|e| e.x_range(top, bottom))
+        .filter(|(min, max)| *min < right && *max > left)
+        .collect();
+    if excluded.is_empty() {
+        return None;
+    }
+    excluded.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut segments = Vec::with_capacity(excluded.len() + 1);
+    let mut x = left;
+    for (min, max) in excluded {
+        if min > x {
+            segments.push((x, min));
+        }
+        x = x.max(max);
+    }
+    if right > x {
+        segments.push((x, right));
+    }
+    Some(segments)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ab_glyph::point;
+
+    #[test]
+    fn rect_segments() {
+        let exclusions = &[
+            Exclusion::Rect(Rect {
+                min: point(40.0, 0.0),
+                max: point(60.0, 20.0),
+            }),
+            Exclusion::Rect(Rect {
+                min: point(-10.0, 10.0),
+                max: point(20.0, 30.0),
+            }),
+        ];
+
+        assert_eq!(
+            segments(exclusions, (0.0, 100.0), (0.0, 10.0)),
+            Some(vec![(0.0, 40.0), (60.0, 100.0)])
+        );
+        assert_eq!(
+            segments(exclusions, (0.0, 100.0), (15.0, 25.0)),
+            Some(vec![(20.0, 40.0), (60.0, 100.0)])
+        );
+        assert_eq!(segments(exclusions, (0.0, 100.0), (30.0, 40.0)), None);
+    }
+
+    #[test]
+    fn polygon_x_range() {
+        static TRIANGLE: &[Point] = &[
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 100.0, y: 100.0 },
+            Point { x: 0.0, y: 100.0 },
+        ];
+        let triangle = Exclusion::Polygon(TRIANGLE.into());
+
+        assert_eq!(triangle.x_range(20.0, 40.0), Some((0.0, 40.0)));
+        assert_eq!(triangle.x_range(90.0, 150.0), Some((0.0, 100.0)));
+        assert_eq!(triangle.x_range(100.0, 150.0), None);
+        assert_eq!(triangle.x_range(-20.0, 0.0), None);
+    }
+}
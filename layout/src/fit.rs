@@ -0,0 +1,443 @@
+use crate::{
+    characters::valid_section, flow::within, words::Word, BaselineShift, FontFallbacks,
+    GlyphPositioner, Layout, LineBreaker, LineHeight, SectionGeometry, SectionGlyph, SectionLine,
+    SectionText, ToSectionText,
+};
+use ab_glyph::*;
+use std::hash::{Hash, Hasher};
+
+/// Pixel precision of fitted scales.
+const SCALE_PRECISION: f32 = 0.1;
+
+/// [`GlyphPositioner`](trait.GlyphPositioner.html) laying out text at the largest scale,
+/// between a min & max, at which it fits the section bounds, e.g. for button labels or
+/// localised titles.
+///
+/// Text fits when every line is within the width bound & all lines are within the height
+/// bound. [`SingleLine`](enum.Layout.html#variant.SingleLine) text fits on one line &
+/// [`Wrap`](enum.Layout.html#variant.Wrap) text within the overflow `max_lines`, if any.
+/// Text that doesn't fit at the min scale overflows as the inner layout, e.g. with an
+/// ellipsis.
+///
+/// Scales are those of the largest section text, other sections are scaled in proportion.
+/// Words are measured once, at the max scale, & scaled to try each smaller scale. Text with
+/// pixel sized spacing, line heights, baseline shifts or placeholders, that don't scale with
+/// the text, is instead measured at each scale tried.
+///
+/// Vertical layouts are not fitted, text is laid out at the max scale.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // a label of up to 2 lines between 12px & 32px
+/// let layout = FitLayout::new(Layout::default_wrap().max_lines(2), 12.0, 32.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FitLayout<L: LineBreaker> {
+    pub layout: Layout<L>,
+    /// Smallest scale text is laid out at.
+    pub min_scale: f32,
+    /// Largest scale text is laid out at.
+    pub max_scale: f32,
+}
+
+impl<L: LineBreaker + PartialEq> PartialEq for FitLayout<L> {
+    /// Scales are compared by their bits, consistent with `Hash`.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.layout == other.layout
+            && self.min_scale.to_bits() == other.min_scale.to_bits()
+            && self.max_scale.to_bits() == other.max_scale.to_bits()
+    }
+}
+
+impl<L: LineBreaker + Eq> Eq for FitLayout<L> {}
+
+impl<L: LineBreaker> Hash for FitLayout<L> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.layout.hash(state);
+        [self.min_scale, self.max_scale]
+            .map(f32::to_bits)
+            .hash(state);
+    }
+}
+
+impl<L: LineBreaker> FitLayout<L> {
+    /// Returns a `FitLayout` fitting text of the `layout` between the `min_scale` &
+    /// `max_scale`.
+    #[inline]
+    pub fn new(layout: Layout<L>, min_scale: f32, max_scale: f32) -> Self {
+        Self {
+            layout,
+            min_scale,
+            max_scale,
+        }
+    }
+
+    /// Calculate a sequence of positioned glyphs to render & the metrics of each laid out
+    /// line, as [`calculate_glyphs_and_lines`](trait.GlyphPositioner.html#method.calculate_glyphs_and_lines),
+    /// along with the scale the text was fitted to.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// # fn main() -> Result<(), InvalidFont> {
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// let layout = FitLayout::new(Layout::default_single_line(), 10.0, 40.0);
+    ///
+    /// let (_, _, scale) = layout.calculate_fit(
+    ///     &[font],
+    ///     &FontFallbacks::default(),
+    ///     &SectionGeometry {
+    ///         bounds: (100.0, 50.0),
+    ///         ..<_>::default()
+    ///     },
+    ///     &[SectionText {
+    ///         text: "Continue",
+    ///         ..<_>::default()
+    ///     }],
+    /// );
+    /// assert!(scale.y > 10.0 && scale.y < 40.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn calculate_fit<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>, PxScale)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let sections: Vec<_> = sections.iter().map(|s| s.to_section_text()).collect();
+        let Some(reference) = reference_scale(&sections) else {
+            let (glyphs, lines) = self
+                .layout
+                .calculate_glyphs_and_lines(fonts, fallbacks, geometry, &sections);
+            return (glyphs, lines, PxScale::from(self.max_scale));
+        };
+
+        let max_factor = self.max_scale.max(0.0) / reference.y;
+        let min_factor = self.min_scale.clamp(0.0, self.max_scale.max(0.0)) / reference.y;
+        let scaled_sections = |factor: f32| -> Vec<SectionText<'_>> {
+            sections
+                .iter()
+                .map(|s| SectionText {
+                    scale: scaled(s.scale, factor),
+                    ..*s
+                })
+                .collect()
+        };
+
+        let Some(measured) =
+            self.layout
+                .measure_words(fonts, fallbacks, &scaled_sections(max_factor))
+        else {
+            // vertical layouts are not fitted
+            let (glyphs, lines) = self.layout.calculate_glyphs_and_lines(
+                fonts,
+                fallbacks,
+                geometry,
+                &scaled_sections(max_factor),
+            );
+            return (glyphs, lines, scaled(reference, max_factor));
+        };
+
+        let linear = sections.iter().all(|s| {
+            s.letter_spacing == 0.0
+                && s.word_spacing == 0.0
+                && s.placeholder.is_none()
+                && !matches!(s.line_height, LineHeight::Px(_))
+                && !matches!(s.baseline_shift, BaselineShift::Px(_))
+        });
+        let words_at = |factor: f32| -> Vec<Word> {
+            match linear {
+                true => {
+                    let factor = factor / max_factor;
+                    measured.words.iter().map(|w| w.scaled(factor)).collect()
+                }
+                false => self
+                    .layout
+                    .measure_words(fonts, fallbacks, &scaled_sections(factor))
+                    .map(|m| m.words)
+                    .unwrap_or_default(),
+            }
+        };
+        let texts: Vec<_> = sections.iter().map(|s| s.text).collect();
+
+        let mut factor = max_factor;
+        let mut words = measured.words.clone();
+        if !self.fits(&words, geometry.bounds, &texts) {
+            // binary search the largest fitting scale, or the min scale if none fit
+            let (mut low, mut high) = (min_factor, max_factor);
+            words = words_at(low);
+            while (high - low) * reference.y > SCALE_PRECISION {
+                let mid = (low + high) / 2.0;
+                let mid_words = words_at(mid);
+                match self.fits(&mid_words, geometry.bounds, &texts) {
+                    true => {
+                        low = mid;
+                        words = mid_words;
+                    }
+                    false => high = mid,
+                }
+            }
+            factor = low;
+        }
+
+        let (glyphs, lines) = self.layout.layout_words(
+            fonts,
+            geometry,
+            words.into_iter(),
+            &texts,
+            measured.bidi.as_ref(),
+        );
+        (glyphs, lines, scaled(reference, factor))
+    }
+
+    /// Returns the scale the text of `sections` was fitted to, from its laid out `glyphs`,
+    /// as [`calculate_fit`](#method.calculate_fit).
+    ///
+    /// Useful when only the glyphs are available, e.g. as cached by glyph_brush.
+    pub fn fitted_scale<'g, S, G>(&self, sections: &[S], glyphs: G) -> PxScale
+    where
+        S: ToSectionText,
+        G: IntoIterator<Item = &'g SectionGlyph>,
+    {
+        let sections: Vec<_> = sections.iter().map(|s| s.to_section_text()).collect();
+        let Some(reference) = reference_scale(&sections) else {
+            return PxScale::from(self.max_scale);
+        };
+        let factor = glyphs
+            .into_iter()
+            .next()
+            .map(|sg| sg.glyph.scale.y / sections[sg.section_index].scale.y)
+            .unwrap_or(self.max_scale.max(0.0) / reference.y);
+        scaled(reference, factor)
+    }
+
+    /// Returns `true` if `words` laid out within the `bounds` fit.
+    fn fits(&self, words: &[Word], (bound_w, bound_h): (f32, f32), texts: &[&str]) -> bool {
+        let max_lines = match self.layout {
+            Layout::SingleLine { .. } => 1,
+            _ => self.layout.get_overflow().max_lines.unwrap_or(usize::MAX),
+        };
+
+        let mut lines = self
+            .layout
+            .fill_lines(words.iter().cloned(), bound_w, texts);
+        let mut count = 0;
+        lines.all(|line| {
+            count += 1;
+            let width = line.segment.map_or(bound_w, |(left, right)| right - left);
+            count <= max_lines
+                && within(line.rightmost + line.inset_width(), width)
+                && within(line.top + line.line_height(), bound_h)
+        })
+    }
+}
+
+impl<L: LineBreaker> GlyphPositioner for FitLayout<L> {
+    #[inline]
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_glyphs_with_fallbacks(fonts, &FontFallbacks::default(), geometry, sections)
+    }
+
+    #[inline]
+    fn calculate_glyphs_with_fallbacks<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_fit(fonts, fallbacks, geometry, sections).0
+    }
+
+    #[inline]
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let (glyphs, lines, _) = self.calculate_fit(fonts, fallbacks, geometry, sections);
+        (glyphs, lines)
+    }
+
+    #[inline]
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        self.layout.bounds_rect(geometry)
+    }
+}
+
+/// Returns the scale of the largest valid section.
+#[inline]
+fn reference_scale(sections: &[SectionText<'_>]) -> Option<PxScale> {
+    sections
+        .iter()
+        .filter(|s| valid_section(s))
+        .map(|s| s.scale)
+        .max_by(|a, b| a.y.total_cmp(&b.y))
+}
+
+#[inline]
+fn scaled(scale: PxScale, factor: f32) -> PxScale {
+    PxScale {
+        x: scale.x * factor,
+        y: scale.y * factor,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::BuiltInLineBreaker;
+    use approx::assert_relative_eq;
+    use std::sync::LazyLock;
+
+    static A_FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap()
+    });
+
+    /// Returns the width of `chars` monospace characters at `scale`.
+    fn mono_width(chars: usize, scale: f32) -> f32 {
+        let sfont = A_FONT.as_scaled(scale);
+        chars as f32 * sfont.h_advance(sfont.glyph_id('a'))
+    }
+
+    fn fit(
+        layout: FitLayout<BuiltInLineBreaker>,
+        bounds: (f32, f32),
+        sections: &[SectionText<'_>],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>, PxScale) {
+        let geometry = SectionGeometry {
+            bounds,
+            ..<_>::default()
+        };
+        layout.calculate_fit(&[&*A_FONT], &FontFallbacks::default(), &geometry, sections)
+    }
+
+    #[test]
+    fn single_line_fits_width() {
+        let sections = &[SectionText {
+            text: "Continue",
+            scale: PxScale::from(12.0),
+            ..<_>::default()
+        }];
+        let layout = FitLayout::new(Layout::default_single_line(), 10.0, 40.0);
+
+        let (glyphs, lines, scale) = fit(
+            layout.clone(),
+            (mono_width(8, 30.0), f32::INFINITY),
+            sections,
+        );
+        assert!(
+            scale.y <= 30.0 && scale.y > 30.0 - SCALE_PRECISION,
+            "{scale:?}"
+        );
+        assert_eq!(lines.len(), 1);
+        assert_eq!(glyphs.len(), 8);
+        assert!(glyphs.iter().all(|sg| sg.glyph.scale == scale));
+        assert_eq!(layout.fitted_scale(sections, &glyphs), scale);
+
+        // fits at the max scale
+        let (_, _, scale) = fit(layout.clone(), (500.0, f32::INFINITY), sections);
+        assert_eq!(scale, PxScale::from(40.0));
+
+        // doesn't fit at the min scale
+        let (glyphs, _, scale) = fit(
+            layout.clone(),
+            (mono_width(8, 5.0), f32::INFINITY),
+            sections,
+        );
+        assert_eq!(scale, PxScale::from(10.0));
+        assert_eq!(layout.fitted_scale(sections, &glyphs), scale);
+    }
+
+    #[test]
+    fn wrap_fits_lines() {
+        let sections = &[SectionText {
+            text: "aaaa bbbb",
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let bound_w = mono_width(9, 20.0);
+
+        // a single line
+        let layout = FitLayout::new(Layout::default_wrap().max_lines(1), 10.0, 40.0);
+        let (_, lines, scale) = fit(layout, (bound_w, f32::INFINITY), sections);
+        assert_eq!(lines.len(), 1);
+        assert!(
+            scale.y <= 20.0 && scale.y > 20.0 - SCALE_PRECISION,
+            "{scale:?}"
+        );
+
+        // 2 lines fit at the max scale
+        let layout = FitLayout::new(Layout::default_wrap().max_lines(2), 10.0, 40.0);
+        let (_, lines, scale) = fit(layout, (bound_w, f32::INFINITY), sections);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(scale, PxScale::from(40.0));
+
+        // 2 lines limited by the height
+        let sfont = A_FONT.as_scaled(30.0);
+        let bound_h = 2.0 * (sfont.height() + sfont.line_gap());
+        let layout = FitLayout::new(Layout::default_wrap(), 10.0, 40.0);
+        let (_, lines, scale) = fit(layout, (bound_w, bound_h), sections);
+        assert_eq!(lines.len(), 2);
+        assert!(
+            scale.y <= 30.0 && scale.y > 30.0 - SCALE_PRECISION,
+            "{scale:?}"
+        );
+    }
+
+    #[test]
+    fn fit_scales_sections_in_proportion() {
+        let sections = &[
+            SectionText {
+                text: "aaaa ",
+                scale: PxScale::from(20.0),
+                ..<_>::default()
+            },
+            SectionText {
+                text: "bbbb",
+                scale: PxScale::from(10.0),
+                letter_spacing: 2.0,
+                ..<_>::default()
+            },
+        ];
+        let bound_w = mono_width(5, 30.0) + mono_width(4, 15.0) + 4.0 * 2.0;
+        let layout = FitLayout::new(Layout::default_single_line(), 10.0, 40.0);
+
+        // pixel letter spacing doesn't scale, so is measured at each scale
+        let (glyphs, _, scale) = fit(layout, (bound_w, f32::INFINITY), sections);
+        assert!(
+            scale.y <= 30.0 && scale.y > 30.0 - SCALE_PRECISION,
+            "{scale:?}"
+        );
+        assert_relative_eq!(glyphs[0].glyph.scale.y, scale.y);
+        assert_relative_eq!(glyphs[5].glyph.scale.y, scale.y / 2.0);
+    }
+}
@@ -0,0 +1,803 @@
+use crate::{
+    bidi::Bidi,
+    characters::Characters,
+    linebreak::WordBreaker,
+    lines::{Line, Lines},
+    words::{WhiteSpace, Word},
+    BuiltInLineBreaker, FontFallbacks, GlyphPositioner, HorizontalAlign, LayoutOptions,
+    LineBreaker, SectionGeometry, SectionGlyph, SectionLine, ToSectionText, VerticalAlign,
+};
+use ab_glyph::*;
+use std::{
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+    ops::Range,
+    sync::Arc,
+};
+
+/// [`GlyphPositioner`](trait.GlyphPositioner.html) flowing wrapped text through a sequence
+/// of [`Frames`](enum.Frames.html), e.g. the columns of a page or a sequence of pages.
+///
+/// Lines fill each frame from the top before continuing in the next. Text not fitting in
+/// the last frame is not laid out, [`calculate_flow`](#method.calculate_flow) reports where
+/// each frame ended so following pages may continue from there.
+///
+/// Breaking a paragraph across frames keeps at least `orphans` of its lines at the bottom
+/// of the frame & `widows` at the top of the next, where possible, by moving lines to the
+/// next frame. Moved lines keep the wrapping of the frame they were first fitted to.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // 2 columns with a 20px gap, balanced to similar heights
+/// let layout = FlowLayout::columns(2, 20.0).balanced();
+/// ```
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct FlowLayout<L: LineBreaker> {
+    pub frames: Frames,
+    pub line_breaker: L,
+    /// Alignment of lines within their frame.
+    pub h_align: HorizontalAlign,
+    /// Min lines of a paragraph left at the bottom of a frame it continues from.
+    /// Defaults to 2.
+    pub orphans: usize,
+    /// Min lines of a paragraph carried to the top of the frame it continues in.
+    /// Defaults to 2.
+    pub widows: usize,
+    /// Tab stops, indents, word breaking, overflow wrapping & white space handling of the
+    /// text. The overflow, exclusions & break strategy are ignored, lines are filled
+    /// greedily within each frame.
+    pub options: LayoutOptions,
+}
+
+/// Frames that [`FlowLayout`](struct.FlowLayout.html) text flows through, in order.
+///
+/// Gaps & rects are compared & hashed by their bits, so `0.0` & `-0.0` differ.
+#[derive(Debug, Clone)]
+pub enum Frames {
+    /// `count` columns of equal width filling the width & height bounds from the render
+    /// position, separated by `gap` pixels. Without a finite width bound text is laid out
+    /// in a single column.
+    ///
+    /// `balanced` columns are filled to similar heights, using as little of the height
+    /// bound as needed, rather than filling each in turn.
+    Columns {
+        count: usize,
+        gap: f32,
+        balanced: bool,
+    },
+    /// Frames, e.g. pages, positioned relative to the render position.
+    Rects(Arc<[Rect]>),
+}
+
+impl PartialEq for Frames {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let bits = |r: &Rect| [r.min.x, r.min.y, r.max.x, r.max.y].map(f32::to_bits);
+        match (self, other) {
+            (
+                Frames::Columns {
+                    count,
+                    gap,
+                    balanced,
+                },
+                Frames::Columns {
+                    count: count2,
+                    gap: gap2,
+                    balanced: balanced2,
+                },
+            ) => count == count2 && gap.to_bits() == gap2.to_bits() && balanced == balanced2,
+            (Frames::Rects(a), Frames::Rects(b)) => a.iter().map(bits).eq(b.iter().map(bits)),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Frames {}
+
+impl Hash for Frames {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            Frames::Columns {
+                count,
+                gap,
+                balanced,
+            } => (0_u8, count, gap.to_bits(), balanced).hash(state),
+            Frames::Rects(ref rects) => {
+                1_u8.hash(state);
+                for Rect { min, max } in rects.iter() {
+                    [min.x, min.y, max.x, max.y].map(f32::to_bits).hash(state);
+                }
+            }
+        }
+    }
+}
+
+/// A frame of text laid out by a [`FlowLayout`](struct.FlowLayout.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowFrame {
+    /// Screen rectangle of the frame. The height of balanced columns is that used.
+    pub rect: Rect,
+    /// The range of the frame's lines in the laid out [`SectionLine`]s.
+    pub lines: Range<usize>,
+    /// The `(section_index, byte_index)` following the last character laid out in the frame,
+    /// or preceding frames if empty. Following text continues from here in the next frame.
+    pub end: (usize, usize),
+}
+
+impl FlowLayout<BuiltInLineBreaker> {
+    /// Returns a layout of `count` columns separated by `gap` pixels.
+    #[inline]
+    pub fn columns(count: usize, gap: f32) -> Self {
+        Self::new(Frames::Columns {
+            count,
+            gap,
+            balanced: false,
+        })
+    }
+
+    /// Returns a layout through the `rects`, positioned relative to the render position.
+    #[inline]
+    pub fn rects(rects: impl Into<Arc<[Rect]>>) -> Self {
+        Self::new(Frames::Rects(rects.into()))
+    }
+
+    #[inline]
+    fn new(frames: Frames) -> Self {
+        Self {
+            frames,
+            line_breaker: BuiltInLineBreaker::default(),
+            h_align: HorizontalAlign::Left,
+            orphans: 2,
+            widows: 2,
+            options: LayoutOptions::default(),
+        }
+    }
+}
+
+impl<L: LineBreaker> FlowLayout<L> {
+    /// Returns an identical `FlowLayout` but with balanced columns. Has no effect on
+    /// [`Frames::Rects`](enum.Frames.html#variant.Rects).
+    pub fn balanced(mut self) -> Self {
+        if let Frames::Columns { balanced, .. } = &mut self.frames {
+            *balanced = true;
+        }
+        self
+    }
+
+    /// Returns an identical `FlowLayout` but with the input `h_align`
+    pub fn h_align(mut self, h_align: HorizontalAlign) -> Self {
+        self.h_align = h_align;
+        self
+    }
+
+    /// Returns an identical `FlowLayout` but with the input `orphans`
+    pub fn orphans(mut self, orphans: usize) -> Self {
+        self.orphans = orphans;
+        self
+    }
+
+    /// Returns an identical `FlowLayout` but with the input `widows`
+    pub fn widows(mut self, widows: usize) -> Self {
+        self.widows = widows;
+        self
+    }
+
+    /// Returns an identical `FlowLayout` but with the input `options`, see
+    /// [`FlowLayout::options`](#structfield.options) for those used.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// let options = LayoutOptions::default().with_tab_stops(TabStops::default());
+    /// let layout = FlowLayout::columns(2, 20.0).options(options);
+    /// ```
+    pub fn options(mut self, options: LayoutOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns an identical `FlowLayout` but with the input `line_breaker`
+    pub fn line_breaker<L2: LineBreaker>(self, line_breaker: L2) -> FlowLayout<L2> {
+        FlowLayout {
+            frames: self.frames,
+            line_breaker,
+            h_align: self.h_align,
+            orphans: self.orphans,
+            widows: self.widows,
+            options: self.options,
+        }
+    }
+
+    /// Returns the screen rectangles of the frames.
+    fn frame_rects(&self, geometry: &SectionGeometry) -> Vec<Rect> {
+        let SectionGeometry {
+            screen_position: (screen_x, screen_y),
+            bounds: (bound_w, bound_h),
+        } = *geometry;
+
+        match self.frames {
+            Frames::Columns { count, gap, .. } => {
+                let count = match bound_w.is_finite() {
+                    true => count.max(1),
+                    false => 1,
+                };
+                let width = ((bound_w - gap * (count - 1) as f32) / count as f32).max(0.0);
+                (0..count)
+                    .map(|idx| {
+                        let left = screen_x + idx as f32 * (width + gap);
+                        Rect {
+                            min: point(left, screen_y),
+                            max: point(left + width, screen_y + bound_h),
+                        }
+                    })
+                    .collect()
+            }
+            Frames::Rects(ref rects) => rects
+                .iter()
+                .map(|Rect { min, max }| Rect {
+                    min: point(min.x + screen_x, min.y + screen_y),
+                    max: point(max.x + screen_x, max.y + screen_y),
+                })
+                .collect(),
+        }
+    }
+
+    /// Calculate a sequence of positioned glyphs to render & the metrics of each laid out
+    /// line, as [`calculate_glyphs_and_lines`](trait.GlyphPositioner.html#method.calculate_glyphs_and_lines),
+    /// along with the frames the lines were laid out in.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// # fn main() -> Result<(), InvalidFont> {
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// let page = Rect {
+    ///     min: point(0.0, 0.0),
+    ///     max: point(200.0, 20.0),
+    /// };
+    /// let text = "A long report that doesn't fit on a single page of text";
+    ///
+    /// let (_, _, frames) = FlowLayout::rects([page]).calculate_flow(
+    ///     &[font],
+    ///     &FontFallbacks::default(),
+    ///     &SectionGeometry::default(),
+    ///     &[SectionText {
+    ///         text,
+    ///         ..<_>::default()
+    ///     }],
+    /// );
+    /// // the next page continues from the end of the last
+    /// let (_, next_page_start) = frames.last().unwrap().end;
+    /// assert!(next_page_start < text.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn calculate_flow<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>, Vec<FlowFrame>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let mut rects = self.frame_rects(geometry);
+        let LayoutOptions {
+            ref tab_stops,
+            indents,
+            word_break,
+            overflow_wrap,
+            white_space,
+            ..
+        } = self.options;
+        // preformatted lines don't wrap
+        let width_bound = |rect: &Rect| match white_space {
+            WhiteSpace::Pre => f32::INFINITY,
+            _ => rect.width(),
+        };
+
+        let bidi = Bidi::new(sections.iter().map(|s| s.to_section_text()));
+        let words = Characters::new(
+            fonts,
+            sections.iter().map(|s| s.to_section_text()),
+            WordBreaker {
+                line_breaker: self.line_breaker.clone(),
+                word_break,
+            },
+            bidi.as_ref(),
+            fallbacks,
+        )
+        .words(white_space);
+        let texts: Vec<_> = sections.iter().map(|s| s.to_section_text().text).collect();
+
+        let first_width = rects.first().map_or(0.0, width_bound);
+        let mut lines = Lines::new(
+            words,
+            first_width,
+            tab_stops.clone(),
+            indents,
+            Arc::default(),
+            &texts,
+        );
+        lines.set_overflow_wrap(overflow_wrap);
+        // lines wrapped but not yet laid out in a frame
+        let mut pending: VecDeque<Line> = VecDeque::new();
+
+        if let Frames::Columns { balanced: true, .. } = self.frames {
+            // columns share a width, so all lines can be wrapped up front
+            pending.extend(&mut lines);
+            let metrics: Vec<_> = pending.iter().map(metrics).collect();
+            let height = self.balanced_height(&metrics, rects.len(), geometry.bounds.1);
+            for rect in &mut rects {
+                rect.max.y = rect.min.y + height;
+            }
+        }
+
+        let mut out = vec![];
+        let mut section_lines: Vec<SectionLine> = vec![];
+        let mut frames = Vec::with_capacity(rects.len());
+        let mut end = (0, 0);
+
+        for rect in rects {
+            let height = rect.height();
+            lines.set_width_bound(width_bound(&rect));
+            self.fill_pending(&mut pending, &mut lines, height);
+
+            let metrics: Vec<_> = pending.iter().map(metrics).collect();
+            let len = frame_len(&metrics, height, self.orphans, self.widows);
+
+            let first_line = section_lines.len();
+            let mut top = rect.min.y;
+            for line in pending.drain(..len) {
+                end = line.end;
+                let line_height = line.line_height();
+                let box_w = match rect.width().is_finite() {
+                    true => rect.width(),
+                    false => line.rightmost,
+                };
+                let caret_x = rect.min.x
+                    + match self.h_align {
+                        HorizontalAlign::Center => box_w / 2.0,
+                        HorizontalAlign::Right => box_w,
+                        _ => 0.0,
+                    };
+                let (glyphs, mut section_line) = line.aligned_on_screen(
+                    fonts,
+                    (caret_x, top),
+                    self.h_align,
+                    VerticalAlign::Top,
+                    box_w,
+                    bidi.as_ref(),
+                );
+                section_line.index = section_lines.len();
+                section_line.glyphs = out.len()..out.len() + glyphs.len();
+                out.extend(glyphs);
+                section_lines.push(section_line);
+                top += line_height;
+            }
+
+            frames.push(FlowFrame {
+                rect,
+                lines: first_line..section_lines.len(),
+                end,
+            });
+        }
+
+        (out, section_lines, frames)
+    }
+
+    /// Wraps lines into `pending` until they overflow the frame `height` & the lines of the
+    /// overflowing paragraph needed to keep `widows` are known.
+    fn fill_pending<W>(&self, pending: &mut VecDeque<Line>, lines: &mut Lines<'_, W>, height: f32)
+    where
+        W: Iterator<Item = Word>,
+    {
+        let mut bottom = 0.0;
+        let mut idx = 0;
+        loop {
+            if idx == pending.len() {
+                match lines.next() {
+                    Some(line) => pending.push_back(line),
+                    None => return,
+                }
+            }
+            bottom += pending[idx].line_height();
+            if !within(bottom, height) {
+                break;
+            }
+            idx += 1;
+        }
+
+        // look ahead to the end of the overflowing paragraph, or enough lines for widows
+        let overflow = idx;
+        while !pending[idx].hard_break && idx + 1 - overflow < self.widows {
+            idx += 1;
+            if idx == pending.len() {
+                match lines.next() {
+                    Some(line) => pending.push_back(line),
+                    None => return,
+                }
+            }
+        }
+    }
+
+    /// Returns the smallest column height, up to the `height_bound`, that fits as many lines
+    /// into `count` columns as the `height_bound` itself.
+    fn balanced_height(&self, lines: &[(f32, bool)], count: usize, height_bound: f32) -> f32 {
+        let overflowing = |height: f32| {
+            let mut rest = lines;
+            for _ in 0..count {
+                rest = &rest[frame_len(rest, height, self.orphans, self.widows)..];
+            }
+            rest.len()
+        };
+
+        let total: f32 = lines.iter().map(|(h, _)| h).sum();
+        let max_height = height_bound.min(total);
+        let target = overflowing(height_bound);
+
+        let (mut low, mut high) = ((total / count as f32).min(max_height), max_height);
+        if overflowing(low) <= target {
+            return low;
+        }
+        for _ in 0..32 {
+            let mid = (low + high) / 2.0;
+            match overflowing(mid) <= target {
+                true => high = mid,
+                false => low = mid,
+            }
+        }
+        high
+    }
+}
+
+impl<L: LineBreaker> GlyphPositioner for FlowLayout<L> {
+    #[inline]
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_glyphs_with_fallbacks(fonts, &FontFallbacks::default(), geometry, sections)
+    }
+
+    #[inline]
+    fn calculate_glyphs_with_fallbacks<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_flow(fonts, fallbacks, geometry, sections).0
+    }
+
+    #[inline]
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let (glyphs, lines, _) = self.calculate_flow(fonts, fallbacks, geometry, sections);
+        (glyphs, lines)
+    }
+
+    /// Returns the union of the frames.
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        let (screen_x, screen_y) = geometry.screen_position;
+        self.frame_rects(geometry)
+            .into_iter()
+            .reduce(|a, b| Rect {
+                min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+                max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+            })
+            .unwrap_or(Rect {
+                min: point(screen_x, screen_y),
+                max: point(screen_x, screen_y),
+            })
+    }
+}
+
+#[inline]
+fn metrics(line: &Line) -> (f32, bool) {
+    (line.line_height(), line.hard_break)
+}
+
+/// Reduce float errors by using relative "<= bound" check
+#[inline]
+pub(crate) fn within(x: f32, bound: f32) -> bool {
+    x < bound || approx::relative_eq!(x, bound)
+}
+
+/// Returns the number of `lines`, of `(line_height, hard_break)`, that fill a frame of
+/// `height`. Lines are moved to the next frame to keep at least `orphans` lines of a broken
+/// paragraph in this frame & `widows` in the next, unless that would empty the frame.
+///
+/// Note: Will always have at least one line, if possible, even if the line itself breaks
+/// the `height`.
+fn frame_len(lines: &[(f32, bool)], height: f32, orphans: usize, widows: usize) -> usize {
+    let mut bottom = 0.0;
+    let fit = lines
+        .iter()
+        .take_while(|(line_height, _)| {
+            bottom += line_height;
+            within(bottom, height)
+        })
+        .count()
+        .max(1)
+        .min(lines.len());
+
+    if fit == lines.len() || lines[fit - 1].1 {
+        // no paragraph is broken
+        return fit;
+    }
+
+    let paragraph_start = lines[..fit]
+        .iter()
+        .rposition(|(_, hard_break)| *hard_break)
+        .map_or(0, |idx| idx + 1);
+    let following = lines[fit..]
+        .iter()
+        .position(|(_, hard_break)| *hard_break)
+        .map_or(lines.len() - fit, |idx| idx + 1);
+
+    let split = fit
+        .saturating_sub(widows.saturating_sub(following))
+        .max(paragraph_start);
+    let split = match split - paragraph_start < orphans {
+        true => paragraph_start,
+        false => split,
+    };
+    match split {
+        0 => fit,
+        split => split,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Indents, SectionText, TabStops, TabWidth};
+    use approx::assert_relative_eq;
+    use std::sync::LazyLock;
+
+    static A_FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap()
+    });
+
+    #[test]
+    fn frame_len_widows_orphans() {
+        // a 2 line paragraph, then a 4 line paragraph
+        let lines = &[
+            (10.0, false),
+            (10.0, true),
+            (10.0, false),
+            (10.0, false),
+            (10.0, false),
+            (10.0, true),
+        ];
+
+        // unrestricted, breaks the 2nd paragraph after its 1st line
+        assert_eq!(frame_len(lines, 30.0, 1, 1), 3);
+        // a lone orphan moves to the next frame
+        assert_eq!(frame_len(lines, 30.0, 2, 1), 2);
+        // a lone widow pulls a line to the next frame
+        assert_eq!(frame_len(lines, 50.0, 1, 2), 4);
+        // the frame keeps an orphan when pulling widows would empty it
+        assert_eq!(frame_len(&lines[2..], 10.0, 2, 2), 1);
+        // lines taller than the frame are still placed
+        assert_eq!(frame_len(lines, 5.0, 2, 2), 1);
+        assert_eq!(frame_len(lines, 100.0, 2, 2), 6);
+    }
+
+    #[test]
+    fn columns_continue() {
+        let text = "aaaa bbbb cccc dddd eeee ffff gggg hhhh";
+        let sfont = A_FONT.as_scaled(20.0);
+        let line_height = sfont.height() + sfont.line_gap();
+        let geometry = SectionGeometry {
+            screen_position: (10.0, 20.0),
+            bounds: (180.0, line_height * 3.0),
+        };
+        let sections = &[SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+
+        // each 80px column fits a word per line, ie 3 words per column
+        let layout = FlowLayout::columns(2, 20.0).orphans(1).widows(1);
+        let (glyphs, lines, frames) =
+            layout.calculate_flow(&[&*A_FONT], &FontFallbacks::default(), &geometry, sections);
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].lines, 0..3);
+        assert_eq!(frames[0].end, (0, 15));
+        assert_eq!(frames[1].lines, 3..6);
+        assert_eq!(frames[1].end, (0, 30));
+        assert_relative_eq!(frames[1].rect.min.x, 110.0);
+        assert_eq!(lines.len(), 6);
+
+        // the 2nd column starts at its left & the top
+        assert_eq!(lines[3].start, (0, 15));
+        assert_relative_eq!(lines[3].left, 110.0);
+        assert_relative_eq!(lines[3].baseline, 20.0 + sfont.ascent());
+        assert_relative_eq!(glyphs[lines[3].glyphs.start].glyph.position.x, 110.0);
+
+        // continuing from the end lays out the remainder
+        let rest = &[SectionText {
+            text: &text[frames[1].end.1..],
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let (_, lines, frames) =
+            layout.calculate_flow(&[&*A_FONT], &FontFallbacks::default(), &geometry, rest);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(frames[1].lines, 2..2);
+        assert_eq!(frames[1].end, (0, 9));
+    }
+
+    #[test]
+    fn layout_options() {
+        let text = "aaaa bbbb\tc";
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        let geometry = SectionGeometry {
+            screen_position: (10.0, 0.0),
+            bounds: (advance * 8.0, f32::INFINITY),
+        };
+        let sections = &[SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let options = LayoutOptions::default()
+            .with_indents(Indents {
+                left: advance,
+                ..<_>::default()
+            })
+            .with_tab_stops(TabStops {
+                width: TabWidth::Px(advance * 3.0),
+                ..<_>::default()
+            });
+
+        let layout = FlowLayout::columns(1, 0.0).options(options.clone());
+        let (glyphs, lines, _) =
+            layout.calculate_flow(&[&*A_FONT], &FontFallbacks::default(), &geometry, sections);
+        assert_eq!(lines.len(), 2);
+        // lines are indented
+        assert_relative_eq!(glyphs[0].glyph.position.x, 10.0 + advance);
+        let b = &glyphs[lines[1].glyphs.clone()][0];
+        assert_relative_eq!(b.glyph.position.x, 10.0 + advance);
+
+        // as a single column wrapping layout
+        let expected = crate::Layout::default_wrap()
+            .options(options.clone())
+            .calculate_glyphs(&[&*A_FONT], &geometry, sections);
+        assert_eq!(glyphs, expected);
+
+        // preformatted text doesn't wrap
+        let layout = FlowLayout::columns(1, 0.0).options(options.with_white_space(WhiteSpace::Pre));
+        let (_, lines, _) =
+            layout.calculate_flow(&[&*A_FONT], &FontFallbacks::default(), &geometry, sections);
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn balanced_columns() {
+        let text = "aaaa bbbb cccc dddd eeee ffff";
+        let sfont = A_FONT.as_scaled(20.0);
+        let line_height = sfont.height() + sfont.line_gap();
+        let geometry = SectionGeometry {
+            bounds: (230.0, f32::INFINITY),
+            ..<_>::default()
+        };
+        let sections = &[SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+
+        let (_, _, frames) = FlowLayout::columns(3, 20.0).balanced().calculate_flow(
+            &[&*A_FONT],
+            &FontFallbacks::default(),
+            &geometry,
+            sections,
+        );
+
+        assert_eq!(
+            frames.iter().map(|f| f.lines.clone()).collect::<Vec<_>>(),
+            [0..2, 2..4, 4..6]
+        );
+        assert_relative_eq!(frames[0].rect.height(), line_height * 2.0, epsilon = 1e-3);
+
+        // unbalanced columns fill the first
+        let (_, _, frames) = FlowLayout::columns(3, 20.0).calculate_flow(
+            &[&*A_FONT],
+            &FontFallbacks::default(),
+            &geometry,
+            sections,
+        );
+        assert_eq!(frames[0].lines, 0..6);
+        assert_eq!(frames[2].end, (0, text.len()));
+    }
+
+    #[test]
+    fn rects_pages() {
+        let pages = [
+            Rect {
+                min: Point { x: 0.0, y: 0.0 },
+                max: Point { x: 300.0, y: 50.0 },
+            },
+            Rect {
+                min: Point { x: 400.0, y: 0.0 },
+                max: Point { x: 700.0, y: 50.0 },
+            },
+        ];
+        let text = "First paragraph.\nSecond paragraph.\nThird paragraph.\nFourth.\nFifth.";
+        let sections = &[SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            ..<_>::default()
+        }];
+        let layout = FlowLayout::rects(pages).h_align(HorizontalAlign::Right);
+
+        let (glyphs, lines, frames) = layout.calculate_flow(
+            &[&*A_FONT],
+            &FontFallbacks::default(),
+            &SectionGeometry {
+                screen_position: (0.0, 100.0),
+                ..<_>::default()
+            },
+            sections,
+        );
+
+        // 2 lines per page
+        assert_eq!(frames[0].lines, 0..2);
+        assert_eq!(frames[1].lines, 2..4);
+        assert_eq!(frames[1].rect.min, point(400.0, 100.0));
+        assert_eq!(frames[1].end.1, text.find("Fifth").unwrap());
+
+        // right aligned within the page
+        let sfont = A_FONT.as_scaled(20.0);
+        let last = glyphs[lines[2].glyphs.clone()]
+            .iter()
+            .rfind(|sg| sg.glyph.id == A_FONT.glyph_id('.'))
+            .unwrap();
+        assert_relative_eq!(
+            last.glyph.position.x + sfont.h_advance(last.glyph.id),
+            700.0,
+            epsilon = 1e-3
+        );
+
+        assert_eq!(
+            layout.bounds_rect(&SectionGeometry {
+                screen_position: (0.0, 100.0),
+                ..<_>::default()
+            }),
+            Rect {
+                min: point(0.0, 100.0),
+                max: point(700.0, 150.0),
+            }
+        );
+    }
+}
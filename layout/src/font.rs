@@ -1,4 +1,6 @@
 use ab_glyph::Font;
+use ttf_parser::{os2, RawFace, Tag};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Id for a font.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
@@ -26,3 +28,143 @@ where
         self.index(index.0)
     }
 }
+
+/// Fallback fonts by [`FontId`](struct.FontId.html), used for characters a section's font
+/// lacks glyphs for.
+///
+/// Each grapheme cluster is laid out using the first font, starting with the section's font
+/// then each fallback in order, that has glyphs for all its characters. The font actually
+/// used is recorded in `SectionGlyph::font_id`.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// let mut fallbacks = FontFallbacks::default();
+/// // FontId(0) falls back to FontId(1), then FontId(2)
+/// fallbacks.set(FontId(0), [FontId(1), FontId(2)]);
+///
+/// assert_eq!(fallbacks.get(FontId(0)), [FontId(1), FontId(2)]);
+/// assert!(fallbacks.get(FontId(1)).is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FontFallbacks(Vec<Vec<FontId>>);
+
+impl FontFallbacks {
+    /// Sets the fonts to fall back to, in order, for `font_id`.
+    pub fn set<I: IntoIterator<Item = FontId>>(&mut self, font_id: FontId, fallbacks: I) {
+        if self.0.len() <= font_id.0 {
+            self.0.resize_with(font_id.0 + 1, Vec::new);
+        }
+        self.0[font_id.0] = fallbacks.into_iter().collect();
+    }
+
+    /// Returns the fonts to fall back to, in order, for `font_id`.
+    #[inline]
+    pub fn get(&self, font_id: FontId) -> &[FontId] {
+        self.0.get(font_id.0).map_or(&[], |f| f)
+    }
+
+    /// Returns `true` if no font has any fallbacks.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|f| f.is_empty())
+    }
+
+    /// Returns the first font covering all `cluster` characters, or failing that its
+    /// first character, defaulting to `font_id`.
+    fn resolve<F: Font>(&self, fonts: &[F], font_id: FontId, cluster: &str) -> FontId {
+        let chain = || {
+            Some(font_id)
+                .into_iter()
+                .chain(self.get(font_id).iter().copied())
+                .filter(|id| id.0 < fonts.len())
+        };
+        let covers = |id: FontId, c: char| is_default_ignorable(c) || fonts[id].glyph_id(c).0 != 0;
+
+        chain()
+            .find(|id| cluster.chars().all(|c| covers(*id, c)))
+            .or_else(|| {
+                let first = cluster.chars().next()?;
+                chain().find(|id| covers(*id, first))
+            })
+            .unwrap_or(font_id)
+    }
+}
+
+/// Returns the OS/2 table of the first face of the font `data`.
+fn os2_table(data: &[u8]) -> Option<os2::Table<'_>> {
+    let face = RawFace::parse(data, 0).ok()?;
+    os2::Table::parse(face.table(Tag::from_bytes(b"OS/2"))?)
+}
+
+/// Returns the OS/2 table subscript & superscript y offsets, in font units, of the first
+/// face of the font `data`. Positive offsets lower subscripts & raise superscripts.
+pub(crate) fn script_offsets(data: &[u8]) -> Option<(i16, i16)> {
+    let os2 = os2_table(data)?;
+    let sub = os2.subscript_metrics().y_offset;
+    let sup = os2.superscript_metrics().y_offset;
+    Some((sub, sup)).filter(|offsets| *offsets != (0, 0))
+}
+
+/// Returns the OS/2 table x-height & cap-height, in font units, of the first face of the
+/// font `data`. Only version 2 tables & later include these, zero when unknown.
+pub(crate) fn optical_heights(data: &[u8]) -> Option<(i16, i16)> {
+    let os2 = os2_table(data)?;
+    Some((os2.x_height()?, os2.capital_height()?))
+}
+
+/// Characters that don't need glyphs, like joiners & variation selectors, so shouldn't
+/// affect which font a cluster uses.
+#[inline]
+fn is_default_ignorable(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{200B}'..='\u{200F}'
+                | '\u{2060}'..='\u{206F}'
+                | '\u{FE00}'..='\u{FE0F}'
+                | '\u{FEFF}'
+                | '\u{E0000}'..='\u{E0FFF}'
+        )
+}
+
+/// Resolves the font, considering fallbacks, for each grapheme cluster of a section's text.
+pub(crate) struct FallbackResolver<'b> {
+    fallbacks: &'b FontFallbacks,
+    font_id: FontId,
+    /// End byte index & resolved font of the current cluster.
+    cluster: (usize, FontId),
+}
+
+impl<'b> FallbackResolver<'b> {
+    #[inline]
+    pub(crate) fn new(fallbacks: &'b FontFallbacks, font_id: FontId) -> Self {
+        Self {
+            fallbacks,
+            font_id,
+            cluster: (0, font_id),
+        }
+    }
+
+    /// Returns the font to use for the char at `byte_index`. Must be called in text order.
+    #[inline]
+    pub(crate) fn font_id<F: Font>(
+        &mut self,
+        fonts: &[F],
+        text: &str,
+        byte_index: usize,
+    ) -> FontId {
+        if self.fallbacks.get(self.font_id).is_empty() {
+            return self.font_id;
+        }
+        if byte_index >= self.cluster.0 {
+            let cluster = text[byte_index..]
+                .graphemes(true)
+                .next()
+                .unwrap_or_default();
+            let font_id = self.fallbacks.resolve(fonts, self.font_id, cluster);
+            self.cluster = (byte_index + cluster.len(), font_id);
+        }
+        self.cluster.1
+    }
+}
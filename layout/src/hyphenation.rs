@@ -0,0 +1,363 @@
+use crate::{BuiltInLineBreaker, LineBreak, LineBreaker};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Knuth-Liang hyphenation patterns for a language.
+///
+/// Patterns use the TeX format, e.g. from the _hyph-utf8_ project `hyph-*.pat.txt` files.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// let patterns = HyphenationPatterns::new("hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n");
+/// assert_eq!(patterns.hyphenate("hyphenation"), [2, 6]);
+/// ```
+#[derive(Clone, Default)]
+pub struct HyphenationPatterns {
+    /// Pattern letters -> inter-letter values, including before the first & after the last.
+    patterns: HashMap<String, Vec<u8>>,
+    /// Exception word -> character counts preceding each hyphen.
+    exceptions: HashMap<String, Vec<usize>>,
+    /// Character length of the longest pattern.
+    max_len: usize,
+    left_min: usize,
+    right_min: usize,
+    /// Hash of the parsed patterns & exceptions, independent of their order.
+    id: u64,
+}
+
+impl fmt::Debug for HyphenationPatterns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyphenationPatterns")
+            .field("patterns", &self.patterns.len())
+            .field("exceptions", &self.exceptions.len())
+            .field("left_min", &self.left_min)
+            .field("right_min", &self.right_min)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl HyphenationPatterns {
+    /// Parses whitespace separated patterns, like `"hy3ph"` or `".ach4"`.
+    /// `%` comments are ignored.
+    ///
+    /// Words are hyphenated leaving at least 2 characters before & 3 after each hyphen,
+    /// see [`with_min_lengths`](#method.with_min_lengths).
+    pub fn new(patterns: &str) -> Self {
+        let mut parsed = Self {
+            left_min: 2,
+            right_min: 3,
+            ..<_>::default()
+        };
+
+        for pattern in words(patterns) {
+            let mut letters = String::new();
+            let mut values = vec![0];
+            for c in pattern.chars() {
+                match c.to_digit(10) {
+                    Some(value) => *values.last_mut().unwrap() = value as u8,
+                    None => {
+                        letters.push(c);
+                        values.push(0);
+                    }
+                }
+            }
+            parsed.max_len = parsed.max_len.max(values.len() - 1);
+            parsed.patterns.insert(letters, values);
+        }
+        parsed.id = parsed.digest();
+        parsed
+    }
+
+    /// Returns patterns with additional whitespace separated, pre-hyphenated exception words,
+    /// like `"ta-ble"`. `%` comments are ignored.
+    ///
+    /// Exceptions match words case-insensitively, as patterns do.
+    pub fn with_exceptions(mut self, exceptions: &str) -> Self {
+        for exception in words(exceptions) {
+            let mut word = String::new();
+            let mut hyphens = Vec::new();
+            for c in exception.chars() {
+                match c {
+                    '-' => hyphens.push(word.chars().count()),
+                    c => word.push(lowercase(c)),
+                }
+            }
+            self.exceptions.insert(word, hyphens);
+        }
+        self.id = self.digest();
+        self
+    }
+
+    /// Returns patterns hyphenating words leaving at least `left_min` characters before and
+    /// `right_min` characters after each hyphen.
+    pub fn with_min_lengths(mut self, left_min: usize, right_min: usize) -> Self {
+        self.left_min = left_min.max(1);
+        self.right_min = right_min.max(1);
+        self
+    }
+
+    /// Returns a hash of the parsed patterns & exceptions, sorted so equal content has an
+    /// equal digest however it was ordered.
+    fn digest(&self) -> u64 {
+        let mut patterns: Vec<_> = self.patterns.iter().collect();
+        patterns.sort_unstable();
+        let mut exceptions: Vec<_> = self.exceptions.iter().collect();
+        exceptions.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+        patterns.hash(&mut hasher);
+        exceptions.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns a key hashing the content of the patterns, equal for equal patterns.
+    #[inline]
+    fn content_key(&self) -> (u64, usize, usize) {
+        (self.id, self.left_min, self.right_min)
+    }
+
+    /// Returns the byte offsets in `word` where it may be hyphenated.
+    pub fn hyphenate(&self, word: &str) -> Vec<usize> {
+        let char_offsets: Vec<_> = word.char_indices().map(|(idx, _)| idx).collect();
+        let len = char_offsets.len();
+        if len < self.left_min + self.right_min {
+            return Vec::new();
+        }
+
+        let lower: String = word.chars().map(lowercase).collect();
+
+        let allowed = |n: &usize| *n >= self.left_min && len - n >= self.right_min;
+        if let Some(hyphens) = self.exceptions.get(&lower) {
+            return hyphens
+                .iter()
+                .filter(|n| allowed(n))
+                .map(|n| char_offsets[*n])
+                .collect();
+        }
+
+        let chars: Vec<char> = Some('.')
+            .into_iter()
+            .chain(lower.chars())
+            .chain(Some('.'))
+            .collect();
+
+        // values[n] is the score before chars[n]
+        let mut values = vec![0; chars.len() + 1];
+        let mut key = String::new();
+        for start in 0..chars.len() {
+            key.clear();
+            for c in chars[start..].iter().take(self.max_len) {
+                key.push(*c);
+                if let Some(pattern) = self.patterns.get(&key) {
+                    for (value, pattern_value) in values[start..].iter_mut().zip(pattern) {
+                        *value = (*value).max(*pattern_value);
+                    }
+                }
+            }
+        }
+
+        // hyphen after `n` word characters, i.e. before chars[n + 1]
+        (1..len)
+            .filter(|n| values[n + 1] % 2 == 1 && allowed(n))
+            .map(|n| char_offsets[n])
+            .collect()
+    }
+}
+
+/// Patterns are equal if parsed to the same patterns & exceptions with the same min lengths.
+impl PartialEq for HyphenationPatterns {
+    fn eq(&self, other: &Self) -> bool {
+        // differing digests shortcut comparing the parsed content
+        self.content_key() == other.content_key()
+            && self.patterns == other.patterns
+            && self.exceptions == other.exceptions
+    }
+}
+
+impl Eq for HyphenationPatterns {}
+
+/// Lowercases `c` to a single character, keeping character counts.
+#[inline]
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Whitespace separated words ignoring `%` comments.
+fn words(s: &str) -> impl Iterator<Item = &str> {
+    s.lines().flat_map(|line| {
+        line.split('%')
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+    })
+}
+
+/// [`LineBreaker`](trait.LineBreaker.html) adding [`LineBreak::Hyphen`] opportunities,
+/// found using [`HyphenationPatterns`], inside the words of another line breaker.
+///
+/// Built-in layouts render a hyphen after the word only when breaking the line there.
+///
+/// Patterns are shared, so cloning the line breaker, or layouts using it, is cheap.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// use std::sync::Arc;
+///
+/// let patterns = Arc::new(HyphenationPatterns::new(
+///     "hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n",
+/// ));
+///
+/// let layout = Layout::default().line_breaker(HyphenatingLineBreaker::new(patterns));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HyphenatingLineBreaker<L: LineBreaker = BuiltInLineBreaker> {
+    patterns: Arc<HyphenationPatterns>,
+    line_breaker: L,
+}
+
+impl HyphenatingLineBreaker {
+    /// Returns a line breaker hyphenating the words of the default
+    /// [`BuiltInLineBreaker`](enum.BuiltInLineBreaker.html).
+    #[inline]
+    pub fn new(patterns: impl Into<Arc<HyphenationPatterns>>) -> Self {
+        Self {
+            patterns: patterns.into(),
+            line_breaker: BuiltInLineBreaker::default(),
+        }
+    }
+}
+
+impl<L: LineBreaker> HyphenatingLineBreaker<L> {
+    /// Returns an identical line breaker hyphenating the words of `line_breaker`.
+    #[inline]
+    pub fn with_line_breaker<L2: LineBreaker>(
+        self,
+        line_breaker: L2,
+    ) -> HyphenatingLineBreaker<L2> {
+        HyphenatingLineBreaker {
+            patterns: self.patterns,
+            line_breaker,
+        }
+    }
+}
+
+/// Patterns are compared by content, so separately parsed equal patterns are equal.
+impl<L: LineBreaker + PartialEq> PartialEq for HyphenatingLineBreaker<L> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        (Arc::ptr_eq(&self.patterns, &other.patterns) || self.patterns == other.patterns)
+            && self.line_breaker == other.line_breaker
+    }
+}
+
+impl<L: LineBreaker + Eq> Eq for HyphenatingLineBreaker<L> {}
+
+/// Patterns are hashed by a digest of their content, consistent with `PartialEq`.
+impl<L: LineBreaker> Hash for HyphenatingLineBreaker<L> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.patterns.content_key().hash(state);
+        self.line_breaker.hash(state);
+    }
+}
+
+impl<L: LineBreaker> LineBreaker for HyphenatingLineBreaker<L> {
+    fn line_breaks<'a>(&self, text: &'a str) -> Box<dyn Iterator<Item = LineBreak> + 'a> {
+        let mut breaks: Vec<_> = self.line_breaker.line_breaks(text).collect();
+
+        let hyphens = text
+            .split_word_bound_indices()
+            .filter(|(_, word)| word.chars().all(char::is_alphabetic))
+            .flat_map(|(start, word)| {
+                self.patterns
+                    .hyphenate(word)
+                    .into_iter()
+                    .map(move |offset| LineBreak::Hyphen(start + offset))
+            });
+        breaks.extend(hyphens);
+
+        // inner breaks take precedence at the same offset
+        breaks.sort_by_key(|b| (b.offset(), matches!(b, LineBreak::Hyphen(_))));
+        breaks.dedup_by_key(|b| b.offset());
+
+        Box::new(breaks.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::LazyLock;
+
+    /// Patterns from Liang's thesis hyphenating "hyphenation".
+    static PATTERNS: LazyLock<HyphenationPatterns> = LazyLock::new(|| {
+        HyphenationPatterns::new(
+            "% example patterns\n\
+             hy3ph he2n hena4 hen5at\n\
+             1na n2at 1tio 2io o2n",
+        )
+        .with_exceptions("ta-ble Pro-ject")
+    });
+
+    #[test]
+    fn hyphenate() {
+        assert_eq!(PATTERNS.hyphenate("hyphenation"), [2, 6]);
+        assert_eq!(PATTERNS.hyphenate("Hyphenation"), [2, 6]);
+        assert_eq!(PATTERNS.hyphenate("table"), [2]);
+        // exceptions are case-insensitive
+        assert_eq!(PATTERNS.hyphenate("TABLE"), [2]);
+        assert_eq!(PATTERNS.hyphenate("Project"), [3]);
+
+        let patterns = PATTERNS.clone().with_min_lengths(3, 3);
+        assert_eq!(patterns.hyphenate("hyphenation"), [6]);
+        assert!(patterns.hyphenate("table").is_empty());
+    }
+
+    #[test]
+    fn eq_by_content() {
+        let breaker =
+            |patterns: &str| HyphenatingLineBreaker::new(HyphenationPatterns::new(patterns));
+
+        assert_eq!(breaker("hy3ph he2n"), breaker("% comment\nhy3ph  he2n"));
+        assert_ne!(breaker("hy3ph he2n"), breaker("hy3ph he2n 1na"));
+
+        // independent of pattern order, with consistent hashes
+        let hash = |breaker: &HyphenatingLineBreaker| {
+            let mut hasher = DefaultHasher::new();
+            breaker.hash(&mut hasher);
+            hasher.finish()
+        };
+        let (a, b) = (breaker("hy3ph he2n"), breaker("he2n hy3ph"));
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_eq!(a, a.clone());
+        assert_ne!(
+            HyphenatingLineBreaker::new(PATTERNS.clone()),
+            HyphenatingLineBreaker::new(PATTERNS.clone().with_min_lengths(3, 3)),
+        );
+    }
+
+    #[test]
+    fn line_breaks() {
+        let breaker = HyphenatingLineBreaker::new(PATTERNS.clone());
+        let breaks: Vec<_> = breaker.line_breaks("a hyphenation\nx").collect();
+        assert_eq!(
+            breaks,
+            [
+                LineBreak::Soft(2),
+                LineBreak::Hyphen(4),
+                LineBreak::Hyphen(8),
+                LineBreak::Hard(14),
+                LineBreak::Soft(15),
+            ]
+        );
+    }
+}
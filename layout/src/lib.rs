@@ -25,11 +25,13 @@
 //!             text: "hello ",
 //!             scale: PxScale::from(20.0),
 //!             font_id: FontId(0),
+//!             ..SectionText::default()
 //!         },
 //!         SectionText {
 //!             text: "glyph_brush_layout",
 //!             scale: PxScale::from(25.0),
 //!             font_id: FontId(1),
+//!             ..SectionText::default()
 //!         },
 //!     ],
 //! );
@@ -41,6 +43,7 @@
 //!     font_id,
 //!     section_index,
 //!     byte_index,
+//!     ..
 //! } = &glyphs[4];
 //! assert_eq!(glyph.id, fonts[0].glyph_id('o'));
 //! assert_eq!(*font_id, FontId(0));
@@ -52,6 +55,7 @@
 //!     font_id,
 //!     section_index,
 //!     byte_index,
+//!     ..
 //! } = &glyphs[14];
 //! assert_eq!(glyph.id, fonts[1].glyph_id('u'));
 //! assert_eq!(*font_id, FontId(1));
@@ -61,19 +65,44 @@
 //! # Ok(())
 //! # }
 //! ```
+mod bidi;
 mod builtin;
 mod characters;
+mod exclusion;
+mod fit;
+mod flow;
 mod font;
+mod hyphenation;
 mod linebreak;
 mod lines;
+mod optimal;
 mod section;
+#[cfg(feature = "shaping")]
+mod shaping;
+mod tabs;
+mod vertical;
 mod words;
 
 /// Re-exported ab_glyph types.
 pub mod ab_glyph {
     pub use ab_glyph::*;
 }
-pub use self::{builtin::*, font::*, linebreak::*, section::*};
+#[cfg(feature = "shaping")]
+pub use self::shaping::*;
+pub use self::{
+    builtin::*,
+    exclusion::*,
+    fit::*,
+    flow::*,
+    font::*,
+    hyphenation::*,
+    linebreak::*,
+    optimal::*,
+    section::*,
+    tabs::*,
+    vertical::VerticalOrientation,
+    words::{IntrinsicWidths, MeasuredWords, WhiteSpace},
+};
 
 use ::ab_glyph::*;
 use std::hash::Hash;
@@ -118,6 +147,166 @@ pub trait GlyphPositioner: Hash {
         let _ = (previous, change);
         self.calculate_glyphs(fonts, geometry, sections)
     }
+
+    /// Calculate a sequence of positioned glyphs to render, using `fallbacks` for characters
+    /// missing from a section's font.
+    ///
+    /// The default implementation ignores `fallbacks` & calls `calculate_glyphs`.
+    fn calculate_glyphs_with_fallbacks<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let _ = fallbacks;
+        self.calculate_glyphs(fonts, geometry, sections)
+    }
+
+    /// Calculate a sequence of positioned glyphs to render, as
+    /// [`calculate_glyphs_with_fallbacks`](#method.calculate_glyphs_with_fallbacks), along
+    /// with the metrics of each laid out line.
+    ///
+    /// The default implementation returns no lines.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// # fn main() -> Result<(), InvalidFont> {
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// let (glyphs, lines) = Layout::default().calculate_glyphs_and_lines(
+    ///     &[font],
+    ///     &FontFallbacks::default(),
+    ///     &SectionGeometry::default(),
+    ///     &[SectionText {
+    ///         text: "hello\nworld",
+    ///         ..<_>::default()
+    ///     }],
+    /// );
+    /// assert_eq!(lines.len(), 2);
+    /// assert_eq!(lines[1].glyphs, 5..10);
+    /// assert_eq!(lines[1].start, (0, 6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let glyphs = self.calculate_glyphs_with_fallbacks(fonts, fallbacks, geometry, sections);
+        (glyphs, Vec::new())
+    }
+
+    /// Measure the words of `sections`, which may be reused to recalculate glyphs after
+    /// bounds changes, see [`recalculate_glyphs_and_lines`](#method.recalculate_glyphs_and_lines).
+    ///
+    /// The default implementation returns `None`, i.e. words are not reusable.
+    fn measure_words<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        sections: &[S],
+    ) -> Option<MeasuredWords>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let _ = (fonts, fallbacks, sections);
+        None
+    }
+
+    /// Returns the min-content & max-content widths of the [`measure_words`](#method.measure_words)
+    /// of `sections`, without laying out lines.
+    ///
+    /// The default implementation returns `None`, i.e. intrinsic widths are unknown.
+    fn intrinsic_widths<S>(&self, words: &MeasuredWords, sections: &[S]) -> Option<IntrinsicWidths>
+    where
+        S: ToSectionText,
+    {
+        let _ = (words, sections);
+        None
+    }
+
+    /// Returns the height of the [`measure_words`](#method.measure_words) of `sections` laid
+    /// out within a `width` bound, without positioning glyphs.
+    ///
+    /// The default implementation returns `None`, i.e. the height is unknown.
+    fn height_for_width<S>(&self, words: &MeasuredWords, width: f32, sections: &[S]) -> Option<f32>
+    where
+        S: ToSectionText,
+    {
+        let _ = (words, width, sections);
+        None
+    }
+
+    /// Recalculate a glyph sequence & lines after a change, as
+    /// [`calculate_glyphs_and_lines`](#method.calculate_glyphs_and_lines), from the
+    /// `previous` layout of the section.
+    ///
+    /// The default implementation calls `recalculate_glyphs_with_fallbacks` for layouts
+    /// without lines, otherwise `calculate_glyphs_and_lines`.
+    fn recalculate_glyphs_and_lines<F, S, P>(
+        &self,
+        previous: PreviousLayout<'_, P>,
+        change: GlyphChange,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        match previous.lines.is_empty() {
+            true => {
+                let glyphs = self.recalculate_glyphs_with_fallbacks(
+                    previous.glyphs,
+                    change,
+                    fonts,
+                    fallbacks,
+                    geometry,
+                    sections,
+                );
+                (glyphs, Vec::new())
+            }
+            false => self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections),
+        }
+    }
+
+    /// Recalculate a glyph sequence after a change, using `fallbacks` for characters
+    /// missing from a section's font.
+    ///
+    /// The default implementation ignores `fallbacks` & calls `recalculate_glyphs`.
+    fn recalculate_glyphs_with_fallbacks<F, S, P>(
+        &self,
+        previous: P,
+        change: GlyphChange,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        let _ = fallbacks;
+        self.recalculate_glyphs(previous, change, fonts, geometry, sections)
+    }
 }
 
 #[derive(Debug)]
@@ -125,5 +314,50 @@ pub trait GlyphPositioner: Hash {
 pub enum GlyphChange {
     /// Only the geometry has changed, contains the old geometry
     Geometry(SectionGeometry),
+    /// Text has been appended to the last section text, contains the `(section_index, byte_index)`
+    /// the new text starts at. Geometry, layout & all other text are unchanged.
+    TextAppended((usize, usize)),
+    /// Text has been edited, contains a `(section_index, byte_index)` at or before the first
+    /// changed byte. Geometry, layout & all text before it are unchanged.
+    TextEdited((usize, usize)),
+    /// Every section text scale has been multiplied by the contained factor. Geometry,
+    /// layout, text & all other styles are unchanged.
+    Scale(f32),
     Unknown,
 }
+
+/// The previous layout of a section, reused by
+/// [`GlyphPositioner::recalculate_glyphs_and_lines`](trait.GlyphPositioner.html#method.recalculate_glyphs_and_lines).
+///
+/// Built with [`PreviousLayout::new`] & `with_*` methods, so further reusable state isn't
+/// breaking.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PreviousLayout<'a, P> {
+    /// The previous glyphs.
+    pub glyphs: P,
+    /// The lines of the previous `glyphs`.
+    pub lines: &'a [SectionLine],
+    /// The [`measure_words`](trait.GlyphPositioner.html#method.measure_words) of the
+    /// previous text, fonts & layout, if available. These are invalid after text changes.
+    pub words: Option<&'a MeasuredWords>,
+}
+
+impl<'a, P> PreviousLayout<'a, P> {
+    /// Returns the previous layout of `glyphs` & their `lines`.
+    #[inline]
+    pub fn new(glyphs: P, lines: &'a [SectionLine]) -> Self {
+        Self {
+            glyphs,
+            lines,
+            words: None,
+        }
+    }
+
+    /// Returns an identical previous layout but with the input measured `words`.
+    #[inline]
+    pub fn with_words(mut self, words: Option<&'a MeasuredWords>) -> Self {
+        self.words = words;
+        self
+    }
+}
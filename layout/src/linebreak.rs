@@ -1,18 +1,21 @@
 use std::{
     fmt,
     hash::Hash,
-    iter::FusedIterator,
+    iter::{self, FusedIterator},
     str::{self, CharIndices},
 };
+use unicode_segmentation::UnicodeSegmentation;
 
-/// Indicator that a character is a line break, soft or hard. Includes the offset (byte-index)
-/// position.
+/// Indicator that a character is a line break, soft, hard or hyphen. Includes the offset
+/// (byte-index) position.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LineBreak {
     /// Soft line break (offset).
     Soft(usize),
     /// Hard line break (offset).
     Hard(usize),
+    /// Soft line break inside a word (offset), a hyphen is rendered when breaking here.
+    Hyphen(usize),
 }
 
 impl LineBreak {
@@ -20,14 +23,14 @@ impl LineBreak {
     #[inline]
     pub fn offset(&self) -> usize {
         match *self {
-            LineBreak::Soft(offset) | LineBreak::Hard(offset) => offset,
+            LineBreak::Soft(offset) | LineBreak::Hard(offset) | LineBreak::Hyphen(offset) => offset,
         }
     }
 }
 
 /// Producer of a [`LineBreak`](enum.LineBreak.html) iterator. Used to allow to the
 /// [`Layout`](enum.Layout.html) to be line break aware in a generic way.
-pub trait LineBreaker: fmt::Debug + Copy + Hash {
+pub trait LineBreaker: fmt::Debug + Clone + Hash {
     fn line_breaks<'a>(&self, glyph_info: &'a str) -> Box<dyn Iterator<Item = LineBreak> + 'a>;
 }
 
@@ -102,6 +105,101 @@ impl LineBreaker for BuiltInLineBreaker {
     }
 }
 
+/// Where [`Layout::Wrap`](enum.Layout.html#variant.Wrap) may break lines between letters,
+/// similar to the CSS `word-break` property. Adjusts the soft breaks of the layout's
+/// [`LineBreaker`](trait.LineBreaker.html).
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // Korean text wrapping at spaces only
+/// let layout = Layout::default().word_break(WordBreak::KeepAll);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum WordBreak {
+    /// Breaks where the line breaker allows.
+    #[default]
+    Normal,
+    /// Also breaks between any two letters or numbers, e.g. inside long words, URLs
+    /// or hashes.
+    BreakAll,
+    /// Doesn't break between letters or numbers, so CJK text only breaks at spaces
+    /// & punctuation like other scripts.
+    KeepAll,
+}
+
+/// Whether [`Layout::Wrap`](enum.Layout.html#variant.Wrap) breaks inside words too wide for
+/// a line of their own, similar to the CSS `overflow-wrap` property.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// let layout = Layout::default().overflow_wrap(OverflowWrap::Anywhere);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum OverflowWrap {
+    /// Words too wide for the width bound overflow it.
+    #[default]
+    Normal,
+    /// Words too wide for the width bound are broken between any characters, filling each
+    /// line with as much of the word as fits. Words that fit on a line are unaffected.
+    Anywhere,
+}
+
+/// `LineBreaker` applying a [`WordBreak`] to the soft breaks of the inner line breaker.
+#[derive(Debug, Clone, Copy, Hash)]
+pub(crate) struct WordBreaker<L> {
+    pub(crate) line_breaker: L,
+    pub(crate) word_break: WordBreak,
+}
+
+impl<L: LineBreaker> LineBreaker for WordBreaker<L> {
+    #[inline]
+    fn line_breaks<'a>(&self, text: &'a str) -> Box<dyn Iterator<Item = LineBreak> + 'a> {
+        let breaks = self.line_breaker.line_breaks(text);
+        match self.word_break {
+            WordBreak::Normal => breaks,
+            WordBreak::BreakAll => {
+                let mut breaks = breaks.peekable();
+                let mut letter_breaks = letter_boundaries(text).peekable();
+                Box::new(iter::from_fn(move || {
+                    match (breaks.peek(), letter_breaks.peek()) {
+                        (Some(b), Some(offset)) if *offset < b.offset() => {
+                            letter_breaks.next().map(LineBreak::Soft)
+                        }
+                        (Some(b), Some(offset)) if *offset == b.offset() => {
+                            letter_breaks.next();
+                            breaks.next()
+                        }
+                        (Some(_), _) => breaks.next(),
+                        (None, _) => letter_breaks.next().map(LineBreak::Soft),
+                    }
+                }))
+            }
+            WordBreak::KeepAll => Box::new(breaks.filter(move |b| match *b {
+                LineBreak::Soft(offset) => !between_letters(text, offset),
+                _ => true,
+            })),
+        }
+    }
+}
+
+/// Returns the offsets of grapheme cluster boundaries between letters or numbers.
+fn letter_boundaries(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.grapheme_indices(true)
+        .skip(1)
+        .map(|(idx, _)| idx)
+        .filter(move |idx| between_letters(text, *idx))
+}
+
+/// Returns `true` if the characters either side of `offset` are letters or numbers.
+#[inline]
+fn between_letters(text: &str, offset: usize) -> bool {
+    let before = text[..offset].chars().next_back();
+    let after = text[offset..].chars().next();
+    before.is_some_and(char::is_alphanumeric) && after.is_some_and(char::is_alphanumeric)
+}
+
 /// Line breakers can't easily tell the difference between the end of a slice being a hard
 /// break and the last character being itself a hard or soft break. This trait allows testing
 /// of eol characters being "true" eol line breakers.
@@ -137,6 +235,44 @@ impl<B: LineBreaker> EolLineBreak<B> for char {
     }
 }
 
+#[cfg(test)]
+mod word_break_test {
+    use super::*;
+
+    fn breaks(word_break: WordBreak, text: &str) -> Vec<LineBreak> {
+        WordBreaker {
+            line_breaker: BuiltInLineBreaker::default(),
+            word_break,
+        }
+        .line_breaks(text)
+        .collect()
+    }
+
+    #[test]
+    fn break_all() {
+        use LineBreak::*;
+        assert_eq!(
+            breaks(WordBreak::BreakAll, "ab cd.\n"),
+            [Soft(1), Soft(3), Soft(4), Hard(7)]
+        );
+        assert_eq!(breaks(WordBreak::Normal, "ab cd.\n"), [Soft(3), Hard(7)]);
+    }
+
+    #[test]
+    fn keep_all() {
+        use LineBreak::*;
+        let text = "提高代碼 執行率。好";
+        let normal = breaks(WordBreak::Normal, text);
+        assert!(normal.contains(&Soft(3)), "{normal:?}");
+
+        // only breaking at spaces & punctuation
+        assert_eq!(
+            breaks(WordBreak::KeepAll, text),
+            [Soft(13), Soft(25), Soft(28)]
+        );
+    }
+}
+
 #[cfg(test)]
 mod eol_line_break {
     use super::*;
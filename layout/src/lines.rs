@@ -1,7 +1,17 @@
-use super::{HorizontalAlign, SectionGlyph, SectionText, VerticalAlign};
-use crate::{linebreak::LineBreaker, words::*};
+use super::{HorizontalAlign, SectionGlyph, SectionLine, VerticalAlign};
+use crate::{
+    bidi::Bidi,
+    exclusion,
+    tabs::{TabAlign, TabSegment},
+    words::*,
+    Ellipsis, Exclusion, Indents, OverflowWrap, TabStops, VerticalOrientation,
+};
 use ab_glyph::*;
-use std::iter::{FusedIterator, Iterator, Peekable};
+use std::{
+    collections::VecDeque,
+    iter::{FusedIterator, Iterator},
+    sync::Arc,
+};
 
 /// A line of `Word`s limited to a max width bound.
 #[derive(Default)]
@@ -9,6 +19,27 @@ pub(crate) struct Line {
     pub glyphs: Vec<SectionGlyph>,
     pub max_v_metrics: VMetrics,
     pub rightmost: f32,
+    /// Pixel advance width of the line including trailing whitespace.
+    pub advance_width: f32,
+    /// Indicates the line is part of a right-to-left paragraph.
+    pub rtl: bool,
+    /// Glyph indices of words that follow inter-word whitespace, used for justification.
+    pub word_gaps: Vec<usize>,
+    /// Indicates the line ended in a hard break, or the end of all glyphs.
+    pub hard_break: bool,
+    /// `(section_index, byte_index)` of the first character.
+    pub start: (usize, usize),
+    /// `(section_index, byte_index)` following the last character.
+    pub end: (usize, usize),
+    /// Paragraph margins & indents.
+    pub indents: Indents,
+    /// Indicates the line is the first of its paragraph.
+    pub paragraph_start: bool,
+    /// Top of the line from the top of the text.
+    pub top: f32,
+    /// Horizontal span of the width bound box the line fills beside exclusions, if any.
+    /// Lines sharing a row each fill a span.
+    pub segment: Option<(f32, f32)>,
 }
 
 impl Line {
@@ -17,32 +48,98 @@ impl Line {
         self.max_v_metrics.ascent - self.max_v_metrics.descent + self.max_v_metrics.line_gap
     }
 
-    /// Returns line glyphs positioned on the screen and aligned.
-    pub fn aligned_on_screen(
+    /// Returns the total horizontal inset of the line, its margins & indent.
+    #[inline]
+    pub(crate) fn inset_width(&self) -> f32 {
+        let (left, right) = self.insets();
+        left + right
+    }
+
+    /// Returns the left & right insets of the line, the indent is on the start side of
+    /// the paragraph. Lines beside exclusions are already inset within their segment.
+    #[inline]
+    fn insets(&self) -> (f32, f32) {
+        if self.segment.is_some() {
+            return (0.0, 0.0);
+        }
+        let Indents {
+            left,
+            right,
+            first_line,
+            hanging,
+        } = self.indents;
+        let indent = match self.paragraph_start {
+            true => first_line,
+            false => hanging,
+        };
+        match self.rtl {
+            false => (left + indent, right),
+            true => (left, right + indent),
+        }
+    }
+
+    /// Returns line glyphs positioned on the screen and aligned, with the line's metrics.
+    /// The metrics have index zero & a glyph range of all the returned glyphs.
+    ///
+    /// `width_bound` is the finite width of the box, starting from the render position, that
+    /// lines are stretched to when justifying & aligned in by paragraph direction. Lines are
+    /// inset within the box, or from the render position, by their margins & indent.
+    /// Lines beside exclusions are aligned within their segment of the box instead.
+    ///
+    /// `bidi` levels are used to reorder the glyphs into visual order.
+    pub fn aligned_on_screen<F: Font>(
         mut self,
-        (screen_x, screen_y): (f32, f32),
+        fonts: &[F],
+        (mut screen_x, screen_y): (f32, f32),
         h_align: HorizontalAlign,
         v_align: VerticalAlign,
-    ) -> Vec<SectionGlyph> {
-        if self.glyphs.is_empty() {
-            return Vec::new();
-        }
-
-        // implement v-aligns when they're are supported
-        let screen_left = match h_align {
-            HorizontalAlign::Left => point(screen_x, screen_y),
-            // - Right alignment attained from left by shifting the line
-            //   leftwards by the rightmost x distance from render position
-            // - Central alignment is attained from left by shifting the line
-            //   leftwards by half the rightmost x distance from render position
-            HorizontalAlign::Center | HorizontalAlign::Right => {
-                let mut shift_left = self.rightmost;
-                if h_align == HorizontalAlign::Center {
-                    shift_left /= 2.0;
-                }
-                point(screen_x - shift_left, screen_y)
+        mut width_bound: f32,
+        bidi: Option<&Bidi>,
+    ) -> (Vec<SectionGlyph>, SectionLine) {
+        if let Some((left, right)) = self.segment {
+            let box_left = match h_align {
+                HorizontalAlign::Center => screen_x - width_bound / 2.0,
+                HorizontalAlign::Right => screen_x - width_bound,
+                _ => screen_x,
+            };
+            width_bound = right - left;
+            screen_x = box_left
+                + left
+                + match h_align {
+                    HorizontalAlign::Center => width_bound / 2.0,
+                    HorizontalAlign::Right => width_bound,
+                    _ => 0.0,
+                };
+        }
+        let width_bound = width_bound - self.inset_width();
+        if !self.glyphs.is_empty() {
+            if h_align == HorizontalAlign::Justify {
+                self.justify(width_bound);
+            }
+            if let Some(bidi) = bidi {
+                bidi.reorder(&mut self);
+            }
+        }
+        let (inset_left, inset_right) = self.insets();
+
+        // - Right alignment attained from left by shifting the line
+        //   leftwards by the rightmost x distance from render position
+        // - Central alignment is attained from left by shifting the line
+        //   leftwards by half the rightmost x distance from render position
+        // - Start/End alignment is left or right alignment within the `width_bound` box
+        //   according to the paragraph direction. Justified lines align to the start.
+        // - Insets narrow the box, or render position, on either side
+        let shift_left = match h_align {
+            HorizontalAlign::Left => -inset_left,
+            HorizontalAlign::Center => (self.rightmost - inset_left + inset_right) / 2.0,
+            HorizontalAlign::Right => self.rightmost + inset_right,
+            HorizontalAlign::Start | HorizontalAlign::Justify if !self.rtl => -inset_left,
+            HorizontalAlign::End if self.rtl => -inset_left,
+            HorizontalAlign::Start | HorizontalAlign::End | HorizontalAlign::Justify => {
+                self.rightmost - width_bound - inset_left
             }
         };
+        let screen_left = point(screen_x - shift_left, screen_y);
 
         let screen_pos = match v_align {
             VerticalAlign::Top => screen_left,
@@ -56,45 +153,496 @@ impl Line {
                 screen_pos.y -= self.line_height();
                 screen_pos
             }
+            VerticalAlign::Baseline | VerticalAlign::LastBaseline => {
+                let mut screen_pos = screen_left;
+                screen_pos.y -= self.max_v_metrics.ascent;
+                screen_pos
+            }
+            VerticalAlign::CapHeightCenter | VerticalAlign::XHeightCenter => {
+                let mut screen_pos = screen_left;
+                screen_pos.y -=
+                    self.max_v_metrics.ascent - self.optical_height(fonts, v_align) / 2.0;
+                screen_pos
+            }
         };
 
         self.glyphs
             .iter_mut()
             .for_each(|sg| sg.glyph.position += screen_pos);
 
-        self.glyphs
+        let metrics = SectionLine {
+            index: 0,
+            glyphs: 0..self.glyphs.len(),
+            start: self.start,
+            end: self.end,
+            left: screen_pos.x,
+            baseline: screen_pos.y + self.max_v_metrics.ascent,
+            ascent: self.max_v_metrics.ascent,
+            descent: self.max_v_metrics.descent,
+            advance_width: self.advance_width,
+            hard_break: self.hard_break,
+            rtl: self.rtl,
+        };
+        (self.glyphs, metrics)
+    }
+
+    /// Returns the largest cap-height, for [`VerticalAlign::CapHeightCenter`], or x-height,
+    /// for [`VerticalAlign::XHeightCenter`], of the line's fonts & scales. Otherwise zero.
+    ///
+    /// Heights are read from the font's OS/2 table, falling back to the top of the `H` or
+    /// `x` glyph outline, or the ascent.
+    pub(crate) fn optical_height<F: Font>(&self, fonts: &[F], v_align: VerticalAlign) -> f32 {
+        let c = match v_align {
+            VerticalAlign::CapHeightCenter => 'H',
+            VerticalAlign::XHeightCenter => 'x',
+            _ => return 0.0,
+        };
+
+        let mut last = None;
+        let mut max = 0.0_f32;
+        for sg in &self.glyphs {
+            if last.replace((sg.font_id, sg.glyph.scale)) == Some((sg.font_id, sg.glyph.scale)) {
+                continue;
+            }
+            let scale_font = fonts[sg.font_id].as_scaled(sg.glyph.scale);
+            let os2_height = crate::font::optical_heights(scale_font.font().font_data())
+                .map(|(x_height, cap_height)| match c {
+                    'H' => cap_height,
+                    _ => x_height,
+                })
+                .filter(|height| *height > 0);
+            let height = match os2_height {
+                Some(height) => f32::from(height) * scale_font.v_scale_factor(),
+                None => {
+                    let id = scale_font.glyph_id(c);
+                    // outline bounds are y-up, the top is `min.y`
+                    Some(id)
+                        .filter(|id| id.0 != 0)
+                        .and_then(|id| scale_font.font.outline(id))
+                        .map_or(scale_font.ascent(), |o| {
+                            o.bounds.min.y * scale_font.v_scale_factor()
+                        })
+                }
+            };
+            max = max.max(height);
+        }
+        max
+    }
+
+    /// Spreads the remaining `width_bound` space across the line's inter-word gaps.
+    ///
+    /// Lines ending in a hard break, i.e. the last line of a paragraph, & lines without
+    /// any inter-word gaps are not stretched.
+    fn justify(&mut self, width_bound: f32) {
+        let extra = width_bound - self.rightmost;
+        if self.hard_break || self.word_gaps.is_empty() || !extra.is_finite() || extra <= 0.0 {
+            return;
+        }
+
+        let gap_width = extra / self.word_gaps.len() as f32;
+        let mut gaps = self.word_gaps.iter().peekable();
+        let mut shift = 0.0;
+        for (idx, sg) in self.glyphs.iter_mut().enumerate() {
+            while gaps.next_if(|gap_idx| **gap_idx <= idx).is_some() {
+                shift += gap_width;
+            }
+            sg.glyph.position.x += shift;
+        }
+        self.rightmost = width_bound;
+        self.advance_width += extra;
+    }
+
+    /// Removes characters to fit the line & `ellipsis` text within the `width_bound`.
+    ///
+    /// Characters are removed by cluster, consecutive glyphs from the same character.
+    /// Truncated lines are not justified.
+    pub(crate) fn truncate<F: Font>(&mut self, fonts: &[F], ellipsis: Ellipsis, width_bound: f32) {
+        let like = match ellipsis {
+            Ellipsis::Start(_) | Ellipsis::Middle(_) => self.glyphs.first(),
+            Ellipsis::End(_) => self.glyphs.last(),
+        };
+        let Some(like) = like.cloned() else {
+            return;
+        };
+        let (mut ellipsis_glyphs, ellipsis_w) =
+            ellipsis_glyphs(fonts, ellipsis.text(), &like, self.max_v_metrics.ascent);
+        let available = width_bound - ellipsis_w;
+
+        // (first glyph index, start x, end x) of each cluster
+        let mut clusters: Vec<(usize, f32, f32)> = Vec::new();
+        for (idx, sg) in self.glyphs.iter().enumerate() {
+            let x = sg.glyph.position.x;
+            match clusters.last_mut() {
+                Some((start, ..)) if same_cluster(&self.glyphs[*start], sg) => {}
+                Some((.., end)) => {
+                    *end = x;
+                    clusters.push((idx, x, x));
+                }
+                None => clusters.push((idx, x, x)),
+            }
+        }
+        if let Some((.., end)) = clusters.last_mut() {
+            *end = self.advance_width;
+        }
+
+        // leading clusters ending within `budget`, ignoring trailing whitespace
+        let head = |budget: f32| {
+            let n = clusters
+                .iter()
+                .take_while(|(_, _, end)| within(end.min(self.rightmost), budget))
+                .count();
+            let end_x = match n {
+                0 => 0.0,
+                n => clusters[n - 1].2.min(self.rightmost),
+            };
+            (n, end_x)
+        };
+        // index of the first of the trailing clusters starting within `budget` of the end
+        let tail = |budget: f32, min: usize| {
+            let n = clusters[min..]
+                .iter()
+                .rev()
+                .take_while(|(_, start, _)| within(self.rightmost - start, budget))
+                .count();
+            clusters.len() - n
+        };
+        let glyph_idx = |cluster: usize| clusters.get(cluster).map_or(self.glyphs.len(), |c| c.0);
+
+        let (head_n, head_end, tail_start) = match ellipsis {
+            Ellipsis::End(_) => {
+                let (n, end_x) = head(available);
+                (n, end_x, clusters.len())
+            }
+            Ellipsis::Start(_) => (0, 0.0, tail(available, 0)),
+            Ellipsis::Middle(_) => {
+                let (n, end_x) = head(available / 2.0);
+                (n, end_x, tail(available - end_x, n))
+            }
+        };
+
+        let (head_idx, tail_idx) = (glyph_idx(head_n), glyph_idx(tail_start));
+        let tail_x = clusters.get(tail_start).map_or(self.rightmost, |c| c.1);
+        let tail_shift = head_end + ellipsis_w - tail_x;
+        let mut tail_glyphs = self.glyphs.split_off(tail_idx);
+        tail_glyphs
+            .iter_mut()
+            .for_each(|sg| sg.glyph.position.x += tail_shift);
+
+        self.glyphs.truncate(head_idx);
+        // drop trailing whitespace before the ellipsis
+        self.glyphs.retain(|sg| sg.glyph.position.x < head_end);
+        ellipsis_glyphs
+            .iter_mut()
+            .for_each(|sg| sg.glyph.position.x += head_end);
+        self.glyphs.append(&mut ellipsis_glyphs);
+        self.glyphs.append(&mut tail_glyphs);
+
+        if tail_start < clusters.len() {
+            self.rightmost += tail_shift;
+            self.advance_width += tail_shift;
+        } else {
+            self.rightmost = head_end + ellipsis_w;
+            self.advance_width = self.rightmost;
+        }
+        self.word_gaps.clear();
+        self.hard_break = true;
     }
 }
 
+/// Reduce float errors, from differences of glyph positions, by using an approximate
+/// "<= bound" check.
+#[inline]
+fn within(x: f32, bound: f32) -> bool {
+    x < bound || approx::relative_eq!(x, bound, epsilon = 1e-3)
+}
+
+#[inline]
+pub(crate) fn same_cluster(a: &SectionGlyph, b: &SectionGlyph) -> bool {
+    a.section_index == b.section_index && a.byte_index == b.byte_index
+}
+
+/// Splits off & returns the leading clusters of the `word` within the `width`, at least one,
+/// leaving the rest in `word`. The returned head ends its line.
+///
+/// Returns `None` if the word has no visible clusters to leave.
+fn split_word(word: &mut Word, width: f32) -> Option<Word> {
+    // glyph index & x position of the cluster to split at
+    let mut split = None;
+    for (idx, sg) in word.glyphs.iter().enumerate().skip(1) {
+        let x = sg.glyph.position.x;
+        if same_cluster(&word.glyphs[idx - 1], sg) {
+            continue;
+        }
+        if !within(x, word.layout_width_no_trail) || (split.is_some() && !within(x, width)) {
+            break;
+        }
+        split = Some((idx, x));
+    }
+    let (idx, x) = split?;
+
+    let tail = word.glyphs.split_off(idx);
+    let head_glyphs = std::mem::replace(&mut word.glyphs, tail);
+    for sg in &mut word.glyphs {
+        sg.glyph.position.x -= x;
+    }
+    if let Some((sg, _)) = &mut word.hyphen {
+        sg.glyph.position.x -= x;
+    }
+    let head_start = std::mem::replace(
+        &mut word.start,
+        (word.glyphs[0].section_index, word.glyphs[0].byte_index),
+    );
+    word.layout_width -= x;
+    word.layout_width_no_trail -= x;
+
+    Some(Word {
+        glyphs: head_glyphs,
+        start: head_start,
+        layout_width: x,
+        layout_width_no_trail: x,
+        max_v_metrics: word.max_v_metrics.clone(),
+        hard_break: false,
+        hyphen: None,
+        tab: None,
+        line_end: true,
+    })
+}
+
+/// Returns `text` glyphs positioned from zero on the `baseline`, with their advance width.
+///
+/// Glyphs use the font, scale & section of the `like` glyph.
+fn ellipsis_glyphs<F: Font>(
+    fonts: &[F],
+    text: &str,
+    like: &SectionGlyph,
+    baseline: f32,
+) -> (Vec<SectionGlyph>, f32) {
+    let scale_font = fonts[like.font_id].as_scaled(like.glyph.scale);
+    let mut caret = 0.0;
+    let mut last_glyph_id = None;
+
+    let glyphs = text
+        .chars()
+        .map(|c| {
+            let id = scale_font.glyph_id(c);
+            if let Some(last) = last_glyph_id.replace(id) {
+                caret += scale_font.kern(last, id);
+            }
+            let glyph = id.with_scale_and_position(like.glyph.scale, point(caret, baseline));
+            caret += scale_font.h_advance(id);
+            SectionGlyph {
+                section_index: like.section_index,
+                byte_index: SectionGlyph::SYNTHETIC_BYTE_INDEX,
+                glyph,
+                font_id: like.font_id,
+                orientation: VerticalOrientation::Upright,
+            }
+        })
+        .collect();
+
+    (glyphs, caret)
+}
+
 /// `Line` iterator.
 ///
-/// Will iterator through `Word` until the next word would break the `width_bound`.
+/// Will iterator through `Word` until the next word would break the `width_bound`, less
+/// the line's `indents`, or a word marked as a `line_end`. The hyphen of a word counts
+/// towards the width only if the line would break there. Words ending in a tab advance the
+/// caret to the next of the `tab_stops`.
+///
+/// Rows of text beside `exclusions` are split into a line per segment of the width bound
+/// not excluded for the height of the row's first word. Segments too narrow for their
+/// first word are skipped.
 ///
 /// Note: Will always have at least one word, if possible, even if the word itself
-/// breaks the `width_bound`.
-pub(crate) struct Lines<'a, 'b, L, F, S>
-where
-    L: LineBreaker,
-    F: Font,
-    S: Iterator<Item = SectionText<'a>>,
-{
-    pub(crate) words: Peekable<Words<'a, 'b, L, F, S>>,
-    pub(crate) width_bound: f32,
+/// breaks the `width_bound`. Unless the `overflow_wrap` is `Anywhere`, breaking such words
+/// between clusters.
+pub(crate) struct Lines<'a, W: Iterator<Item = Word>> {
+    words: Lookahead<W>,
+    width_bound: f32,
+    tab_stops: TabStops,
+    indents: Indents,
+    exclusions: Arc<[Exclusion]>,
+    /// Indicates the next line is the first of its paragraph.
+    paragraph_start: bool,
+    /// Top of the current row from the top of the text.
+    top: f32,
+    /// Height of the lines of the current row so far.
+    row_height: f32,
+    /// Remaining segments of the current row in right-to-left order.
+    segments: Vec<(f32, f32)>,
+    /// Section texts, used to find decimal tab separators.
+    texts: &'a [&'a str],
+    overflow_wrap: OverflowWrap,
+}
+
+impl<'a, W: Iterator<Item = Word>> Lines<'a, W> {
+    #[inline]
+    pub(crate) fn new(
+        words: W,
+        width_bound: f32,
+        tab_stops: TabStops,
+        indents: Indents,
+        exclusions: Arc<[Exclusion]>,
+        texts: &'a [&'a str],
+    ) -> Self {
+        Self {
+            words: Lookahead {
+                words,
+                peeked: VecDeque::new(),
+            },
+            width_bound,
+            tab_stops,
+            indents,
+            // exclusions are only within finite bounds
+            exclusions: match width_bound.is_finite() {
+                true => exclusions,
+                false => Arc::default(),
+            },
+            paragraph_start: true,
+            top: 0.0,
+            row_height: 0.0,
+            segments: Vec::new(),
+            texts,
+            overflow_wrap: OverflowWrap::Normal,
+        }
+    }
+
+    /// Sets the width bound of following lines.
+    #[inline]
+    pub(crate) fn set_width_bound(&mut self, width_bound: f32) {
+        self.width_bound = width_bound;
+    }
+
+    /// Sets whether words too wide for a line of their own are broken.
+    #[inline]
+    pub(crate) fn set_overflow_wrap(&mut self, overflow_wrap: OverflowWrap) {
+        self.overflow_wrap = overflow_wrap;
+    }
+
+    /// Returns the next segment of the current row, or of following rows, with room for
+    /// a word of `width` & `height`. `None` if the row is not beside any exclusions.
+    fn next_segment(&mut self, width: f32, height: f32) -> Option<(f32, f32)> {
+        if self.exclusions.is_empty() || height <= 0.0 {
+            return None;
+        }
+        let indent = match self.paragraph_start {
+            true => self.indents.first_line,
+            false => self.indents.hanging,
+        };
+        let bounds = (
+            self.indents.left + indent,
+            self.width_bound - self.indents.right,
+        );
+
+        loop {
+            while let Some(segment) = self.segments.pop() {
+                if within(width, segment.1 - segment.0) {
+                    return Some(segment);
+                }
+            }
+            if self.row_height > 0.0 {
+                // the rest of the row is too narrow
+                self.end_row();
+            }
+
+            let band = (self.top, self.top + height);
+            let mut segments = exclusion::segments(&self.exclusions, bounds, band)?;
+            if !segments.iter().any(|s| within(width, s.1 - s.0)) {
+                // no room beside the exclusions, try below
+                self.top += height;
+                continue;
+            }
+            segments.reverse();
+            self.segments = segments;
+        }
+    }
+
+    /// Moves below the current row.
+    fn end_row(&mut self) {
+        self.top += self.row_height;
+        self.row_height = 0.0;
+        self.segments.clear();
+    }
+
+    /// Aligns the glyphs following a tab to its stop.
+    fn align_tab(&self, line: &mut Line, caret_x: &mut f32, segment: TabSegment) {
+        if segment.glyph_start == line.glyphs.len() {
+            return;
+        }
+        let shift = segment.shift(&line.glyphs, line.rightmost, self.texts);
+        for sg in &mut line.glyphs[segment.glyph_start..] {
+            sg.glyph.position.x += shift;
+        }
+        line.rightmost += shift;
+        *caret_x += shift;
+    }
+
+    /// Collects up to `max_lines` lines entirely within the `height_bound`, truncating the
+    /// last with the `ellipsis` if any further lines are dropped.
+    ///
+    /// Note: Will always have at least one line, if possible & `max_lines > 0`, even if
+    /// the line itself breaks the `height_bound`.
+    pub(crate) fn truncated<F: Font>(
+        self,
+        fonts: &[F],
+        ellipsis: Ellipsis,
+        max_lines: usize,
+        width_bound: f32,
+        height_bound: f32,
+    ) -> Vec<Line> {
+        let mut lines = Vec::new();
+        let mut dropped = false;
+
+        for line in self {
+            let bottom = line.top + line.line_height();
+            let in_bounds = bottom < height_bound || approx::relative_eq!(bottom, height_bound);
+            if lines.len() >= max_lines || (!in_bounds && !lines.is_empty()) {
+                dropped = true;
+                break;
+            }
+            lines.push(line);
+        }
+
+        if dropped {
+            if let Some(last) = lines.last_mut() {
+                let width_bound = width_bound - last.inset_width();
+                last.truncate(fonts, ellipsis, width_bound);
+            }
+        }
+        lines
+    }
 }
 
-impl<'a, L, F, S> Iterator for Lines<'a, '_, L, F, S>
-where
-    L: LineBreaker,
-    F: Font,
-    S: Iterator<Item = SectionText<'a>>,
-{
+impl<W: Iterator<Item = Word>> Iterator for Lines<'_, W> {
     type Item = Line;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let first = self.words.peek()?;
+        let first_width = match first.hard_break {
+            false => first.layout_width_no_trail,
+            true => first.layout_width,
+        };
+        let first_height = first.max_v_metrics.height();
+        let segment = self.next_segment(first_width, first_height);
+
         let mut caret = point(0.0, 0.0);
-        let mut line = Line::default();
+        let mut line = Line {
+            indents: self.indents,
+            paragraph_start: self.paragraph_start,
+            top: self.top,
+            segment,
+            ..Line::default()
+        };
+        let width_bound = match segment {
+            Some((left, right)) => right - left,
+            None => self.width_bound - line.inset_width(),
+        };
 
         let mut progressed = false;
+        let mut last_word_trails = false;
+        let mut hyphen: Option<(SectionGlyph, f32)> = None;
+        let mut tab_segment: Option<TabSegment> = None;
 
         while let Some(word) = self.words.peek() {
             // Drop trailing spaces when bounds-wrapping.
@@ -105,24 +653,49 @@ where
             };
 
             let word_right = caret.x + word_wrap_width;
+            // Leave room for a hyphen, in case the line breaks after the word
+            let hyphen_right = word_right + word.hyphen.as_ref().map_or(0.0, |(_, w)| *w);
             // Reduce float errors by using relative "<= width bound" check
-            let word_in_bounds =
-                word_right < self.width_bound || approx::relative_eq!(word_right, self.width_bound);
+            let mut word_in_bounds =
+                hyphen_right < width_bound || approx::relative_eq!(hyphen_right, width_bound);
+            if !word_in_bounds
+                && word.hyphen.is_some()
+                && !word.line_end
+                && within(word_right, width_bound)
+            {
+                // the hyphen is only needed if the line breaks inside the word here
+                let next_x = caret.x + word.layout_width;
+                word_in_bounds = self.words.continues_within(next_x, width_bound);
+            }
 
             // only if `progressed` means the first word is allowed to overlap the bounds
             if !word_in_bounds && progressed {
+                // breaking inside a hyphenated word
+                if let Some((sg, width)) = hyphen.take() {
+                    line.glyphs.push(sg);
+                    line.rightmost += width;
+                    caret.x = line.rightmost;
+                }
                 break;
             }
 
-            let word = self.words.next().unwrap();
+            let head = match self.overflow_wrap {
+                OverflowWrap::Anywhere if !word_in_bounds => {
+                    split_word(self.words.peek_mut().unwrap(), width_bound - caret.x)
+                }
+                _ => None,
+            };
+            let word = head.unwrap_or_else(|| self.words.next().unwrap());
+            if !progressed {
+                line.start = word.start;
+            }
             progressed = true;
 
             line.rightmost = word_right;
 
-            if (line.glyphs.is_empty() || !word.glyphs.is_empty())
-                && word.max_v_metrics.height() > line.max_v_metrics.height()
-            {
-                let diff_y = word.max_v_metrics.ascent - caret.y;
+            if line.glyphs.is_empty() || !word.glyphs.is_empty() {
+                let v_metrics = line.max_v_metrics.clone().max(word.max_v_metrics);
+                let diff_y = v_metrics.ascent - caret.y;
                 caret.y += diff_y;
 
                 // modify all smaller lined glyphs to occupy the new larger line
@@ -130,29 +703,292 @@ where
                     glyph.position.y += diff_y;
                 }
 
-                line.max_v_metrics = word.max_v_metrics;
+                line.max_v_metrics = v_metrics;
+            }
+
+            // a gap follows a word with visible content & trailing whitespace
+            if last_word_trails && !word.glyphs.is_empty() {
+                line.word_gaps.push(line.glyphs.len());
             }
+            last_word_trails =
+                word.layout_width_no_trail > 0.0 && word.layout_width > word.layout_width_no_trail;
 
             line.glyphs.extend(word.glyphs.into_iter().map(|mut sg| {
                 sg.glyph.position += caret;
                 sg
             }));
+            hyphen = word.hyphen.map(|(mut sg, width)| {
+                sg.glyph.position += caret;
+                (sg, width)
+            });
 
             caret.x += word.layout_width;
 
+            if let Some(space_width) = word.tab {
+                if let Some(segment) = tab_segment.take() {
+                    self.align_tab(&mut line, &mut caret.x, segment);
+                }
+                let (stop, align) = self.tab_stops.next(caret.x, space_width);
+                match align {
+                    TabAlign::Left => caret.x = stop,
+                    align => {
+                        tab_segment = Some(TabSegment {
+                            stop,
+                            align,
+                            glyph_start: line.glyphs.len(),
+                            start_x: caret.x,
+                        })
+                    }
+                }
+                // only whitespace after the last tab is stretched when justifying
+                line.word_gaps.clear();
+            }
+
             if word.hard_break {
+                line.hard_break = true;
+                break;
+            }
+            if word.line_end {
+                if let Some((sg, width)) = hyphen.take() {
+                    line.glyphs.push(sg);
+                    line.rightmost += width;
+                    caret.x = line.rightmost;
+                }
                 break;
             }
         }
+        if let Some(segment) = tab_segment {
+            self.align_tab(&mut line, &mut caret.x, segment);
+        }
+        line.advance_width = caret.x;
+        line.end = match self.words.peek() {
+            Some(word) => word.start,
+            None => self
+                .texts
+                .last()
+                .map_or((0, 0), |text| (self.texts.len() - 1, text.len())),
+        };
+        if progressed {
+            self.paragraph_start = line.hard_break;
+            self.row_height = self.row_height.max(line.line_height());
+            if line.hard_break || self.segments.is_empty() {
+                self.end_row();
+            }
+        }
 
         Some(line).filter(|_| progressed)
     }
 }
 
-impl<'a, L, F, S> FusedIterator for Lines<'a, '_, L, F, S>
-where
-    L: LineBreaker,
-    F: Font,
-    S: Iterator<Item = SectionText<'a>>,
-{
+impl<W: Iterator<Item = Word> + FusedIterator> FusedIterator for Lines<'_, W> {}
+
+/// `Word` iterator able to peek any number of words ahead.
+struct Lookahead<W: Iterator<Item = Word>> {
+    words: W,
+    peeked: VecDeque<Word>,
+}
+
+impl<W: Iterator<Item = Word>> Lookahead<W> {
+    #[inline]
+    fn peek_nth(&mut self, n: usize) -> Option<&mut Word> {
+        while self.peeked.len() <= n {
+            self.peeked.push_back(self.words.next()?);
+        }
+        self.peeked.get_mut(n)
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<&Word> {
+        self.peek_nth(0).map(|word| &*word)
+    }
+
+    #[inline]
+    fn peek_mut(&mut self) -> Option<&mut Word> {
+        self.peek_nth(0)
+    }
+
+    /// Returns whether the words following the next word of a hyphenated word, the first
+    /// starting at `x`, fit within the `width_bound` up to a break. In which case the line
+    /// may continue past the next word without breaking at its hyphen.
+    fn continues_within(&mut self, mut x: f32, width_bound: f32) -> bool {
+        let mut n = 1;
+        while let Some(word) = self.peek_nth(n) {
+            if within(x + word.line_end_width(), width_bound) {
+                return true;
+            }
+            let word_right = x + word.layout_width_no_trail;
+            if word.hyphen.is_none() || word.line_end || !within(word_right, width_bound) {
+                return false;
+            }
+            x += word.layout_width;
+            n += 1;
+        }
+        true
+    }
+}
+
+impl<W: Iterator<Item = Word>> Iterator for Lookahead<W> {
+    type Item = Word;
+
+    #[inline]
+    fn next(&mut self) -> Option<Word> {
+        self.peeked.pop_front().or_else(|| self.words.next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BuiltInLineBreaker, Ellipsis, FontFallbacks, GlyphPositioner, HorizontalAlign, Layout,
+        SectionGeometry, SectionText,
+    };
+    use ab_glyph::*;
+    use approx::assert_relative_eq;
+    use std::sync::LazyLock;
+
+    static A_FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap()
+    });
+
+    /// Returns the x position of each glyph laid out within `bound_w`.
+    fn glyph_xs(layout: &Layout<BuiltInLineBreaker>, text: &str, bound_w: f32) -> Vec<f32> {
+        layout
+            .calculate_glyphs(
+                &[&*A_FONT],
+                &SectionGeometry {
+                    bounds: (bound_w, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text,
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            )
+            .iter()
+            .map(|sg| sg.glyph.position.x)
+            .collect()
+    }
+
+    #[test]
+    fn justify_trailing_hard_break() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let bound_w = advance * 6.5;
+        let justified = Layout::default_wrap().h_align(HorizontalAlign::Justify);
+
+        // "aa bb " wraps & is stretched, "cc" ends the paragraph so is not
+        let xs = glyph_xs(&justified, "aa bb cc\n", bound_w);
+        assert_eq!(xs.len(), 8);
+        assert_relative_eq!(xs[3], advance * 4.5);
+        assert_relative_eq!(xs[4], advance * 5.5);
+        assert_eq!(xs[6..], [0.0, advance]);
+
+        // a single paragraph line followed by a trailing newline is left as is
+        let xs = glyph_xs(&justified, "aa bb\n", bound_w);
+        assert_eq!(xs, glyph_xs(&Layout::default_wrap(), "aa bb\n", bound_w));
+    }
+
+    #[test]
+    fn justify_single_word_line() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let bound_w = advance * 6.5;
+        let text = "aa bbbbb cc";
+
+        // "bbbbb " has no inter-word gaps to stretch
+        let left = glyph_xs(&Layout::default_wrap(), text, bound_w);
+        let justified = glyph_xs(
+            &Layout::default_wrap().h_align(HorizontalAlign::Justify),
+            text,
+            bound_w,
+        );
+        assert_eq!(justified.len(), 11);
+        assert_eq!(justified[3..9], left[3..9]);
+        assert_relative_eq!(justified[3], 0.0);
+    }
+
+    #[test]
+    fn empty_line_metrics() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let (glyphs, lines) = Layout::default_wrap().calculate_glyphs_and_lines(
+            &[&*A_FONT],
+            &FontFallbacks::default(),
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "aa\n\nbb",
+                scale: PxScale::from(20.0),
+                ..<_>::default()
+            }],
+        );
+        assert_eq!(glyphs.len(), 4);
+        assert_eq!(lines.len(), 3);
+
+        // the blank line has no glyphs but the height of its newline's font
+        let empty = &lines[1];
+        assert_eq!(empty.index, 1);
+        assert_eq!(empty.glyphs, 2..2);
+        assert_eq!(empty.start, (0, 3));
+        assert_eq!(empty.end, (0, 4));
+        assert_relative_eq!(empty.left, 0.0);
+        assert_relative_eq!(empty.ascent, sfont.ascent());
+        assert_relative_eq!(empty.descent, sfont.descent());
+        assert_relative_eq!(empty.advance_width, 0.0);
+        assert!(empty.hard_break);
+        assert!(!empty.rtl);
+
+        let line_height = sfont.height() + sfont.line_gap();
+        assert_relative_eq!(empty.baseline, lines[0].baseline + line_height);
+        assert_relative_eq!(lines[2].baseline, empty.baseline + line_height);
+        assert_eq!(lines[2].glyphs, 2..4);
+        assert_eq!(lines[2].start, (0, 4));
+    }
+
+    #[test]
+    fn ellipsis_line_metrics() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        let text = "aaaa bbbb cccc";
+        for ellipsis in [
+            Ellipsis::End("…"),
+            Ellipsis::Start("…"),
+            Ellipsis::Middle("…"),
+        ] {
+            let (glyphs, lines) = Layout::default_single_line()
+                .ellipsis(ellipsis)
+                .calculate_glyphs_and_lines(
+                    &[&*A_FONT],
+                    &FontFallbacks::default(),
+                    &SectionGeometry {
+                        bounds: (advance * 8.5, f32::INFINITY),
+                        ..<_>::default()
+                    },
+                    &[SectionText {
+                        text,
+                        scale: PxScale::from(20.0),
+                        ..<_>::default()
+                    }],
+                );
+            assert_eq!(lines.len(), 1, "{ellipsis:?}");
+            let line = &lines[0];
+
+            // the line's glyphs include the ellipsis & its text range the removed text
+            assert_eq!(line.glyphs, 0..glyphs.len(), "{ellipsis:?}");
+            assert_eq!(
+                glyphs.iter().filter(|sg| sg.is_synthetic()).count(),
+                1,
+                "{ellipsis:?}"
+            );
+            assert_eq!(line.start, (0, 0), "{ellipsis:?}");
+            assert_eq!(line.end, (0, text.len()), "{ellipsis:?}");
+            assert!(line.hard_break, "{ellipsis:?}");
+
+            // & its advance width is the truncated width, within the bound
+            let last = glyphs.last().unwrap();
+            assert_relative_eq!(
+                line.left + line.advance_width,
+                last.glyph.position.x + sfont.h_advance(last.glyph.id),
+                epsilon = 1e-3
+            );
+            assert!(line.advance_width <= advance * 8.5, "{ellipsis:?}");
+        }
+    }
 }
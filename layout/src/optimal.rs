@@ -0,0 +1,234 @@
+use crate::{words::Word, Indents};
+use std::vec;
+
+/// How [`Layout::Wrap`](enum.Layout.html#variant.Wrap) chooses where lines break.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// let layout = Layout::default()
+///     .h_align(HorizontalAlign::Justify)
+///     .break_strategy(BreakStrategy::Optimal);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum BreakStrategy {
+    /// Fills each line with as many words as fit before moving to the next.
+    #[default]
+    Greedy,
+    /// Chooses the breaks of each paragraph together, as in the Knuth-Plass algorithm,
+    /// minimising the total demerits of its lines. Lines are penalised by how much their
+    /// inter-word whitespace would need to stretch to fill the width bound & by breaking
+    /// at hyphens. The last line of each paragraph is free to be short.
+    ///
+    /// Produces more even lines than greedy filling, particularly for justified text.
+    /// Paragraphs of more than 2000 words or containing tabs, and text flowing around
+    /// exclusions, are filled greedily.
+    Optimal,
+}
+
+/// Paragraphs longer than this are filled greedily to bound the cost.
+const MAX_PARAGRAPH_WORDS: usize = 2000;
+/// Demerits added to every line, favouring fewer lines.
+const LINE_PENALTY: f32 = 10.0;
+/// Demerits added to lines ending at a hyphen.
+const HYPHEN_PENALTY: f32 = 50.0;
+/// Badness of lines that cannot stretch to fill the width bound.
+const MAX_BADNESS: f32 = 10_000.0;
+
+/// `Word` iterator marking the line breaks of each paragraph chosen by
+/// [`BreakStrategy::Optimal`], see `Word::line_end`.
+pub(crate) struct OptimalBreaks<W: Iterator<Item = Word>> {
+    words: W,
+    /// Widths available to the first & following lines of each paragraph, `None`
+    /// for greedy filling.
+    widths: Option<(f32, f32)>,
+    /// Remaining words of the current paragraph.
+    paragraph: vec::IntoIter<Word>,
+}
+
+impl<W: Iterator<Item = Word>> OptimalBreaks<W> {
+    #[inline]
+    pub(crate) fn new(
+        words: W,
+        strategy: BreakStrategy,
+        width_bound: f32,
+        indents: Indents,
+    ) -> Self {
+        let widths = Some(width_bound - indents.left - indents.right)
+            .filter(|w| strategy == BreakStrategy::Optimal && w.is_finite())
+            .map(|w| (w - indents.first_line, w - indents.hanging));
+        Self {
+            words,
+            widths,
+            paragraph: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<W: Iterator<Item = Word>> Iterator for OptimalBreaks<W> {
+    type Item = Word;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(word) = self.paragraph.next() {
+            return Some(word);
+        }
+        let Some(widths) = self.widths else {
+            return self.words.next();
+        };
+
+        let mut paragraph = vec![];
+        for word in &mut self.words {
+            let hard_break = word.hard_break;
+            paragraph.push(word);
+            if hard_break {
+                break;
+            }
+        }
+        if paragraph.len() <= MAX_PARAGRAPH_WORDS && paragraph.iter().all(|w| w.tab.is_none()) {
+            mark_breaks(&mut paragraph, widths);
+        }
+        self.paragraph = paragraph.into_iter();
+        self.paragraph.next()
+    }
+}
+
+/// Marks the `line_end` words of the paragraph breaks with the least total demerits.
+fn mark_breaks(words: &mut [Word], (first_width, width): (f32, f32)) {
+    if words.len() < 2 {
+        return;
+    }
+
+    // advance widths of all words before each index, and their trailing whitespace
+    let mut advance = Vec::with_capacity(words.len() + 1);
+    let mut whitespace = Vec::with_capacity(words.len() + 1);
+    advance.push(0.0);
+    whitespace.push(0.0);
+    for word in words.iter() {
+        advance.push(advance.last().unwrap() + word.layout_width);
+        whitespace
+            .push(whitespace.last().unwrap() + word.layout_width - word.layout_width_no_trail);
+    }
+
+    let last = words.len() - 1;
+    // least demerits of the paragraph up to each index & the start of its last line
+    let mut best = vec![(f32::INFINITY, 0); words.len() + 1];
+    best[0].0 = 0.0;
+
+    for end in 0..words.len() {
+        let word = &words[end];
+        let end_width = match word.hard_break {
+            false => word.layout_width_no_trail,
+            true => word.layout_width,
+        } + word.hyphen.as_ref().map_or(0.0, |(_, w)| *w);
+
+        for start in (0..=end).rev() {
+            let available = match start {
+                0 => first_width,
+                _ => width,
+            };
+            let line_width = advance[end] - advance[start] + end_width;
+            let slack = available - line_width;
+            let fits = slack >= 0.0 || approx::relative_eq!(line_width, available);
+            // lines of a single word may overflow
+            if !fits && start != end {
+                // only a wider first line may still fit
+                match start > 0 && first_width > width {
+                    true => continue,
+                    false => break,
+                }
+            }
+            if best[start].0.is_infinite() {
+                continue;
+            }
+
+            let badness = if end == last {
+                0.0
+            } else if !fits {
+                MAX_BADNESS
+            } else {
+                let stretch = whitespace[end] - whitespace[start];
+                match stretch > 0.0 {
+                    true => (100.0 * (slack / stretch).powi(3)).min(MAX_BADNESS),
+                    false if slack < 1e-3 => 0.0,
+                    false => MAX_BADNESS,
+                }
+            };
+            let mut demerits = (LINE_PENALTY + badness).powi(2);
+            if end != last && word.hyphen.is_some() {
+                demerits += HYPHEN_PENALTY.powi(2);
+            }
+
+            let total = best[start].0 + demerits;
+            if total < best[end + 1].0 {
+                best[end + 1] = (total, start);
+            }
+        }
+    }
+
+    let mut end = words.len();
+    while end > 0 {
+        words[end - 1].line_end = true;
+        end = best[end].1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::words::VMetrics;
+
+    fn word(width: f32, trail: f32) -> Word {
+        Word {
+            glyphs: vec![],
+            start: (0, 0),
+            layout_width: width + trail,
+            layout_width_no_trail: width,
+            max_v_metrics: VMetrics::default(),
+            hard_break: false,
+            hyphen: None,
+            tab: None,
+            line_end: false,
+        }
+    }
+
+    fn line_ends(words: &[Word]) -> Vec<usize> {
+        words
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.line_end)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    #[test]
+    fn even_lines() {
+        // greedy fills "aaaa bb cc", leaving "dddddd" alone on a loose 2nd line
+        let mut words = vec![
+            word(40.0, 10.0),
+            word(20.0, 10.0),
+            word(20.0, 10.0),
+            word(60.0, 10.0),
+            word(50.0, 0.0),
+        ];
+        words[4].hard_break = true;
+
+        mark_breaks(&mut words, (100.0, 100.0));
+        assert_eq!(line_ends(&words), [1, 3, 4]);
+
+        // a wide first line fits all
+        for w in &mut words {
+            w.line_end = false;
+        }
+        mark_breaks(&mut words, (250.0, 100.0));
+        assert_eq!(line_ends(&words), [4]);
+    }
+
+    #[test]
+    fn overflowing_words() {
+        let mut words = vec![word(150.0, 10.0), word(20.0, 0.0)];
+        words[1].hard_break = true;
+
+        mark_breaks(&mut words, (100.0, 100.0));
+        assert_eq!(line_ends(&words), [0, 1]);
+    }
+}
@@ -1,6 +1,6 @@
-use crate::FontId;
+use crate::{FontId, VerticalOrientation};
 use ab_glyph::*;
-use std::f32;
+use std::{f32, ops::Range};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SectionGeometry {
@@ -32,6 +32,17 @@ pub struct SectionText<'a> {
     /// It must be a valid id in the `FontMap` used for layout calls.
     /// The default `FontId(0)` should always be valid.
     pub font_id: FontId,
+    /// Height of lines containing this text. Defaults to [`LineHeight::Normal`].
+    pub line_height: LineHeight,
+    /// Extra pixel spacing added after each character, may be negative. Defaults to 0.
+    pub letter_spacing: f32,
+    /// Extra pixel spacing added after each space, in addition to `letter_spacing`,
+    /// may be negative. Defaults to 0.
+    pub word_spacing: f32,
+    /// Inline object laid out in place of the text, if any. Defaults to `None`.
+    pub placeholder: Option<Placeholder>,
+    /// Vertical offset from the line's baseline. Defaults to [`BaselineShift::Baseline`].
+    pub baseline_shift: BaselineShift,
 }
 
 impl Default for SectionText<'static> {
@@ -41,6 +52,195 @@ impl Default for SectionText<'static> {
             text: "",
             scale: PxScale::from(16.0),
             font_id: FontId::default(),
+            line_height: LineHeight::default(),
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
+            placeholder: None,
+            baseline_shift: BaselineShift::default(),
+        }
+    }
+}
+
+/// Inline object, like an icon or emoji image, laid out in place of a section's text.
+///
+/// Placeholders are laid out as unbreakable words of their `width`, with line break
+/// opportunities either side, & their height is included in the line height. Each is
+/// returned as a single [`SectionGlyph`] with the [`SectionGlyph::PLACEHOLDER_ID`] glyph id,
+/// positioned at its left on the baseline, see [`rect`](#method.rect). The section text
+/// is not rendered, `"\u{FFFC}"` the object replacement character is a good choice.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::{ab_glyph::*, *};
+/// # fn main() -> Result<(), InvalidFont> {
+/// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+/// let icon = Placeholder {
+///     width: 16.0,
+///     height: 16.0,
+///     baseline_offset: 2.0,
+/// };
+/// let glyphs = Layout::default().calculate_glyphs(
+///     &[font],
+///     &SectionGeometry::default(),
+///     &[
+///         SectionText {
+///             text: "Press ",
+///             ..<_>::default()
+///         },
+///         SectionText {
+///             text: "\u{FFFC}",
+///             placeholder: Some(icon),
+///             ..<_>::default()
+///         },
+///     ],
+/// );
+///
+/// let icon_glyph = glyphs.iter().find(|sg| sg.is_placeholder()).unwrap();
+/// let rect = icon.rect(icon_glyph);
+/// assert_eq!(rect.width(), 16.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Placeholder {
+    /// Pixel width.
+    pub width: f32,
+    /// Pixel height.
+    pub height: f32,
+    /// Distance of the bottom of the placeholder below the baseline, e.g. 0 to sit on the
+    /// baseline or positive to descend like text descenders.
+    pub baseline_offset: f32,
+}
+
+impl Placeholder {
+    /// Returns the screen rectangle of the placeholder laid out as `glyph`.
+    #[inline]
+    pub fn rect(&self, glyph: &SectionGlyph) -> Rect {
+        let Point { x, y } = glyph.glyph.position;
+        Rect {
+            min: point(x, y + self.baseline_offset - self.height),
+            max: point(x + self.width, y + self.baseline_offset),
+        }
+    }
+}
+
+/// Height of a line of text, similar to the CSS `line-height` property.
+///
+/// Lines are as high as needed to include the line height of all their text. Custom heights distribute the
+/// difference to the font's ascent to descent height equally above & below the text.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LineHeight {
+    /// Font defined line height, ascent to descent plus the line gap.
+    #[default]
+    Normal,
+    /// Absolute line height in pixels.
+    Px(f32),
+    /// Multiple of the text's pixel scale, e.g. `1.5`.
+    Multiplier(f32),
+}
+
+impl LineHeight {
+    /// Returns the space added above & below the font's ascent to descent height.
+    ///
+    /// `Normal` line heights add no space above and the line gap below.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+    /// let scaled = font.as_scaled(20.0);
+    /// let half_leading = LineHeight::Px(30.0).half_leading(&scaled);
+    /// assert!((half_leading - (30.0 - scaled.ascent() + scaled.descent()) / 2.0).abs() < 1e-5);
+    /// ```
+    #[inline]
+    pub fn half_leading<F: Font, SF: ScaleFont<F>>(self, scale_font: &SF) -> f32 {
+        let height = match self {
+            LineHeight::Normal => return 0.0,
+            LineHeight::Px(px) => px,
+            LineHeight::Multiplier(m) => m * scale_font.scale().y,
+        };
+        (height - scale_font.ascent() + scale_font.descent()) / 2.0
+    }
+}
+
+/// Vertical offset of text from the line's baseline, similar to the CSS `baseline-shift`
+/// property, e.g. for exponents, footnote markers or chemical formulas.
+///
+/// Shifted text moves its ascent & descent with it, so lines grow to include it.
+/// [`Layout::Vertical`](enum.Layout.html#variant.Vertical) text is shifted right instead.
+/// The text scale is unchanged, superscripts & subscripts are often also given a smaller scale.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::{ab_glyph::*, *};
+/// # fn main() -> Result<(), InvalidFont> {
+/// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+/// let glyphs = Layout::default().calculate_glyphs(
+///     &[font],
+///     &SectionGeometry::default(),
+///     &[
+///         SectionText {
+///             text: "x",
+///             ..<_>::default()
+///         },
+///         SectionText {
+///             text: "2",
+///             baseline_shift: BaselineShift::Superscript,
+///             ..<_>::default()
+///         },
+///     ],
+/// );
+///
+/// // the superscript is raised above the baseline
+/// assert!(glyphs[1].glyph.position.y < glyphs[0].glyph.position.y);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BaselineShift {
+    /// Text sits on the line's baseline.
+    #[default]
+    Baseline,
+    /// Absolute shift in pixels, positive values raise the text & negative values lower it.
+    Px(f32),
+    /// Raised by the font's OS/2 table superscript y offset.
+    ///
+    /// Fonts must provide [`Font::font_data`], fonts without an OS/2 table are raised by
+    /// a third of the text scale.
+    Superscript,
+    /// Lowered by the font's OS/2 table subscript y offset.
+    ///
+    /// Fonts must provide [`Font::font_data`], fonts without an OS/2 table are lowered by
+    /// a fifth of the text scale.
+    Subscript,
+}
+
+impl BaselineShift {
+    /// Returns the pixel distance text is raised above the baseline, negative if lowered.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+    /// let scaled = font.as_scaled(20.0);
+    /// assert_eq!(BaselineShift::Px(-3.0).px(&scaled), -3.0);
+    /// assert!(BaselineShift::Superscript.px(&scaled) > 0.0);
+    /// assert!(BaselineShift::Subscript.px(&scaled) < 0.0);
+    /// ```
+    #[inline]
+    pub fn px<F: Font, SF: ScaleFont<F>>(self, scale_font: &SF) -> f32 {
+        let offsets = || crate::font::script_offsets(scale_font.font().font_data());
+        match self {
+            BaselineShift::Baseline => 0.0,
+            BaselineShift::Px(px) => px,
+            BaselineShift::Superscript => match offsets() {
+                Some((_, sup)) => f32::from(sup) * scale_font.v_scale_factor(),
+                None => scale_font.scale().y / 3.0,
+            },
+            BaselineShift::Subscript => match offsets() {
+                Some((sub, _)) => -f32::from(sub) * scale_font.v_scale_factor(),
+                None => -scale_font.scale().y / 5.0,
+            },
         }
     }
 }
@@ -75,4 +275,65 @@ pub struct SectionGlyph {
     pub glyph: Glyph,
     /// Font id.
     pub font_id: FontId,
+    /// Orientation the glyph is drawn in. [`VerticalOrientation::Sideways`] glyphs, like
+    /// Latin in [`Layout::Vertical`](enum.Layout.html#variant.Vertical) text, are drawn
+    /// rotated 90° clockwise about the glyph position.
+    pub orientation: VerticalOrientation,
+}
+
+impl SectionGlyph {
+    /// `byte_index` of synthetic glyphs not derived from the section text, like an
+    /// overflow [`Ellipsis`](enum.Ellipsis.html). Their `section_index` is that of the
+    /// adjacent text.
+    pub const SYNTHETIC_BYTE_INDEX: usize = usize::MAX;
+
+    /// Glyph id of [`Placeholder`] glyphs, which have no outline.
+    pub const PLACEHOLDER_ID: GlyphId = GlyphId(u16::MAX);
+
+    /// Returns `true` if the glyph is not derived from the section text, like an overflow
+    /// [`Ellipsis`](enum.Ellipsis.html).
+    #[inline]
+    pub fn is_synthetic(&self) -> bool {
+        self.byte_index == Self::SYNTHETIC_BYTE_INDEX
+    }
+
+    /// Returns `true` if the glyph is laid out for a [`Placeholder`], see
+    /// [`Placeholder::rect`].
+    #[inline]
+    pub fn is_placeholder(&self) -> bool {
+        self.glyph.id == Self::PLACEHOLDER_ID
+    }
+}
+
+/// Metrics of a laid out line of [`SectionGlyph`]s, see
+/// [`GlyphPositioner::calculate_glyphs_and_lines`](trait.GlyphPositioner.html#method.calculate_glyphs_and_lines).
+///
+/// Lines either side of an [`Exclusion`](enum.Exclusion.html) share a row, with the same top.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionLine {
+    /// The index of the line, in top-to-bottom then left-to-right order.
+    pub index: usize,
+    /// The range of the line's glyphs in the laid out [`SectionGlyph`]s.
+    pub glyphs: Range<usize>,
+    /// The `(section_index, byte_index)` of the line's first character.
+    pub start: (usize, usize),
+    /// The `(section_index, byte_index)` following the line's last character, including
+    /// any trailing whitespace & line break.
+    pub end: (usize, usize),
+    /// Screen x position of the line's left edge, from which `advance_width` extends.
+    pub left: f32,
+    /// Screen y position of the baseline.
+    pub baseline: f32,
+    /// Max ascent of the line's fonts above the baseline, including any extra line height.
+    pub ascent: f32,
+    /// Max descent of the line's fonts below the baseline, typically negative.
+    pub descent: f32,
+    /// Pixel advance width of the line including trailing whitespace.
+    pub advance_width: f32,
+    /// Indicates the line ended in a hard break, or the end of all text, rather than
+    /// wrapping.
+    pub hard_break: bool,
+    /// Indicates the line's paragraph is right-to-left. Lines containing right-to-left
+    /// text have their glyphs in visual, left-to-right, order rather than logical order.
+    pub rtl: bool,
 }
@@ -0,0 +1,900 @@
+use crate::{
+    bidi::{self, Bidi},
+    builtin::{reposition, reposition_lines},
+    characters::{self, valid_section, Character},
+    linebreak::{EolLineBreak, LineBreaker},
+    words::{IntrinsicWidths, MeasuredWords, WhiteSpace, Words},
+    BuiltInLineBreaker, FallbackResolver, FontFallbacks, FontId, GlyphChange, GlyphPositioner,
+    Layout, PreviousLayout, SectionGeometry, SectionGlyph, SectionLine, SectionText, ToSectionText,
+};
+use ab_glyph::*;
+use rustybuzz::{Direction, UnicodeBuffer};
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::{Enumerate, FusedIterator},
+    ptr,
+    sync::Arc,
+    vec,
+};
+
+/// [`Layout`](enum.Layout.html) wrapper that shapes text using the fonts' OpenType tables.
+///
+/// Shaping handles ligatures, contextual forms, e.g. Arabic joining, combining mark placement,
+/// GPOS kerning & the reordering required by scripts like Devanagari. Shaped glyphs are
+/// then wrapped & aligned exactly as the inner layout does.
+///
+/// Glyphs shaped from multiple characters, like ligatures or a base character with its marks,
+/// share the `SectionGlyph::byte_index` of the cluster's first character.
+/// Each section is shaped separately, so ligatures & kerning don't apply across sections,
+/// e.g. between differently styled text.
+///
+/// Fonts must provide [`Font::font_data`], only the first face of font collections is used.
+/// Sections using fonts that fail to parse for shaping are laid out unshaped.
+/// [`Layout::Vertical`](enum.Layout.html#variant.Vertical) layouts are not shaped.
+///
+/// The fonts used are parsed for shaping by each calculation, unless parsed up front with
+/// [`with_fonts`](#method.with_fonts).
+///
+/// Requires the `shaping` feature.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// let layout = Layout::default().h_align(HorizontalAlign::Center).shaped();
+/// ```
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ShapedLayout<'f, L: LineBreaker>(pub Layout<L>, ShapingFaces<'f>);
+
+impl Default for ShapedLayout<'static, BuiltInLineBreaker> {
+    #[inline]
+    fn default() -> Self {
+        Layout::default().shaped()
+    }
+}
+
+impl<L: LineBreaker> From<Layout<L>> for ShapedLayout<'static, L> {
+    #[inline]
+    fn from(layout: Layout<L>) -> Self {
+        Self(layout, ShapingFaces::default())
+    }
+}
+
+impl<L: LineBreaker> Layout<L> {
+    /// Returns a [`ShapedLayout`](struct.ShapedLayout.html) with this layout's wrapping
+    /// & alignment.
+    #[inline]
+    pub fn shaped(self) -> ShapedLayout<'static, L> {
+        ShapedLayout::from(self)
+    }
+}
+
+impl<L: LineBreaker> ShapedLayout<'_, L> {
+    /// Returns an identical layout that reuses faces parsed for shaping from `fonts` now,
+    /// rather than parsing the fonts used by each calculation.
+    ///
+    /// Faces are used for calculations with the same fonts, i.e. fonts with the same
+    /// [`Font::font_data`] at each `FontId`. Other fonts are parsed as needed.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// # fn main() -> Result<(), InvalidFont> {
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf"))?;
+    /// let fonts = [font];
+    /// let layout = Layout::default().shaped().with_fonts(&fonts);
+    ///
+    /// // faces are parsed once for both calculations
+    /// for text in ["office", "affine"] {
+    ///     let glyphs = layout.calculate_glyphs(
+    ///         &fonts,
+    ///         &SectionGeometry::default(),
+    ///         &[SectionText {
+    ///             text,
+    ///             ..<_>::default()
+    ///         }],
+    ///     );
+    /// #   assert!(!glyphs.is_empty());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_fonts<F: Font>(self, fonts: &[F]) -> ShapedLayout<'_, L> {
+        ShapedLayout(self.0, ShapingFaces::new(fonts))
+    }
+}
+
+/// Faces parsed for shaping up front, by font id, along with the font data they were
+/// parsed from.
+///
+/// Faces only save re-parsing the fonts, so don't affect layout equality or hashing.
+#[derive(Clone, Default)]
+pub(crate) struct ShapingFaces<'f>(Arc<[(&'f [u8], Option<rustybuzz::Face<'f>>)]>);
+
+impl<'f> ShapingFaces<'f> {
+    fn new<F: Font>(fonts: &'f [F]) -> Self {
+        Self(
+            fonts
+                .iter()
+                .map(|font| {
+                    let data = font.font_data();
+                    (data, rustybuzz::Face::from_slice(data, 0))
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the face of `font_id`, if parsed from the same font `data`.
+    #[inline]
+    fn get(&self, font_id: FontId, data: &[u8]) -> Option<&rustybuzz::Face<'f>> {
+        self.0
+            .get(font_id.0)
+            .filter(|(face_data, _)| ptr::eq(*face_data, data))
+            .and_then(|(_, face)| face.as_ref())
+    }
+}
+
+impl fmt::Debug for ShapingFaces<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ShapingFaces").field(&self.0.len()).finish()
+    }
+}
+
+impl PartialEq for ShapingFaces<'_> {
+    #[inline]
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ShapingFaces<'_> {}
+
+impl Hash for ShapingFaces<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl<L: LineBreaker> GlyphPositioner for ShapedLayout<'_, L> {
+    #[inline]
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_glyphs_with_fallbacks(fonts, &FontFallbacks::default(), geometry, sections)
+    }
+
+    #[inline]
+    fn calculate_glyphs_with_fallbacks<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections)
+            .0
+    }
+
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        if let Layout::Vertical { .. } = self.0 {
+            return self
+                .0
+                .calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections);
+        }
+
+        let bidi = Bidi::new(sections.iter().map(|s| s.to_section_text()));
+
+        let words = ShapedCharacters::new(
+            fonts,
+            &self.1,
+            sections.iter().map(|s| s.to_section_text()),
+            self.0.get_word_breaker(),
+            bidi.as_ref(),
+            fallbacks,
+        )
+        .words(self.0.get_white_space());
+        let texts: Vec<_> = sections.iter().map(|s| s.to_section_text().text).collect();
+
+        self.0
+            .layout_words(fonts, geometry, words, &texts, bidi.as_ref())
+    }
+
+    #[inline]
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        self.0.bounds_rect(geometry)
+    }
+
+    #[inline]
+    fn recalculate_glyphs<F, S, P>(
+        &self,
+        previous: P,
+        change: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        self.recalculate_glyphs_with_fallbacks(
+            previous,
+            change,
+            fonts,
+            &FontFallbacks::default(),
+            geometry,
+            sections,
+        )
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn recalculate_glyphs_with_fallbacks<F, S, P>(
+        &self,
+        previous: P,
+        change: GlyphChange,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        match change {
+            GlyphChange::Geometry(old) if old.bounds == geometry.bounds => {
+                reposition(previous, &old, geometry)
+            }
+            GlyphChange::Scale(factor) => self
+                .0
+                .rescale(previous, None, factor, geometry, sections)
+                .map(|(glyphs, _)| glyphs)
+                .unwrap_or_else(|| {
+                    self.calculate_glyphs_with_fallbacks(fonts, fallbacks, geometry, sections)
+                }),
+            _ => self.calculate_glyphs_with_fallbacks(fonts, fallbacks, geometry, sections),
+        }
+    }
+
+    fn measure_words<F, S>(
+        &self,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        sections: &[S],
+    ) -> Option<MeasuredWords>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        if let Layout::Vertical { .. } = self.0 {
+            return None;
+        }
+
+        let bidi = Bidi::new(sections.iter().map(|s| s.to_section_text()));
+        let words = ShapedCharacters::new(
+            fonts,
+            &self.1,
+            sections.iter().map(|s| s.to_section_text()),
+            self.0.get_word_breaker(),
+            bidi.as_ref(),
+            fallbacks,
+        )
+        .words(self.0.get_white_space())
+        .collect();
+
+        Some(MeasuredWords { words, bidi })
+    }
+
+    #[inline]
+    fn intrinsic_widths<S>(&self, words: &MeasuredWords, sections: &[S]) -> Option<IntrinsicWidths>
+    where
+        S: ToSectionText,
+    {
+        self.0.intrinsic_widths(words, sections)
+    }
+
+    #[inline]
+    fn height_for_width<S>(&self, words: &MeasuredWords, width: f32, sections: &[S]) -> Option<f32>
+    where
+        S: ToSectionText,
+    {
+        self.0.height_for_width(words, width, sections)
+    }
+
+    /// Bounds changes re-use any previous words avoiding re-shaping. Uniform scale changes
+    /// to unbounded layouts scale the previous glyphs. Text changes reshape from the line
+    /// the built-in layout would relay out from, reusing the previous lines before it.
+    #[allow(clippy::float_cmp)]
+    fn recalculate_glyphs_and_lines<F, S, P>(
+        &self,
+        previous: PreviousLayout<'_, P>,
+        change: GlyphChange,
+        fonts: &[F],
+        fallbacks: &FontFallbacks,
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        match change {
+            GlyphChange::Geometry(old) if old.bounds == geometry.bounds => (
+                reposition(previous.glyphs, &old, geometry),
+                reposition_lines(previous.lines, &old, geometry),
+            ),
+            GlyphChange::Geometry(_) if previous.words.is_some() => {
+                let measured = previous.words.unwrap();
+                let texts: Vec<_> = sections.iter().map(|s| s.to_section_text().text).collect();
+                self.0.layout_words(
+                    fonts,
+                    geometry,
+                    measured.words.iter().cloned(),
+                    &texts,
+                    measured.bidi.as_ref(),
+                )
+            }
+            GlyphChange::TextAppended(changed) | GlyphChange::TextEdited(changed) => self
+                .0
+                .relayout(
+                    previous.glyphs,
+                    previous.lines,
+                    changed,
+                    geometry,
+                    sections,
+                    |layout, geometry, texts| {
+                        ShapedLayout(layout.clone(), self.1.clone())
+                            .calculate_glyphs_and_lines(fonts, fallbacks, geometry, texts)
+                    },
+                )
+                .unwrap_or_else(|| {
+                    self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections)
+                }),
+            GlyphChange::Scale(factor) => self
+                .0
+                .rescale(
+                    previous.glyphs,
+                    Some(previous.lines),
+                    factor,
+                    geometry,
+                    sections,
+                )
+                .unwrap_or_else(|| {
+                    self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections)
+                }),
+            _ => self.calculate_glyphs_and_lines(fonts, fallbacks, geometry, sections),
+        }
+    }
+}
+
+/// Glyphs shaped from a cluster of characters.
+struct Cluster {
+    /// Byte index of the first character of the cluster.
+    start: usize,
+    font_id: FontId,
+    /// Glyphs in visual order positioned relative to the cluster's left edge.
+    glyphs: Vec<(GlyphId, Point)>,
+    /// Shaped advance width of the cluster, `None` if unshaped.
+    advance: Option<f32>,
+}
+
+/// `Character` iterator of shaped glyphs.
+///
+/// Each section is shaped in runs of consistent bidi direction. Clusters are produced in
+/// logical order, with a cluster's glyphs advancing together.
+pub(crate) struct ShapedCharacters<'a, 'b, L, F, S>
+where
+    L: LineBreaker,
+    F: Font,
+    S: Iterator<Item = SectionText<'a>>,
+{
+    fonts: &'b [F],
+    /// Faces parsed up front, see `ShapedLayout::with_fonts`.
+    parsed: &'b ShapingFaces<'b>,
+    /// Lazily parsed, or up front parsed, shaping faces by font id.
+    faces: Vec<Option<Option<Cow<'b, rustybuzz::Face<'b>>>>>,
+    section_text: Enumerate<S>,
+    line_breaker: L,
+    bidi: Option<&'b Bidi>,
+    fallbacks: &'b FontFallbacks,
+    section_chars: vec::IntoIter<Character<'b, F>>,
+}
+
+impl<'a, 'b, L, F, S> ShapedCharacters<'a, 'b, L, F, S>
+where
+    L: LineBreaker,
+    F: Font,
+    S: Iterator<Item = SectionText<'a>>,
+{
+    /// Returns a new `ShapedCharacters` iterator.
+    ///
+    /// `bidi` levels are used to shape right-to-left runs.
+    /// Runs are also split by the font used, considering `fallbacks`.
+    pub(crate) fn new(
+        fonts: &'b [F],
+        parsed: &'b ShapingFaces<'b>,
+        section_text: S,
+        line_breaker: L,
+        bidi: Option<&'b Bidi>,
+        fallbacks: &'b FontFallbacks,
+    ) -> Self {
+        Self {
+            fonts,
+            parsed,
+            faces: fonts.iter().map(|_| None).collect(),
+            section_text: section_text.enumerate(),
+            line_breaker,
+            bidi,
+            fallbacks,
+            section_chars: Vec::new().into_iter(),
+        }
+    }
+
+    /// Wraps into a `Words` iterator laying out spaces according to `white_space`.
+    pub(crate) fn words(self, white_space: WhiteSpace) -> Words<'b, F, Self> {
+        Words::new(self, white_space)
+    }
+
+    fn shape_section(
+        &mut self,
+        section_index: usize,
+        section: SectionText<'a>,
+    ) -> Vec<Character<'b, F>> {
+        if let Some(placeholder) = section.placeholder {
+            return vec![characters::placeholder_character(
+                self.fonts,
+                section_index,
+                &section,
+                placeholder,
+            )];
+        }
+        let SectionText {
+            text,
+            scale,
+            font_id,
+            line_height,
+            baseline_shift,
+            ..
+        } = section;
+        let fonts = self.fonts;
+        let mut fallback = FallbackResolver::new(self.fallbacks, font_id);
+
+        let mut clusters = Vec::new();
+        for (start, end, rtl, font_id) in runs(text, section_index, self.bidi, |idx| {
+            fallback.font_id(fonts, text, idx)
+        }) {
+            let run = &text[start..end];
+            let scale_font = fonts[font_id].as_scaled(scale);
+            let parsed = self.parsed;
+            let face = self.faces[font_id.0]
+                .get_or_insert_with(|| {
+                    let data = fonts[font_id].font_data();
+                    match parsed.get(font_id, data) {
+                        Some(face) => Some(Cow::Borrowed(face)),
+                        None => rustybuzz::Face::from_slice(data, 0).map(Cow::Owned),
+                    }
+                })
+                .as_deref();
+
+            match face {
+                Some(face) => {
+                    clusters.extend(shape_run(face, scale_font, run, start, rtl, font_id))
+                }
+                None => clusters.extend(run.char_indices().map(|(idx, c)| {
+                    let mut id = scale_font.glyph_id(c);
+                    // Rule L4: mirror characters, like brackets, in right-to-left runs
+                    if let Some(mirror) = bidi::mirrored(c).filter(|_| rtl) {
+                        let mirror_id = scale_font.glyph_id(mirror);
+                        if mirror_id.0 != 0 {
+                            id = mirror_id;
+                        }
+                    }
+                    Cluster {
+                        start: start + idx,
+                        font_id,
+                        glyphs: vec![(id, point(0.0, 0.0))],
+                        advance: None,
+                    }
+                })),
+            }
+        }
+
+        let mut line_breaks = self.line_breaker.line_breaks(text).peekable();
+        let mut chars = Vec::with_capacity(clusters.len());
+        for (idx, cluster) in clusters.iter().enumerate() {
+            let end = clusters.get(idx + 1).map_or(text.len(), |c| c.start);
+            let cluster_text = &text[cluster.start..end];
+
+            let mut line_break = None;
+            while let Some(lbreak) = line_breaks.next_if(|b| b.offset() <= end) {
+                if lbreak.offset() > cluster.start {
+                    line_break = Some(lbreak);
+                }
+            }
+            if line_break.is_some() && end == text.len() {
+                // handle inherent end-of-str breaks
+                let last_char = cluster_text.chars().next_back();
+                line_break =
+                    line_break.and(last_char.and_then(|c| c.eol_line_break(&self.line_breaker)));
+            }
+
+            let control = cluster_text.chars().all(|c| c.is_control());
+            let whitespace = cluster_text.chars().all(|c| c.is_whitespace());
+            let tab = cluster_text == "\t";
+            let space = matches!(cluster_text, " " | "\t");
+            let newline = matches!(cluster_text, "\n" | "\r" | "\r\n");
+            let spacing = cluster_text
+                .chars()
+                .next()
+                .map_or(0.0, |c| characters::spacing(&section, c));
+
+            // The glyph nearest the cluster origin goes first, as it positions the whole
+            // cluster when reordered. All glyphs share the cluster's caret position,
+            // with the last carrying the cluster advance.
+            let origin = (0..cluster.glyphs.len())
+                .min_by(|a, b| {
+                    let a = cluster.glyphs[*a].1.x.abs();
+                    a.total_cmp(&cluster.glyphs[*b].1.x.abs())
+                })
+                .unwrap_or_default();
+            let order = Some(origin)
+                .into_iter()
+                .chain((0..cluster.glyphs.len()).filter(|idx| *idx != origin));
+
+            let scale_font = fonts[cluster.font_id].as_scaled(scale);
+            let shift = baseline_shift.px(&scale_font);
+
+            let last = cluster.glyphs.len() - 1;
+            for (n, glyph_idx) in order.enumerate() {
+                let (id, position) = cluster.glyphs[glyph_idx];
+                chars.push(Character {
+                    glyph: id.with_scale_and_position(scale, position),
+                    scale_font,
+                    font_id: cluster.font_id,
+                    line_break: line_break.filter(|_| n == last),
+                    control,
+                    whitespace,
+                    tab: tab && n == last,
+                    space,
+                    newline,
+                    section_index,
+                    byte_index: cluster.start,
+                    advance: cluster.advance.map(|a| if n == last { a } else { 0.0 }),
+                    spacing: if n == last { spacing } else { 0.0 },
+                    line_height,
+                    placeholder: None,
+                    baseline_shift: shift,
+                });
+            }
+        }
+        chars
+    }
+}
+
+impl<'a, 'b, L, F, S> Iterator for ShapedCharacters<'a, 'b, L, F, S>
+where
+    L: LineBreaker,
+    F: Font,
+    S: Iterator<Item = SectionText<'a>>,
+{
+    type Item = Character<'b, F>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(c) = self.section_chars.next() {
+                return Some(c);
+            }
+
+            let (section_index, section) = self.section_text.next()?;
+            if valid_section(&section) {
+                self.section_chars = self.shape_section(section_index, section).into_iter();
+            }
+        }
+    }
+}
+
+impl<'a, L, F, S> FusedIterator for ShapedCharacters<'a, '_, L, F, S>
+where
+    L: LineBreaker,
+    F: Font,
+    S: Iterator<Item = SectionText<'a>>,
+{
+}
+
+/// Splits section text into runs of the same bidi direction & font,
+/// `(start, end, rtl, font_id)`.
+fn runs(
+    text: &str,
+    section_index: usize,
+    bidi: Option<&Bidi>,
+    mut font_id: impl FnMut(usize) -> FontId,
+) -> Vec<(usize, usize, bool, FontId)> {
+    let mut runs: Vec<(usize, usize, bool, FontId)> = Vec::new();
+    for (idx, c) in text.char_indices() {
+        let rtl = bidi.is_some_and(|bidi| bidi.is_rtl(section_index, idx));
+        let font_id = font_id(idx);
+        let end = idx + c.len_utf8();
+        match runs.last_mut() {
+            Some((_, run_end, run_rtl, run_font)) if *run_rtl == rtl && *run_font == font_id => {
+                *run_end = end
+            }
+            _ => runs.push((idx, end, rtl, font_id)),
+        }
+    }
+    runs
+}
+
+/// Shapes a run of text returning clusters in logical order.
+fn shape_run<F: Font>(
+    face: &rustybuzz::Face<'_>,
+    scale_font: PxScaleFont<F>,
+    run: &str,
+    run_start: usize,
+    rtl: bool,
+    font_id: FontId,
+) -> Vec<Cluster> {
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(run);
+    buffer.set_direction(match rtl {
+        true => Direction::RightToLeft,
+        false => Direction::LeftToRight,
+    });
+    buffer.guess_segment_properties();
+    let shaped = rustybuzz::shape(face, &[], buffer);
+
+    let h_scale = scale_font.h_scale_factor();
+    let v_scale = scale_font.v_scale_factor();
+
+    // shaped glyphs are in visual order
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for (info, pos) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+        let start = run_start + info.cluster as usize;
+        if clusters.last().map(|c| c.start) != Some(start) {
+            clusters.push(Cluster {
+                start,
+                font_id,
+                glyphs: Vec::new(),
+                advance: Some(0.0),
+            });
+        }
+        let cluster = clusters.last_mut().unwrap();
+        let advance = cluster.advance.get_or_insert(0.0);
+        let position = point(
+            *advance + pos.x_offset as f32 * h_scale,
+            -pos.y_offset as f32 * v_scale,
+        );
+        cluster.glyphs.push((GlyphId(info.glyph_id as _), position));
+        *advance += pos.x_advance as f32 * h_scale;
+    }
+
+    if rtl {
+        clusters.reverse();
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{FontId, HorizontalAlign};
+    use approx::assert_relative_eq;
+    use std::sync::LazyLock;
+
+    static DEJAVU: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap()
+    });
+
+    fn section(text: &str) -> SectionText<'_> {
+        SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            font_id: FontId(0),
+            ..<_>::default()
+        }
+    }
+
+    fn calculate_glyphs(text: &str, geometry: &SectionGeometry) -> Vec<SectionGlyph> {
+        ShapedLayout::default().calculate_glyphs(&[&*DEJAVU], geometry, &[section(text)])
+    }
+
+    #[test]
+    fn ligature_cluster() {
+        let glyphs = calculate_glyphs("office", &SectionGeometry::default());
+
+        let byte_indices: Vec<_> = glyphs.iter().map(|sg| sg.byte_index).collect();
+        assert_eq!(byte_indices, [0, 1, 4, 5], "expected 'ffi' ligature");
+        assert_ne!(glyphs[1].glyph.id, DEJAVU.glyph_id('f'));
+    }
+
+    #[test]
+    fn combining_mark_cluster() {
+        let glyphs = calculate_glyphs("x\u{301}y", &SectionGeometry::default());
+
+        let byte_indices: Vec<_> = glyphs.iter().map(|sg| sg.byte_index).collect();
+        assert_eq!(byte_indices, [0, 0, 3]);
+
+        // the mark doesn't advance the caret
+        let x_advance = DEJAVU.as_scaled(20.0).h_advance(DEJAVU.glyph_id('x'));
+        assert_relative_eq!(glyphs[2].glyph.position.x, x_advance);
+    }
+
+    #[test]
+    fn arabic_joining() {
+        let glyphs = calculate_glyphs("بب", &SectionGeometry::default());
+
+        // visual order, right-to-left
+        let byte_indices: Vec<_> = glyphs.iter().map(|sg| sg.byte_index).collect();
+        assert_eq!(byte_indices, [2, 0]);
+
+        // joined forms, rather than the isolated form
+        let isolated = DEJAVU.glyph_id('ب');
+        assert!(
+            glyphs.iter().all(|sg| sg.glyph.id != isolated),
+            "{glyphs:#?}"
+        );
+    }
+
+    #[test]
+    fn wrap_shaped_words() {
+        let width = calculate_glyphs("office", &SectionGeometry::default())
+            .last()
+            .map(|sg| sg.glyph.position.x)
+            .unwrap();
+
+        let glyphs = calculate_glyphs(
+            "office office",
+            &SectionGeometry {
+                bounds: (width * 1.5, f32::INFINITY),
+                ..<_>::default()
+            },
+        );
+
+        let second_line: Vec<_> = glyphs
+            .iter()
+            .filter(|sg| sg.glyph.position.y > glyphs[0].glyph.position.y)
+            .map(|sg| sg.byte_index)
+            .collect();
+        assert_eq!(second_line, [7, 8, 11, 12]);
+        assert_relative_eq!(
+            glyphs
+                .iter()
+                .find(|sg| sg.byte_index == 7)
+                .unwrap()
+                .glyph
+                .position
+                .x,
+            0.0
+        );
+    }
+
+    #[test]
+    fn recalculate_text_changes() {
+        let width = calculate_glyphs("office", &SectionGeometry::default())
+            .last()
+            .map(|sg| sg.glyph.position.x)
+            .unwrap();
+        let geometry = SectionGeometry {
+            bounds: (width * 1.5, f32::INFINITY),
+            ..<_>::default()
+        };
+        let layout = ShapedLayout::default();
+        let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+            &[&*DEJAVU],
+            &FontFallbacks::default(),
+            &geometry,
+            &[section("office office\noffice office")],
+        );
+        assert_eq!(lines.len(), 4);
+
+        for (text, change) in [
+            (
+                "office office\noffice offices",
+                GlyphChange::TextAppended((0, 27)),
+            ),
+            (
+                "office office\noffice affine",
+                GlyphChange::TextEdited((0, 21)),
+            ),
+        ] {
+            let (recalc, recalc_lines) = layout.recalculate_glyphs_and_lines(
+                PreviousLayout::new(glyphs.clone(), &lines),
+                change,
+                &[&*DEJAVU],
+                &FontFallbacks::default(),
+                &geometry,
+                &[section(text)],
+            );
+            let (expected, expected_lines) = layout.calculate_glyphs_and_lines(
+                &[&*DEJAVU],
+                &FontFallbacks::default(),
+                &geometry,
+                &[section(text)],
+            );
+
+            assert_eq!(recalc.len(), expected.len(), "{text}");
+            for (sg, expected) in recalc.iter().zip(&expected) {
+                assert_eq!(sg.byte_index, expected.byte_index);
+                assert_eq!(sg.glyph.id, expected.glyph.id);
+                assert_relative_eq!(sg.glyph.position.x, expected.glyph.position.x);
+                assert_relative_eq!(sg.glyph.position.y, expected.glyph.position.y);
+            }
+            assert_eq!(recalc_lines.len(), expected_lines.len());
+            for (line, expected) in recalc_lines.iter().zip(&expected_lines) {
+                assert_eq!((line.start, line.end), (expected.start, expected.end));
+                assert_relative_eq!(line.baseline, expected.baseline, epsilon = 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn shaped_layout_alignment() {
+        let left = calculate_glyphs("office", &SectionGeometry::default());
+        let right = Layout::default()
+            .h_align(HorizontalAlign::Right)
+            .shaped()
+            .calculate_glyphs(
+                &[&*DEJAVU],
+                &SectionGeometry::default(),
+                &[section("office")],
+            );
+
+        let width = left.last().unwrap().glyph.position.x
+            + DEJAVU
+                .as_scaled(20.0)
+                .h_advance(left.last().unwrap().glyph.id);
+        assert_relative_eq!(right[0].glyph.position.x, -width, epsilon = 0.001);
+    }
+
+    #[test]
+    fn with_fonts_faces() {
+        let fonts = [&*DEJAVU];
+        let layout = ShapedLayout::default().with_fonts(&fonts);
+        assert_eq!(layout, ShapedLayout::default());
+        assert!(layout.1.get(FontId(0), DEJAVU.font_data()).is_some());
+
+        let glyphs =
+            layout.calculate_glyphs(&fonts, &SectionGeometry::default(), &[section("office")]);
+        assert_eq!(
+            glyphs,
+            calculate_glyphs("office", &SectionGeometry::default())
+        );
+
+        // other fonts are parsed as needed
+        let mono =
+            FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap();
+        assert!(layout.1.get(FontId(0), mono.font_data()).is_none());
+        let glyphs =
+            layout.calculate_glyphs(&[&mono], &SectionGeometry::default(), &[section("office")]);
+        let expected = ShapedLayout::default().calculate_glyphs(
+            &[&mono],
+            &SectionGeometry::default(),
+            &[section("office")],
+        );
+        assert_eq!(glyphs, expected);
+    }
+}
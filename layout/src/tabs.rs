@@ -0,0 +1,282 @@
+use crate::SectionGlyph;
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// Tab stops of built-in horizontal layouts, positions are relative to the start of a line.
+///
+/// A tab advances to the next stop after the caret, explicit `stops` first & then
+/// stops every tab `width`.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // a stop after each column, e.g. measured from the widest cell
+/// # let column_widths = [120.0, 180.0];
+/// let stops: Vec<_> = column_widths
+///     .iter()
+///     .scan(0.0, |x, width| {
+///         *x += width;
+///         Some(TabStop::left(*x))
+///     })
+///     .collect();
+///
+/// let layout = Layout::default().tab_stops(TabStops {
+///     stops: stops.into(),
+///     width: TabWidth::Px(60.0),
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TabStops {
+    /// Explicit stops in ascending position order.
+    pub stops: Arc<[TabStop]>,
+    /// Interval of the default, left aligned, stops following any explicit ones.
+    pub width: TabWidth,
+}
+
+impl Default for TabStops {
+    /// No explicit stops with default stops every 8 spaces.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            stops: Arc::default(),
+            width: TabWidth::Spaces(8),
+        }
+    }
+}
+
+impl TabStops {
+    /// Returns the next stop after `x`.
+    ///
+    /// `space_width` is the advance of a space in the tab's font & scale.
+    pub(crate) fn next(&self, x: f32, space_width: f32) -> (f32, TabAlign) {
+        // Reduce float errors, a caret aligned to a stop is not before it
+        let after =
+            |position: f32| position > x && !approx::relative_eq!(position, x, epsilon = 1e-3);
+
+        if let Some(stop) = self.stops.iter().find(|s| after(s.position)) {
+            return (stop.position, stop.align);
+        }
+
+        let width = match self.width {
+            TabWidth::Spaces(n) => n as f32 * space_width,
+            TabWidth::Px(px) => px,
+        };
+        if width <= 0.0 || !width.is_finite() {
+            return (x, TabAlign::Left);
+        }
+        let stop = (x / width).floor() * width + width;
+        match after(stop) {
+            true => (stop, TabAlign::Left),
+            false => (stop + width, TabAlign::Left),
+        }
+    }
+}
+
+/// Interval between default tab stops.
+///
+/// Pixel widths are compared & hashed by their bits, so `0.0` & `-0.0` differ.
+#[derive(Debug, Clone, Copy)]
+pub enum TabWidth {
+    /// Count of space advances, using the font & scale of the tab.
+    Spaces(u32),
+    /// Pixel width.
+    Px(f32),
+}
+
+impl PartialEq for TabWidth {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (TabWidth::Spaces(a), TabWidth::Spaces(b)) => a == b,
+            (TabWidth::Px(a), TabWidth::Px(b)) => a.to_bits() == b.to_bits(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TabWidth {}
+
+impl Hash for TabWidth {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            TabWidth::Spaces(n) => (0_u8, n).hash(state),
+            TabWidth::Px(px) => (1_u8, px.to_bits()).hash(state),
+        }
+    }
+}
+
+/// Explicit tab stop.
+///
+/// Positions are compared & hashed by their bits, so `0.0` & `-0.0` differ.
+#[derive(Debug, Clone, Copy)]
+pub struct TabStop {
+    /// Pixel position relative to the start of the line.
+    pub position: f32,
+    pub align: TabAlign,
+}
+
+impl TabStop {
+    /// Returns a stop starting the text following the tab at `position`.
+    #[inline]
+    pub const fn left(position: f32) -> Self {
+        Self {
+            position,
+            align: TabAlign::Left,
+        }
+    }
+
+    /// Returns a stop ending the text following the tab at `position`.
+    #[inline]
+    pub const fn right(position: f32) -> Self {
+        Self {
+            position,
+            align: TabAlign::Right,
+        }
+    }
+
+    /// Returns a stop centering the text following the tab on `position`.
+    #[inline]
+    pub const fn center(position: f32) -> Self {
+        Self {
+            position,
+            align: TabAlign::Center,
+        }
+    }
+
+    /// Returns a stop aligning the first `separator` of the text following the tab
+    /// at `position`.
+    #[inline]
+    pub const fn decimal(position: f32, separator: char) -> Self {
+        Self {
+            position,
+            align: TabAlign::Decimal(separator),
+        }
+    }
+}
+
+impl PartialEq for TabStop {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.position.to_bits() == other.position.to_bits() && self.align == other.align
+    }
+}
+
+impl Eq for TabStop {}
+
+impl Hash for TabStop {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.position.to_bits().hash(state);
+        self.align.hash(state);
+    }
+}
+
+/// Alignment of the text following a tab, up to the next tab or the line end, to its stop.
+///
+/// Text is never moved before the tab, so aligned text that is too wide will overrun
+/// the stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TabAlign {
+    /// Text starts at the stop.
+    #[default]
+    Left,
+    /// Text ends at the stop.
+    Right,
+    /// Text is centered on the stop.
+    Center,
+    /// The first separator character, e.g. `'.'`, of the text is positioned at the stop.
+    /// Text without a separator ends at the stop.
+    Decimal(char),
+}
+
+/// Text following a tab aligned to a stop once its extent is known.
+pub(crate) struct TabSegment {
+    pub stop: f32,
+    pub align: TabAlign,
+    /// Index of the first line glyph after the tab.
+    pub glyph_start: usize,
+    /// Caret position after the tab.
+    pub start_x: f32,
+}
+
+impl TabSegment {
+    /// Returns the shift aligning the segment `glyphs`, ending at `right`.
+    ///
+    /// `texts` are the section texts used to find decimal separators.
+    pub fn shift(&self, glyphs: &[SectionGlyph], right: f32, texts: &[&str]) -> f32 {
+        let width = (right - self.start_x).max(0.0);
+        let anchor = match self.align {
+            TabAlign::Left => 0.0,
+            TabAlign::Right => width,
+            TabAlign::Center => width / 2.0,
+            TabAlign::Decimal(separator) => glyphs[self.glyph_start..]
+                .iter()
+                .find(|sg| {
+                    texts
+                        .get(sg.section_index)
+                        .and_then(|text| text.get(sg.byte_index..))
+                        .and_then(|s| s.chars().next())
+                        == Some(separator)
+                })
+                .map_or(width, |sg| sg.glyph.position.x - self.start_x),
+        };
+        (self.stop - anchor - self.start_x).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn next_stop() {
+        let tabs = TabStops {
+            stops: [TabStop::right(50.0), TabStop::center(80.0)].into(),
+            width: TabWidth::Spaces(4),
+        };
+
+        assert_eq!(tabs.next(0.0, 10.0), (50.0, TabAlign::Right));
+        assert_eq!(tabs.next(50.0, 10.0), (80.0, TabAlign::Center));
+        assert_eq!(tabs.next(80.0, 10.0), (120.0, TabAlign::Left));
+        assert_eq!(tabs.next(125.0, 10.0), (160.0, TabAlign::Left));
+
+        let px = TabStops {
+            width: TabWidth::Px(32.0),
+            ..<_>::default()
+        };
+        assert_eq!(px.next(0.0, 10.0), (32.0, TabAlign::Left));
+        assert_eq!(px.next(32.0, 10.0), (64.0, TabAlign::Left));
+
+        let none = TabStops {
+            width: TabWidth::Spaces(0),
+            ..<_>::default()
+        };
+        assert_eq!(none.next(12.0, 10.0), (12.0, TabAlign::Left));
+    }
+
+    #[test]
+    fn eq_hash_agree() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let hasher = RandomState::new();
+        let stops = |position: f32, width: f32| TabStops {
+            stops: [TabStop::left(position)].into(),
+            width: TabWidth::Px(width),
+        };
+
+        assert_ne!(stops(0.0, 10.0), stops(-0.0, 10.0));
+        assert_ne!(stops(10.0, 0.0), stops(10.0, -0.0));
+        assert_eq!(stops(f32::NAN, f32::NAN), stops(f32::NAN, f32::NAN));
+        assert_eq!(
+            hasher.hash_one(stops(f32::NAN, f32::NAN)),
+            hasher.hash_one(stops(f32::NAN, f32::NAN))
+        );
+        assert_eq!(
+            hasher.hash_one(stops(5.0, 1.0)),
+            hasher.hash_one(stops(5.0, 1.0))
+        );
+    }
+}
@@ -0,0 +1,555 @@
+use crate::{
+    characters::{Character, Characters},
+    linebreak::{LineBreak, LineBreaker},
+    words::VMetrics,
+    FontFallbacks, HorizontalAlign, Placeholder, SectionGeometry, SectionGlyph, ToSectionText,
+    VerticalAlign,
+};
+use ab_glyph::*;
+use std::iter::Peekable;
+
+/// Orientation of a glyph, see [`SectionGlyph::orientation`](struct.SectionGlyph.html#structfield.orientation).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VerticalOrientation {
+    /// Drawn upright, e.g. all horizontal text & CJK ideographs & kana in vertical text.
+    #[default]
+    Upright,
+    /// Drawn rotated 90° clockwise about the glyph position, e.g. Latin in vertical text.
+    Sideways,
+}
+
+impl VerticalOrientation {
+    /// Returns the orientation of a character in vertical layouts.
+    ///
+    /// Follows a simplification of
+    /// [Unicode Standard Annex #50](https://www.unicode.org/reports/tr50/), characters of
+    /// East Asian scripts & symbols are upright, others are sideways.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// assert_eq!(VerticalOrientation::of('縦'), VerticalOrientation::Upright);
+    /// assert_eq!(VerticalOrientation::of('A'), VerticalOrientation::Sideways);
+    /// ```
+    pub fn of(c: char) -> Self {
+        match c {
+            '\u{1100}'..='\u{11FF}'
+            | '\u{2460}'..='\u{24FF}'
+            | '\u{25A0}'..='\u{27BF}'
+            | '\u{2E80}'..='\u{A4CF}'
+            | '\u{A960}'..='\u{A97F}'
+            | '\u{AC00}'..='\u{D7FF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE10}'..='\u{FE1F}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF01}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{1F000}'..='\u{1FAFF}'
+            | '\u{20000}'..='\u{3FFFF}' => VerticalOrientation::Upright,
+            _ => VerticalOrientation::Sideways,
+        }
+    }
+
+    /// Returns the screen rect of `upright` glyph bounds, e.g. from
+    /// [`Font::glyph_bounds`](ab_glyph::Font::glyph_bounds), for a glyph positioned at
+    /// `position` drawn in this orientation.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// let upright = Rect {
+    ///     min: point(10.0, 5.0),
+    ///     max: point(18.0, 11.0),
+    /// };
+    /// let sideways = VerticalOrientation::Sideways.rotate_rect(upright, point(10.0, 10.0));
+    /// assert_eq!(sideways.min, point(9.0, 10.0));
+    /// assert_eq!(sideways.max, point(15.0, 18.0));
+    /// ```
+    #[inline]
+    pub fn rotate_rect(self, upright: Rect, position: Point) -> Rect {
+        match self {
+            VerticalOrientation::Upright => upright,
+            VerticalOrientation::Sideways => {
+                // (x, y) relative to the position rotates clockwise to (-y, x)
+                let Rect { min, max } = upright;
+                Rect {
+                    min: point(
+                        position.x - (max.y - position.y),
+                        position.y + (min.x - position.x),
+                    ),
+                    max: point(
+                        position.x - (min.y - position.y),
+                        position.y + (max.x - position.x),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Sequence of glyphs where the last is a line-break.
+///
+/// Glyphs are relatively positioned from (0, 0) in a top-center alignment style.
+struct VerticalWord {
+    glyphs: Vec<SectionGlyph>,
+    /// pixel advance height of word includes ending spaces/invisibles
+    layout_height: f32,
+    /// pixel advance height of word not including any trailing spaces/invisibles
+    layout_height_no_trail: f32,
+    max_v_metrics: VMetrics,
+    /// indicates the break after the word is a hard one
+    hard_break: bool,
+}
+
+/// A column of words limited to a max height bound.
+///
+/// Glyphs are relatively positioned from (0, 0) in a top-center alignment style.
+#[derive(Default)]
+struct Column {
+    glyphs: Vec<SectionGlyph>,
+    /// Horizontal line metrics, the column width is the max line height.
+    max_v_metrics: VMetrics,
+    bottommost: f32,
+}
+
+/// `VerticalWord` iterator.
+struct VerticalWords<'a, 'b, L, F, S>
+where
+    L: LineBreaker,
+    F: Font,
+    S: Iterator<Item = crate::SectionText<'a>>,
+{
+    characters: Peekable<Characters<'a, 'b, L, F, S>>,
+    texts: Vec<&'a str>,
+}
+
+impl<'a, L, F, S> Iterator for VerticalWords<'a, '_, L, F, S>
+where
+    L: LineBreaker,
+    F: Font,
+    S: Iterator<Item = crate::SectionText<'a>>,
+{
+    type Item = VerticalWord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut glyphs = Vec::new();
+        let mut caret = 0.0;
+        let mut caret_no_trail = caret;
+        let mut last_sideways_id = None;
+        let mut max_v_metrics = VMetrics::default();
+        let mut hard_break = false;
+        let mut progress = false;
+
+        while let Some(Character {
+            mut glyph,
+            scale_font,
+            font_id,
+            line_break,
+            control,
+            whitespace,
+            section_index,
+            byte_index,
+            spacing,
+            line_height,
+            placeholder,
+            baseline_shift,
+            ..
+        }) = self.characters.next()
+        {
+            progress = true;
+
+            let v_metrics = match placeholder {
+                // centered across the column
+                Some(Placeholder { width, .. }) => VMetrics {
+                    ascent: width / 2.0,
+                    descent: -width / 2.0,
+                    line_gap: 0.0,
+                },
+                None => VMetrics::from(scale_font).with_line_height(line_height, &scale_font),
+            };
+            // shifted towards the right, the side ascents face
+            max_v_metrics = max_v_metrics.max(v_metrics.shifted(baseline_shift));
+
+            let c = self.texts[section_index][byte_index..].chars().next();
+            let orientation = c.map_or(VerticalOrientation::Upright, VerticalOrientation::of);
+
+            let (advance_height, orientation) = match (placeholder, orientation) {
+                (Some(p), _) => {
+                    last_sideways_id = None;
+                    // positioned on the baseline, as horizontal placeholders
+                    glyph.position = point(-p.width / 2.0, caret + p.height - p.baseline_offset);
+                    (p.height, VerticalOrientation::Upright)
+                }
+                (None, VerticalOrientation::Upright) => {
+                    last_sideways_id = None;
+                    let (advance, origin_y) = upright_metrics(&scale_font, glyph.id);
+                    glyph.position = point(-scale_font.h_advance(glyph.id) / 2.0, caret + origin_y);
+                    (advance, orientation)
+                }
+                (None, VerticalOrientation::Sideways) => {
+                    if let Some(id) = last_sideways_id.replace(glyph.id) {
+                        caret += scale_font.kern(id, glyph.id);
+                    }
+                    // baseline centered in the column, ascent facing right
+                    let baseline_x = -(scale_font.ascent() + scale_font.descent()) / 2.0;
+                    glyph.position = point(baseline_x, caret);
+                    (scale_font.h_advance(glyph.id), orientation)
+                }
+            };
+
+            if !control {
+                glyph.position.x += baseline_shift;
+                glyphs.push(SectionGlyph {
+                    section_index,
+                    byte_index,
+                    glyph,
+                    font_id,
+                    orientation,
+                });
+                caret += advance_height;
+
+                if !whitespace {
+                    // not an invisible trail
+                    caret_no_trail = caret;
+                }
+                caret += spacing;
+            }
+
+            if let Some(lbreak) = line_break {
+                hard_break = matches!(lbreak, LineBreak::Hard(_));
+                break;
+            }
+            // placeholders are words of their own
+            if self
+                .characters
+                .peek()
+                .is_some_and(|c| c.placeholder.is_some())
+            {
+                break;
+            }
+        }
+
+        if progress {
+            return Some(VerticalWord {
+                glyphs,
+                layout_height: caret,
+                layout_height_no_trail: caret_no_trail,
+                max_v_metrics,
+                hard_break,
+            });
+        }
+
+        None
+    }
+}
+
+/// Returns the vertical advance & distance from the top of the advance to the baseline.
+///
+/// Uses the font's vertical metrics if available, otherwise an advance of the font's ascent
+/// to descent with the baseline at the ascent, so the em box fills the advance.
+#[inline]
+fn upright_metrics<F: Font>(scale_font: &PxScaleFont<F>, id: GlyphId) -> (f32, f32) {
+    let v_advance = scale_font.v_advance(id);
+    if v_advance > 0.0 {
+        // outline bounds are y-up, the top is `min.y`
+        let y_max = scale_font
+            .font
+            .outline(id)
+            .map_or(scale_font.ascent(), |o| {
+                o.bounds.min.y * scale_font.v_scale_factor()
+            });
+        (v_advance, scale_font.v_side_bearing(id) + y_max)
+    } else {
+        (
+            scale_font.ascent() - scale_font.descent(),
+            scale_font.ascent(),
+        )
+    }
+}
+
+/// Greedily fills columns with words up to the `height_bound`.
+fn columns<W: Iterator<Item = VerticalWord>>(words: W, height_bound: f32) -> Vec<Column> {
+    let mut columns = Vec::new();
+    let mut column = Column::default();
+    let mut caret = 0.0;
+    let mut progressed = false;
+
+    for word in words {
+        // Drop trailing spaces when bounds-wrapping.
+        // However, if the word ends in a hard-break keep the trailing space height.
+        let word_wrap_height = match word.hard_break {
+            false => word.layout_height_no_trail,
+            true => word.layout_height,
+        };
+
+        let word_bottom = caret + word_wrap_height;
+        // Reduce float errors by using relative "<= height bound" check
+        let word_in_bounds =
+            word_bottom < height_bound || approx::relative_eq!(word_bottom, height_bound);
+
+        if !word_in_bounds && progressed {
+            columns.push(std::mem::take(&mut column));
+            caret = 0.0;
+        }
+        progressed = true;
+
+        column.bottommost = caret + word_wrap_height;
+        if column.glyphs.is_empty() || !word.glyphs.is_empty() {
+            column.max_v_metrics = column.max_v_metrics.clone().max(word.max_v_metrics);
+        }
+        column.glyphs.extend(word.glyphs.into_iter().map(|mut sg| {
+            sg.glyph.position.y += caret;
+            sg
+        }));
+        caret += word.layout_height;
+
+        if word.hard_break {
+            columns.push(std::mem::take(&mut column));
+            caret = 0.0;
+            progressed = false;
+        }
+    }
+    if progressed {
+        columns.push(column);
+    }
+
+    columns
+}
+
+/// Positions glyphs in columns from top-to-bottom, progressing right-to-left.
+pub(crate) fn calculate_glyphs<L, F, S>(
+    fonts: &[F],
+    fallbacks: &FontFallbacks,
+    geometry: &SectionGeometry,
+    sections: &[S],
+    line_breaker: L,
+    h_align: HorizontalAlign,
+    v_align: VerticalAlign,
+) -> Vec<SectionGlyph>
+where
+    L: LineBreaker,
+    F: Font,
+    S: ToSectionText,
+{
+    let SectionGeometry {
+        screen_position: (screen_x, screen_y),
+        bounds: (bound_w, bound_h),
+    } = *geometry;
+
+    let words = VerticalWords {
+        characters: Characters::new(
+            fonts,
+            sections.iter().map(|s| s.to_section_text()),
+            line_breaker,
+            None,
+            fallbacks,
+        )
+        .peekable(),
+        texts: sections.iter().map(|s| s.to_section_text().text).collect(),
+    };
+
+    // columns starting beyond the width bound are not visible
+    let mut block_w = 0.0;
+    let columns: Vec<_> = columns(words, bound_h)
+        .into_iter()
+        .take_while(|column| {
+            let visible = block_w < bound_w;
+            block_w += column.max_v_metrics.height();
+            visible
+        })
+        .collect();
+    let block_w = columns
+        .iter()
+        .map(|c| c.max_v_metrics.height())
+        .sum::<f32>();
+
+    let mut column_right = match h_align.vertical() {
+        HorizontalAlign::Left => screen_x + block_w,
+        HorizontalAlign::Center => screen_x + block_w / 2.0,
+        _ => screen_x,
+    };
+
+    let mut out = Vec::new();
+    for column in columns {
+        let width = column.max_v_metrics.height();
+        let top = match v_align {
+            VerticalAlign::Top | VerticalAlign::Baseline => screen_y,
+            VerticalAlign::Center
+            | VerticalAlign::CapHeightCenter
+            | VerticalAlign::XHeightCenter => screen_y - column.bottommost / 2.0,
+            VerticalAlign::Bottom | VerticalAlign::LastBaseline => screen_y - column.bottommost,
+        };
+        let offset = point(column_right - width / 2.0, top);
+        out.extend(column.glyphs.into_iter().map(|mut sg| {
+            sg.glyph.position += offset;
+            sg
+        }));
+        column_right -= width;
+    }
+    out
+}
+
+impl HorizontalAlign {
+    /// Returns the physical alignment of vertical layout columns, which progress
+    /// right-to-left.
+    #[inline]
+    pub(crate) fn vertical(self) -> Self {
+        match self {
+            HorizontalAlign::Start | HorizontalAlign::Justify => HorizontalAlign::Right,
+            HorizontalAlign::End => HorizontalAlign::Left,
+            h_align => h_align,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BuiltInLineBreaker, FontId, SectionText};
+    use approx::assert_relative_eq;
+    use std::sync::LazyLock;
+
+    static A_FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap()
+    });
+    /// Has vertical metrics.
+    static V_FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/GaramondNo8-Reg.ttf")).unwrap()
+    });
+
+    /// Returns a single glyph word with 2px of trailing space.
+    fn word(byte_index: usize, layout_height: f32, hard_break: bool) -> VerticalWord {
+        VerticalWord {
+            glyphs: vec![SectionGlyph {
+                section_index: 0,
+                byte_index,
+                glyph: GlyphId(0).with_scale(10.0),
+                font_id: FontId(0),
+                orientation: VerticalOrientation::Upright,
+            }],
+            layout_height,
+            layout_height_no_trail: layout_height - 2.0,
+            max_v_metrics: VMetrics {
+                ascent: 5.0,
+                descent: -5.0,
+                line_gap: 0.0,
+            },
+            hard_break,
+        }
+    }
+
+    fn byte_indices(column: &Column) -> Vec<usize> {
+        column.glyphs.iter().map(|sg| sg.byte_index).collect()
+    }
+
+    #[test]
+    fn columns_wrap_at_height_bound() {
+        let words = (0..3).map(|i| word(i, 10.0, false));
+        let columns = columns(words, 25.0);
+
+        assert_eq!(columns.len(), 2);
+        assert_eq!(byte_indices(&columns[0]), [0, 1]);
+        assert_eq!(byte_indices(&columns[1]), [2]);
+        assert_relative_eq!(columns[1].glyphs[0].glyph.position.y, 0.0);
+        // trailing space doesn't count towards the bound
+        assert_relative_eq!(columns[0].bottommost, 18.0);
+
+        // last word fits exactly without its trailing space
+        let words = (0..3).map(|i| word(i, 10.0, false));
+        let columns = super::columns(words, 28.0);
+        assert_eq!(columns.len(), 1);
+        assert_relative_eq!(columns[0].glyphs[2].glyph.position.y, 20.0);
+    }
+
+    #[test]
+    fn columns_hard_breaks() {
+        let mut empty = word(1, 10.0, true);
+        empty.glyphs.clear();
+        let words = [word(0, 10.0, true), empty, word(2, 10.0, false)];
+        let columns = columns(words.into_iter(), f32::INFINITY);
+
+        // the empty hard break is an empty column
+        assert_eq!(columns.len(), 3);
+        assert_eq!(byte_indices(&columns[0]), [0]);
+        assert!(columns[1].glyphs.is_empty());
+        assert_relative_eq!(columns[1].max_v_metrics.height(), 10.0);
+        assert_eq!(byte_indices(&columns[2]), [2]);
+        assert_relative_eq!(columns[2].glyphs[0].glyph.position.y, 0.0);
+        // a hard break keeps its trailing space height
+        assert_relative_eq!(columns[0].bottommost, 10.0);
+    }
+
+    #[test]
+    fn placeholder_column() {
+        let icon = Placeholder {
+            width: 30.0,
+            height: 20.0,
+            baseline_offset: 0.0,
+        };
+        let glyphs = calculate_glyphs(
+            &[&*A_FONT],
+            &FontFallbacks::default(),
+            &SectionGeometry {
+                screen_position: (100.0, 0.0),
+                ..SectionGeometry::default()
+            },
+            &[
+                SectionText {
+                    text: "\u{FFFC}",
+                    scale: PxScale::from(10.0),
+                    placeholder: Some(icon),
+                    ..<_>::default()
+                },
+                SectionText {
+                    text: "提",
+                    scale: PxScale::from(10.0),
+                    ..<_>::default()
+                },
+            ],
+            BuiltInLineBreaker::default(),
+            HorizontalAlign::Right,
+            VerticalAlign::Top,
+        );
+
+        assert_eq!(glyphs.len(), 2);
+        // the placeholder is wider than the text so sets the column width
+        let rect = icon.rect(&glyphs[0]);
+        assert_relative_eq!(rect.min.x, 70.0);
+        assert_relative_eq!(rect.max.x, 100.0);
+        assert_relative_eq!(rect.min.y, 0.0);
+        assert_relative_eq!(rect.max.y, 20.0);
+
+        // followed by text centered in the column
+        let sfont = A_FONT.as_scaled(10.0);
+        let text = &glyphs[1].glyph;
+        assert_relative_eq!(text.position.x + sfont.h_advance(text.id) / 2.0, 85.0);
+        assert_relative_eq!(text.position.y, 20.0 + sfont.ascent());
+    }
+
+    #[test]
+    fn upright_metrics_fallback() {
+        let sfont = A_FONT.as_scaled(20.0);
+        assert_relative_eq!(sfont.v_advance(sfont.glyph_id('提')), 0.0);
+
+        let (advance, origin_y) = upright_metrics(&sfont, sfont.glyph_id('提'));
+        assert_relative_eq!(advance, sfont.ascent() - sfont.descent());
+        assert_relative_eq!(origin_y, sfont.ascent());
+    }
+
+    #[test]
+    fn upright_metrics_vertical() {
+        let sfont = V_FONT.as_scaled(20.0);
+        let id = sfont.glyph_id('a');
+        let (advance, origin_y) = upright_metrics(&sfont, id);
+        assert_relative_eq!(advance, sfont.v_advance(id));
+
+        // the glyph top is the top side bearing below the top of the advance
+        let top = sfont.outline_glyph(id.with_scale_and_position(20.0, point(0.0, origin_y)));
+        let top = top.unwrap().px_bounds().min.y;
+        assert!(
+            (top - sfont.v_side_bearing(id)).abs() <= 1.0,
+            "{top} vs {}",
+            sfont.v_side_bearing(id)
+        );
+    }
+}
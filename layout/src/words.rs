@@ -1,11 +1,12 @@
 use crate::{
-    characters::{Character, Characters},
-    linebreak::{LineBreak, LineBreaker},
-    lines::Lines,
-    SectionGlyph, SectionText,
+    bidi::Bidi, characters::Character, linebreak::LineBreak, lines::same_cluster, LineHeight,
+    Placeholder, SectionGlyph, VerticalOrientation,
 };
 use ab_glyph::*;
-use std::iter::{FusedIterator, Iterator, Peekable};
+use std::{
+    fmt,
+    iter::{FusedIterator, Iterator, Peekable},
+};
 
 #[derive(Clone, Debug, Default)]
 pub(crate) struct VMetrics {
@@ -20,12 +21,68 @@ impl VMetrics {
         self.ascent - self.descent + self.line_gap
     }
 
+    /// Returns metrics with a custom `line_height` replacing the line gap with equal space
+    /// above the ascent & below the descent.
+    #[inline]
+    pub fn with_line_height<F: Font>(
+        self,
+        line_height: LineHeight,
+        scale_font: &PxScaleFont<F>,
+    ) -> Self {
+        if line_height == LineHeight::Normal {
+            return self;
+        }
+        let half_leading = line_height.half_leading(scale_font);
+        Self {
+            ascent: self.ascent + half_leading,
+            descent: self.descent - half_leading,
+            line_gap: 0.0,
+        }
+    }
+
+    /// Returns metrics of text raised `shift` pixels above the baseline.
+    #[inline]
+    pub fn shifted(self, shift: f32) -> Self {
+        Self {
+            ascent: self.ascent + shift,
+            descent: self.descent + shift,
+            line_gap: self.line_gap,
+        }
+    }
+
+    /// Returns metrics of text scaled by `factor`.
+    #[inline]
+    pub fn scaled(self, factor: f32) -> Self {
+        Self {
+            ascent: self.ascent * factor,
+            descent: self.descent * factor,
+            line_gap: self.line_gap * factor,
+        }
+    }
+
+    /// Returns metrics including both ascents & descents, with the line gap of the taller.
     #[inline]
     pub fn max(self, other: Self) -> Self {
-        if other.height() > self.height() {
-            other
+        let line_gap = if other.height() > self.height() {
+            other.line_gap
         } else {
-            self
+            self.line_gap
+        };
+        Self {
+            ascent: self.ascent.max(other.ascent),
+            descent: self.descent.min(other.descent),
+            line_gap,
+        }
+    }
+}
+
+impl From<Placeholder> for VMetrics {
+    #[inline]
+    fn from(placeholder: Placeholder) -> Self {
+        Self {
+            ascent: placeholder.height - placeholder.baseline_offset,
+            descent: -placeholder.baseline_offset,
+            line_gap: 0.0,
         }
     }
 }
@@ -41,11 +98,86 @@ impl<F: Font> From<PxScaleFont<F>> for VMetrics {
     }
 }
 
+/// Measured words of a section's text, see
+/// [`GlyphPositioner::measure_words`](trait.GlyphPositioner.html#method.measure_words).
+///
+/// Words are independent of the section geometry so may be reused to quickly lay out
+/// the same text, fonts & layout with different bounds.
+#[derive(Clone)]
+pub struct MeasuredWords {
+    pub(crate) words: Vec<Word>,
+    pub(crate) bidi: Option<Bidi>,
+}
+
+impl fmt::Debug for MeasuredWords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeasuredWords")
+            .field("words", &self.words.len())
+            .field("bidi", &self.bidi.is_some())
+            .finish()
+    }
+}
+
+/// Intrinsic widths of a section's text, see
+/// [`GlyphPositioner::intrinsic_widths`](trait.GlyphPositioner.html#method.intrinsic_widths).
+///
+/// Named after the CSS min-content & max-content sizes, e.g. for flexbox or grid layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IntrinsicWidths {
+    /// Width of the widest unbreakable run of text, the narrowest width bound the text
+    /// wraps within without overflowing.
+    pub min_content: f32,
+    /// Width of the text laid out without wrapping, the widest paragraph.
+    pub max_content: f32,
+}
+
+/// How [`Layout`](enum.Layout.html) lays out the spaces & newlines of section text, similar
+/// to the CSS `white-space` property.
+///
+/// Collapsing keeps the `byte_index` of laid out glyphs pointing at the original text, so
+/// text doesn't need to be preprocessed.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // user-generated text with runs of spaces & CRLF line endings
+/// let layout = Layout::default().white_space(WhiteSpace::Normal);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum WhiteSpace {
+    /// Runs of spaces, tabs & newlines collapse into a single space, removed at the start
+    /// & end of lines. Lines wrap at the width bound.
+    Normal,
+    /// Spaces & newlines are kept. Lines only break at newlines.
+    Pre,
+    /// Spaces & newlines are kept. Lines wrap at the width bound.
+    #[default]
+    PreWrap,
+    /// Runs of spaces & tabs collapse into a single space, removed at the start & end of
+    /// lines, newlines are kept. Lines wrap at the width bound.
+    PreLine,
+}
+
+impl WhiteSpace {
+    /// Returns `true` if the character is a space collapsed by this mode.
+    #[inline]
+    fn collapses(self, space: bool, newline: bool) -> bool {
+        match self {
+            Self::Normal => space || newline,
+            Self::PreLine => space,
+            Self::Pre | Self::PreWrap => false,
+        }
+    }
+}
+
 /// Single 'word' ie a sequence of `Character`s where the last is a line-break.
 ///
 /// Glyphs are relatively positioned from (0, 0) in a left-top alignment style.
+#[derive(Clone)]
 pub(crate) struct Word {
     pub glyphs: Vec<SectionGlyph>,
+    /// `(section_index, byte_index)` of the first character
+    pub start: (usize, usize),
     /// pixel advance width of word includes ending spaces/invisibles
     pub layout_width: f32,
     /// pixel advance width of word not including any trailing spaces/invisibles
@@ -53,37 +185,111 @@ pub(crate) struct Word {
     pub max_v_metrics: VMetrics,
     /// indicates the break after the word is a hard one
     pub hard_break: bool,
+    /// hyphen glyph positioned after the word & its advance width, if the break after the
+    /// word is a hyphen one. Rendered only when the line breaks after the word.
+    pub hyphen: Option<(SectionGlyph, f32)>,
+    /// advance width of a space in the font of a tab ending the word, if the word ends
+    /// in a tab
+    pub tab: Option<f32>,
+    /// indicates the line breaks after the word, as chosen by
+    /// [`BreakStrategy::Optimal`](enum.BreakStrategy.html#variant.Optimal)
+    pub line_end: bool,
+}
+
+impl Word {
+    /// Returns the width the word fills of a line it ends, including trailing whitespace
+    /// only before a hard break, & a hyphen if it breaks at one.
+    #[inline]
+    pub(crate) fn line_end_width(&self) -> f32 {
+        let width = match self.hard_break {
+            true => self.layout_width,
+            false => self.layout_width_no_trail,
+        };
+        width + self.hyphen.as_ref().map_or(0.0, |(_, w)| *w)
+    }
+
+    /// Returns the advance width of the widest cluster, consecutive glyphs from the same
+    /// character, of the word's visible width.
+    pub(crate) fn widest_cluster(&self) -> f32 {
+        let mut widest: f32 = 0.0;
+        let mut start: Option<&SectionGlyph> = None;
+        for sg in &self.glyphs {
+            if let Some(s) = start.filter(|s| !same_cluster(s, sg)) {
+                widest = widest.max(sg.glyph.position.x - s.glyph.position.x);
+                start = Some(sg);
+            }
+            start.get_or_insert(sg);
+        }
+        if let Some(s) = start {
+            widest = widest.max(self.layout_width_no_trail - s.glyph.position.x);
+        }
+        widest
+    }
+
+    /// Returns the word as measured with every text scale multiplied by `factor`.
+    ///
+    /// Only valid for words of text without pixel sized spacing, line heights,
+    /// baseline shifts or placeholders, which don't scale with the text.
+    pub(crate) fn scaled(&self, factor: f32) -> Self {
+        let scaled_glyph = |sg: &SectionGlyph| {
+            let mut sg = sg.clone();
+            let PxScale { x, y } = sg.glyph.scale;
+            sg.glyph.scale = PxScale {
+                x: x * factor,
+                y: y * factor,
+            };
+            sg.glyph.position = point(sg.glyph.position.x * factor, sg.glyph.position.y * factor);
+            sg
+        };
+        Self {
+            glyphs: self.glyphs.iter().map(scaled_glyph).collect(),
+            start: self.start,
+            layout_width: self.layout_width * factor,
+            layout_width_no_trail: self.layout_width_no_trail * factor,
+            max_v_metrics: self.max_v_metrics.clone().scaled(factor),
+            hard_break: self.hard_break,
+            hyphen: self
+                .hyphen
+                .as_ref()
+                .map(|(glyph, width)| (scaled_glyph(glyph), width * factor)),
+            tab: self.tab.map(|space| space * factor),
+            line_end: self.line_end,
+        }
+    }
 }
 
 /// `Word` iterator.
-pub(crate) struct Words<'a, 'b, L, F, S>
+pub(crate) struct Words<'b, F, C>
 where
-    L: LineBreaker,
-    F: Font,
-    S: Iterator<Item = SectionText<'a>>,
+    F: Font + 'b,
+    C: Iterator<Item = Character<'b, F>>,
 {
-    pub(crate) characters: Peekable<Characters<'a, 'b, L, F, S>>,
+    pub(crate) characters: Peekable<C>,
+    pub(crate) white_space: WhiteSpace,
+    /// indicates the next collapsible space is removed, as it starts a line or follows
+    /// another
+    pub(crate) collapse_space: bool,
 }
 
-impl<'a, 'b, L, F, S> Words<'a, 'b, L, F, S>
+impl<'b, F, C> Words<'b, F, C>
 where
-    L: LineBreaker,
-    F: Font,
-    S: Iterator<Item = SectionText<'a>>,
+    F: Font + 'b,
+    C: Iterator<Item = Character<'b, F>>,
 {
-    pub(crate) fn lines(self, width_bound: f32) -> Lines<'a, 'b, L, F, S> {
-        Lines {
-            words: self.peekable(),
-            width_bound,
+    #[inline]
+    pub(crate) fn new(characters: C, white_space: WhiteSpace) -> Self {
+        Self {
+            characters: characters.peekable(),
+            white_space,
+            collapse_space: true,
         }
     }
 }
 
-impl<'a, L, F, S> Iterator for Words<'a, '_, L, F, S>
+impl<'b, F, C> Iterator for Words<'b, F, C>
 where
-    L: LineBreaker,
-    F: Font,
-    S: Iterator<Item = SectionText<'a>>,
+    F: Font + 'b,
+    C: Iterator<Item = Character<'b, F>>,
 {
     type Item = Word;
 
@@ -95,37 +301,82 @@ where
         let mut last_glyph_id = None;
         let mut max_v_metrics = VMetrics::default();
         let mut hard_break = false;
+        let mut hyphen = None;
+        let mut tab_space = None;
+        let mut start = None;
         let mut progress = false;
+        // indicates every character of the word so far was a removed collapsible space
+        let mut collapsed = true;
 
-        for Character {
+        while let Some(Character {
             mut glyph,
             scale_font,
             font_id,
-            line_break,
-            control,
+            mut line_break,
+            mut control,
             whitespace,
+            mut tab,
+            space,
+            newline,
             section_index,
             byte_index,
-        } in &mut self.characters
+            mut advance,
+            spacing,
+            line_height,
+            placeholder,
+            baseline_shift,
+        }) = self.characters.next()
         {
             progress = true;
+            start.get_or_insert((section_index, byte_index));
+
+            let v_metrics = match placeholder {
+                Some(placeholder) => VMetrics::from(placeholder),
+                None => VMetrics::from(scale_font).with_line_height(line_height, &scale_font),
+            };
+            max_v_metrics = max_v_metrics.max(v_metrics.shifted(baseline_shift));
 
-            max_v_metrics = max_v_metrics.max(scale_font.into());
+            let mut removed = false;
+            if self.white_space.collapses(space, newline) {
+                // collapsed newlines are breakable spaces
+                line_break = line_break.map(|lbreak| LineBreak::Soft(lbreak.offset()));
+                if self.collapse_space {
+                    removed = true;
+                    control = true;
+                } else {
+                    // lay out the first of the run as a single space
+                    glyph = scale_font.scaled_glyph(' ');
+                    control = false;
+                    advance = None;
+                }
+                tab = false;
+                self.collapse_space = true;
+            } else {
+                self.collapse_space = false;
+            }
+            collapsed &= removed;
 
-            if let Some(id) = last_glyph_id.take() {
-                caret += scale_font.kern(id, glyph.id);
+            if !removed {
+                if let Some(id) = last_glyph_id.take() {
+                    caret += scale_font.kern(id, glyph.id);
+                }
+                // shaped glyphs have kerning applied to their advance
+                if advance.is_none() {
+                    last_glyph_id = Some(glyph.id);
+                }
             }
-            last_glyph_id = Some(glyph.id);
 
             if !control {
-                let advance_width = scale_font.h_advance(glyph.id);
+                let advance_width = advance.unwrap_or_else(|| scale_font.h_advance(glyph.id));
 
-                glyph.position = point(caret, 0.0);
+                glyph.position.x += caret;
+                glyph.position.y -= baseline_shift;
                 glyphs.push(SectionGlyph {
                     section_index,
                     byte_index,
                     glyph,
                     font_id,
+                    orientation: VerticalOrientation::Upright,
                 });
                 caret += advance_width;
 
@@ -133,24 +384,71 @@ where
                     // not an invisible trail
                     caret_no_trail = caret;
                 }
+                caret += spacing;
+            }
+
+            if tab {
+                // tabs end words, the line caret then advances to the next tab stop
+                tab_space = Some(scale_font.h_advance(scale_font.glyph_id(' ')));
             }
 
             if let Some(lbreak) = line_break {
-                // simulate hard-break at end of all sections
-                if matches!(lbreak, LineBreak::Hard(_)) || self.characters.peek().is_none() {
-                    hard_break = true
+                hard_break = matches!(lbreak, LineBreak::Hard(_));
+                if hard_break {
+                    self.collapse_space = true;
+                } else if collapsed {
+                    // removed spaces join the following word
+                    continue;
+                }
+                if let LineBreak::Hyphen(_) = lbreak {
+                    let id = scale_font.glyph_id('-');
+                    let glyph = SectionGlyph {
+                        section_index,
+                        byte_index: SectionGlyph::SYNTHETIC_BYTE_INDEX,
+                        glyph: id.with_scale_and_position(
+                            scale_font.scale,
+                            point(caret_no_trail, -baseline_shift),
+                        ),
+                        font_id,
+                        orientation: VerticalOrientation::Upright,
+                    };
+                    hyphen = Some((glyph, scale_font.h_advance(id)));
                 }
                 break;
             }
+            if tab_space.is_some() {
+                break;
+            }
+            // placeholders are words of their own
+            if self
+                .characters
+                .peek()
+                .is_some_and(|c| c.placeholder.is_some())
+            {
+                break;
+            }
         }
 
         if progress {
+            // simulate hard-break at end of all sections
+            hard_break |= self.characters.peek().is_none();
+
+            // collapsible spaces at the end of a line are removed
+            let layout_width = match hard_break && self.white_space.collapses(true, false) {
+                true => caret_no_trail,
+                false => caret,
+            };
+
             return Some(Word {
                 glyphs,
-                layout_width: caret,
+                start: start.unwrap_or_default(),
+                layout_width,
                 layout_width_no_trail: caret_no_trail,
                 hard_break,
+                hyphen,
+                tab: tab_space,
                 max_v_metrics,
+                line_end: false,
             });
         }
 
@@ -158,10 +456,136 @@ where
     }
 }
 
-impl<'a, L, F, S> FusedIterator for Words<'a, '_, L, F, S>
+impl<'b, F, C> FusedIterator for Words<'b, F, C>
 where
-    L: LineBreaker,
-    F: Font,
-    S: Iterator<Item = SectionText<'a>>,
+    F: Font + 'b,
+    C: FusedIterator<Item = Character<'b, F>>,
 {
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        FontFallbacks, GlyphPositioner, Layout, Placeholder, SectionGeometry, SectionText,
+        WhiteSpace,
+    };
+    use ab_glyph::*;
+    use approx::assert_relative_eq;
+    use std::sync::LazyLock;
+
+    static A_FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap()
+    });
+
+    #[test]
+    fn wrapped_placeholder_taller_than_line() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        let icon = Placeholder {
+            width: 30.0,
+            height: 50.0,
+            baseline_offset: 10.0,
+        };
+        let (glyphs, lines) = Layout::default_wrap().calculate_glyphs_and_lines(
+            &[&*A_FONT],
+            &FontFallbacks::default(),
+            &SectionGeometry {
+                bounds: (advance * 3.0 + 20.0, f32::INFINITY),
+                ..<_>::default()
+            },
+            &[
+                SectionText {
+                    text: "aa ",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                },
+                SectionText {
+                    text: "\u{FFFC}",
+                    scale: PxScale::from(20.0),
+                    placeholder: Some(icon),
+                    ..<_>::default()
+                },
+                SectionText {
+                    text: "a",
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                },
+            ],
+        );
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].glyphs, 3..5);
+        assert!(glyphs[3].is_placeholder());
+
+        // only the placeholder's line grows to fit it, above & below the baseline
+        assert_relative_eq!(lines[0].ascent, sfont.ascent());
+        assert_relative_eq!(lines[0].descent, sfont.descent());
+        assert_relative_eq!(lines[1].ascent, 40.0);
+        assert_relative_eq!(lines[1].descent, -10.0);
+
+        // the wrapped placeholder rect starts the line & fills its height
+        let rect = icon.rect(&glyphs[3]);
+        assert_relative_eq!(rect.min.x, 0.0);
+        assert_relative_eq!(rect.min.y, lines[1].baseline - lines[1].ascent);
+        assert_relative_eq!(rect.max.y, lines[1].baseline - lines[1].descent);
+        assert!(rect.min.y >= lines[0].baseline - lines[0].descent);
+        assert_relative_eq!(glyphs[4].glyph.position.x, rect.max.x);
+    }
+
+    /// Returns the byte index of each glyph & the start byte index of each line.
+    fn layout_bytes(white_space: WhiteSpace, text: &str, bound_w: f32) -> (Vec<usize>, Vec<usize>) {
+        let (glyphs, lines) = Layout::default_wrap()
+            .white_space(white_space)
+            .calculate_glyphs_and_lines(
+                &[&*A_FONT],
+                &FontFallbacks::default(),
+                &SectionGeometry {
+                    bounds: (bound_w, f32::INFINITY),
+                    ..<_>::default()
+                },
+                &[SectionText {
+                    text,
+                    scale: PxScale::from(20.0),
+                    ..<_>::default()
+                }],
+            );
+        (
+            glyphs.iter().map(|sg| sg.byte_index).collect(),
+            lines.iter().map(|l| l.start.1).collect(),
+        )
+    }
+
+    #[test]
+    fn crlf_single_line_break() {
+        for white_space in [WhiteSpace::PreLine, WhiteSpace::PreWrap, WhiteSpace::Pre] {
+            // "\r\n" is one break without glyphs
+            let (bytes, starts) = layout_bytes(white_space, "aa\r\nbb", f32::INFINITY);
+            assert_eq!(bytes, [0, 1, 4, 5], "{white_space:?}");
+            assert_eq!(starts, [0, 4], "{white_space:?}");
+
+            let (bytes, starts) = layout_bytes(white_space, "aa\r\n\r\nbb", f32::INFINITY);
+            assert_eq!(bytes, [0, 1, 6, 7], "{white_space:?}");
+            assert_eq!(starts, [0, 4, 6], "{white_space:?}");
+        }
+
+        // or a single collapsible space
+        let (bytes, starts) = layout_bytes(WhiteSpace::Normal, "aa\r\nbb", f32::INFINITY);
+        assert_eq!(bytes, [0, 1, 2, 4, 5]);
+        assert_eq!(starts, [0]);
+    }
+
+    #[test]
+    fn wrapped_line_leading_spaces() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        for white_space in [WhiteSpace::Normal, WhiteSpace::PreLine] {
+            // spaces at the wrap are removed, the next line starts at its first word
+            let (bytes, starts) = layout_bytes(white_space, "aa    bb  cc", advance * 3.5);
+            assert_eq!(bytes, [0, 1, 2, 6, 7, 8, 10, 11], "{white_space:?}");
+            assert_eq!(starts, [0, 6, 10], "{white_space:?}");
+        }
+
+        // preserved spaces hang at the end of the line instead
+        let (bytes, starts) = layout_bytes(WhiteSpace::PreWrap, "aa    bb", advance * 3.5);
+        assert_eq!(bytes, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(starts, [0, 6]);
+    }
+}